@@ -4,13 +4,19 @@
 //! - Single-call mode: Send all files in one API call (efficient for cloud models)
 //! - Tool-calling mode: LLM explores with tools (for capable local models)
 
+use crate::agent::provider::{build_provider, ChatOptions, ChatProvider, Provider, StreamEvent};
 use crate::agent::tools::{get_tool_definitions, ReportedIssue, ToolCall, ToolExecutor};
+use crate::cache::ResponseCache;
 use crate::scanner::{FileScanner, ScanConfig};
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
 use tracing::{debug, info, warn};
 
 /// Configuration for the agent.
@@ -25,6 +31,27 @@ pub struct AgentConfig {
     pub single_call_mode: bool,
     /// Max tool results to keep in context (sliding window)
     pub max_context_messages: usize,
+    /// Which chat-completion backend to use.
+    pub provider: Provider,
+    /// API key for cloud providers (unused by Ollama).
+    pub api_key: Option<String>,
+    /// Max bytes of file content packed into a single-call prompt chunk,
+    /// used as a rough proxy for the model's context window.
+    pub chunk_byte_budget: usize,
+    /// Max number of single-call chunks analyzed concurrently.
+    pub max_concurrency: usize,
+    /// Context window (`num_ctx`) to request from Ollama. `None` leaves it
+    /// at the server's default (4096 as of this writing), which silently
+    /// truncates large single-call prompts.
+    pub context_length: Option<u32>,
+    /// Timeout for a single chunk's LLM call in single-call mode (a chunk is
+    /// one or more files grouped by `chunk_byte_budget`). Separate from the
+    /// overall HTTP client timeout: this bounds one pathological file so it
+    /// can't burn the whole run, rather than the whole request.
+    pub file_timeout_seconds: u64,
+    /// Retries after the first attempt before a chunk that keeps timing out
+    /// is given up on. `0` means a single attempt, no retries.
+    pub file_retries: usize,
 }
 
 impl Default for AgentConfig {
@@ -37,6 +64,13 @@ impl Default for AgentConfig {
             timeout_seconds: 300,
             single_call_mode: false,
             max_context_messages: 10, // Keep last 10 tool results
+            provider: Provider::Ollama,
+            api_key: None,
+            chunk_byte_budget: 24_000, // ~6k tokens at a 4-bytes-per-token rule of thumb
+            max_concurrency: num_cpus::get(),
+            context_length: None,
+            file_timeout_seconds: 120,
+            file_retries: 2,
         }
     }
 }
@@ -58,50 +92,70 @@ pub struct ToolCallMessage {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCallFunction {
     pub name: String,
+    /// Ollama sends a nested JSON object here; OpenAI-compatible APIs send a
+    /// JSON-encoded string instead, so accept either.
+    #[serde(deserialize_with = "value_or_json_string")]
     pub arguments: Value,
 }
 
-/// Ollama chat API request.
-#[derive(Debug, Serialize)]
-struct OllamaChatRequest {
-    model: String,
-    messages: Vec<ChatMessage>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    tools: Vec<Value>,
-    stream: bool,
-    options: OllamaOptions,
+/// Deserialize a field that may be a JSON value or a JSON-encoded string.
+fn value_or_json_string<'de, D>(deserializer: D) -> std::result::Result<Value, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = Value::deserialize(deserializer)?;
+    match raw {
+        Value::String(s) => Ok(serde_json::from_str(&s).unwrap_or(Value::String(s))),
+        other => Ok(other),
+    }
 }
 
-#[derive(Debug, Serialize)]
-struct OllamaOptions {
-    temperature: f32,
+/// A provider-agnostic chat response: the assistant's text plus any tool calls.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMessage {
+    pub content: String,
+    pub tool_calls: Option<Vec<ToolCallMessage>>,
 }
 
-/// Ollama chat API response.
-#[derive(Debug, Deserialize)]
-struct OllamaChatResponse {
-    message: ResponseMessage,
-    #[allow(dead_code)] // Response field, used for future stream handling
-    done: bool,
+/// A file that analysis gave up on after exhausting `file_retries`, almost
+/// always because its chunk kept exceeding `file_timeout_seconds`. Single-call
+/// mode groups small files together, so one timed-out chunk can cover several
+/// of these at once.
+#[derive(Debug, Clone)]
+pub struct FailedFile {
+    pub path: String,
+    pub error: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct ResponseMessage {
-    #[allow(dead_code)] // Response field
-    role: String,
-    content: String,
-    #[serde(default)]
-    tool_calls: Option<Vec<ToolCallMessage>>,
+/// Outcome of analyzing one chunk: either the issues it reported, or the
+/// files it covers, given up on after the final timed-out attempt.
+enum ChunkOutcome {
+    Issues(Vec<ReportedIssue>),
+    Failed(Vec<FailedFile>),
 }
 
 /// The code analysis agent.
 pub struct CodeAnalysisAgent {
     config: AgentConfig,
-    http_client: reqwest::Client,
+    provider: Arc<dyn ChatProvider>,
     tool_executor: ToolExecutor,
     messages: Vec<ChatMessage>,
     repo_root: PathBuf,
     scan_config: ScanConfig,
+    /// Optional sink for incremental `StreamEvent`s; set via `set_stream_channel`.
+    stream_tx: Option<mpsc::UnboundedSender<StreamEvent>>,
+    /// Work units performed by the most recent `run_analysis` call: agent
+    /// iterations in tool-calling mode, or chunks analyzed in single-call
+    /// mode. Exposed for the bench harness's iteration-count metric.
+    last_run_iterations: usize,
+    /// Files given up on during the most recent single-call run after
+    /// exhausting `file_retries` on a timed-out chunk. Always empty in
+    /// tool-calling mode.
+    last_failed_files: Vec<FailedFile>,
+    /// Content-hash response cache consulted/populated by `analyze_files`
+    /// in single-call mode. `None` disables caching entirely (the default;
+    /// set via `with_cache`).
+    response_cache: Option<Arc<ResponseCache>>,
 }
 
 impl CodeAnalysisAgent {
@@ -118,13 +172,66 @@ impl CodeAnalysisAgent {
             .build()
             .expect("Failed to create HTTP client");
 
+        let provider: Arc<dyn ChatProvider> = Arc::from(build_provider(&config, http_client));
+
         Self {
             config,
-            http_client,
+            provider,
             tool_executor: ToolExecutor::new(repo_root.clone(), scan_config.clone()),
             messages: Vec::new(),
             repo_root: repo_root.clone(),
             scan_config,
+            stream_tx: None,
+            last_run_iterations: 0,
+            last_failed_files: Vec::new(),
+            response_cache: None,
+        }
+    }
+
+    /// Attach a content-hash response cache, consulted/populated by
+    /// `analyze_files` in single-call mode. `None` leaves caching disabled
+    /// (the default after `new`).
+    pub fn with_cache(mut self, cache: Option<ResponseCache>) -> Self {
+        self.response_cache = cache.map(Arc::new);
+        self
+    }
+
+    /// Work units performed by the most recent `run_analysis` call (agent
+    /// iterations, or chunks analyzed in single-call mode). `0` before any
+    /// run has completed.
+    pub fn last_run_iterations(&self) -> usize {
+        self.last_run_iterations
+    }
+
+    /// Files given up on during the most recent single-call run. Empty
+    /// before any run has completed, and always empty in tool-calling mode.
+    pub fn last_failed_files(&self) -> &[FailedFile] {
+        &self.last_failed_files
+    }
+
+    /// Subscribe to incremental streaming events (content deltas, tool-call
+    /// progress) emitted while the agent talks to the provider. Without a
+    /// subscriber, the agent falls back to blocking on the whole completion.
+    pub fn set_stream_channel(&mut self, sender: mpsc::UnboundedSender<StreamEvent>) {
+        self.stream_tx = Some(sender);
+    }
+
+    /// Confirm the backend is reachable and the configured model is actually
+    /// installed before running any analysis. Call this before `run_analysis`
+    /// so a missing model surfaces as a clear error up front, rather than
+    /// failing deep inside `chat_with_tools`.
+    pub async fn preflight(&self) -> Result<()> {
+        self.provider.validate_model().await
+    }
+
+    /// Build the per-request sampling options shared by every chat call.
+    fn chat_options(&self) -> ChatOptions {
+        ChatOptions {
+            temperature: self.config.temperature,
+            context_length: self.config.context_length,
+            top_p: None,
+            seed: None,
+            num_predict: None,
         }
     }
 
@@ -137,7 +244,8 @@ impl CodeAnalysisAgent {
         }
     }
 
-    /// Single-call mode: Read all files, send in ONE API call
+    /// Single-call mode: chunk files into context-sized prompts and analyze
+    /// chunks concurrently, merging and deduplicating the resulting issues.
     async fn run_single_call_analysis(&mut self) -> Result<Vec<ReportedIssue>> {
         info!("Starting single-call analysis (efficient mode)");
 
@@ -151,119 +259,182 @@ impl CodeAnalysisAgent {
             return Ok(vec![]);
         }
 
-        // Build the prompt with all file contents
-        let mut prompt = String::new();
-        prompt.push_str("Analyze the following code files and report any issues.\n\n");
-        prompt.push_str("For each issue found, output it in this exact JSON format:\n");
-        prompt.push_str(r#"{"file_path": "path/to/file.rs", "line_number": 42, "severity": "high", "category": "security", "title": "Issue Title", "description": "Description", "suggestion": "How to fix"}"#);
-        prompt.push_str("\n\nOutput one JSON object per line for each issue. Only output JSON, no other text.\n\n");
-        prompt.push_str("=== FILES TO ANALYZE ===\n\n");
-
-        for (path, content) in &files {
-            prompt.push_str(&format!("### FILE: {}\n```\n{}\n```\n\n", path, content));
-        }
+        let mut files: Vec<(String, String)> = files.into_iter().collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
 
-        prompt.push_str("=== END OF FILES ===\n\n");
-        prompt.push_str("Now analyze and output issues as JSON (one per line):");
+        self.analyze_files(files).await
+    }
 
-        // Send single API call
-        info!("Sending single API request with all files...");
-        let response = self.send_simple_prompt(&prompt).await?;
+    /// Analyze a caller-supplied set of files (path, content) using the same
+    /// chunked, concurrent single-call pipeline as `run_single_call_analysis`,
+    /// without re-scanning the whole repository. Used for incremental modes
+    /// (e.g. `--watch`) that only want to re-analyze files known to have
+    /// changed.
+    pub(crate) async fn analyze_files(
+        &mut self,
+        files: Vec<(String, String)>,
+    ) -> Result<Vec<ReportedIssue>> {
+        if files.is_empty() {
+            return Ok(vec![]);
+        }
 
-        // Parse issues from response
-        let issues = self.parse_issues_from_response(&response);
-        info!("Parsed {} issues from response", issues.len());
+        // Serve whatever we can from the response cache first, so only
+        // files that actually changed (or were never analyzed under this
+        // model/temperature) are sent to the LLM at all.
+        let (mut all_issues, files) = self.split_cached_files(files);
 
-        Ok(issues)
-    }
+        if files.is_empty() {
+            info!("All files served from the response cache; no LLM calls needed");
+            self.last_run_iterations = 0;
+            self.last_failed_files = Vec::new();
+            return Ok(dedupe_issues(all_issues));
+        }
 
+        // Keep the to-be-analyzed files' content around for `put`-ing
+        // results back into the cache once chunks finish, since
+        // `chunk_files_by_byte_budget` consumes `files` by value.
+        let files_for_cache = self.response_cache.is_some().then(|| files.clone());
 
-    /// Send a simple prompt (no tools) and get response
-    async fn send_simple_prompt(&self, prompt: &str) -> Result<String> {
-        let url = format!("{}/api/chat", self.config.ollama_url);
-
-        let request = OllamaChatRequest {
-            model: self.config.model_name.clone(),
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: SINGLE_CALL_SYSTEM_PROMPT.to_string(),
-                    tool_calls: None,
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: prompt.to_string(),
-                    tool_calls: None,
-                },
-            ],
-            tools: vec![],
-            stream: false,
-            options: OllamaOptions {
-                temperature: self.config.temperature,
-            },
-        };
+        let chunks = chunk_files_by_byte_budget(files, self.config.chunk_byte_budget);
+        let total_chunks = chunks.len();
+        let concurrency = self.config.max_concurrency.max(1);
+        info!(
+            "Split files into {} chunk(s), analyzing up to {} concurrently",
+            total_chunks, concurrency
+        );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    anyhow::anyhow!("Request timed out after {}s", self.config.timeout_seconds)
-                } else if e.is_connect() {
-                    anyhow::anyhow!("Cannot connect to Ollama at {}", self.config.ollama_url)
-                } else {
-                    anyhow::anyhow!("Failed to send request: {}", e)
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut join_set = JoinSet::new();
+        let file_timeout = Duration::from_secs(self.config.file_timeout_seconds);
+        let file_retries = self.config.file_retries;
+
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let provider = Arc::clone(&self.provider);
+            let options = self.chat_options();
+            let messages = single_call_chunk_messages(&chunk);
+            let paths: Vec<String> = chunk.iter().map(|(path, _)| path.clone()).collect();
+
+            if let Some(context_length) = self.config.context_length {
+                let estimated_tokens = estimate_message_tokens(&messages);
+                if estimated_tokens as u32 > context_length {
+                    warn!(
+                        "Chunk {}/{} is ~{} tokens, which overflows the configured context \
+                         window ({} tokens); Ollama will silently truncate it. Lower \
+                         chunk_byte_budget or raise context_length.",
+                        index + 1,
+                        total_chunks,
+                        estimated_tokens,
+                        context_length
+                    );
                 }
-            })?;
+            }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!("Ollama API error {}: {}", status, body));
+            let semaphore = Arc::clone(&semaphore);
+            let stream_tx = self.stream_tx.clone();
+
+            join_set.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .context("Chunk analysis semaphore was closed")?;
+                debug!("Analyzing chunk {}/{}", index + 1, total_chunks);
+                run_chunk_with_timeout(
+                    provider,
+                    paths,
+                    messages,
+                    options,
+                    stream_tx,
+                    file_timeout,
+                    file_retries,
+                    index,
+                    total_chunks,
+                )
+                .await
+            });
         }
 
-        let chat_response: OllamaChatResponse = response
-            .json()
-            .await
-            .context("Failed to parse Ollama response")?;
-
-        Ok(chat_response.message.content)
-    }
+        let mut failed_files = Vec::new();
+        while let Some(result) = join_set.join_next().await {
+            match result.context("Chunk analysis task panicked")?? {
+                ChunkOutcome::Issues(issues) => all_issues.extend(issues),
+                ChunkOutcome::Failed(files) => failed_files.extend(files),
+            }
+        }
 
-    /// Parse issues from LLM response (JSON lines format)
-    fn parse_issues_from_response(&self, response: &str) -> Vec<ReportedIssue> {
-        let mut issues = Vec::new();
+        let issues = dedupe_issues(all_issues);
+        info!(
+            "Parsed {} unique issues from {} chunk(s), {} file(s) failed",
+            issues.len(),
+            total_chunks,
+            failed_files.len()
+        );
 
-        for line in response.lines() {
-            let line = line.trim();
-            if line.is_empty() || !line.starts_with('{') {
-                continue;
+        if let (Some(cache), Some(files_for_cache)) = (&self.response_cache, &files_for_cache) {
+            let failed_paths: HashSet<&str> =
+                failed_files.iter().map(|f| f.path.as_str()).collect();
+            for (path, content) in files_for_cache {
+                if failed_paths.contains(path.as_str()) {
+                    continue;
+                }
+                let file_issues: Vec<ReportedIssue> =
+                    issues.iter().filter(|issue| &issue.file_path == path).cloned().collect();
+                if let Err(e) = cache.put(&self.config.model_name, self.config.temperature, path, content, &file_issues) {
+                    warn!("Failed to write response cache entry for {}: {}", path, e);
+                }
             }
+        }
 
-            // Try to parse as JSON
-            if let Ok(json) = serde_json::from_str::<Value>(line) {
-                if let Some(issue) = self.json_to_issue(&json) {
-                    issues.push(issue);
+        self.last_run_iterations = total_chunks;
+        self.last_failed_files = failed_files;
+        Ok(issues)
+    }
+
+    /// Partition `files` into already-cached issues and the files that
+    /// still need an LLM call, consulting `self.response_cache` (a no-op,
+    /// returning everything as still-needing-analysis, when caching is
+    /// disabled).
+    fn split_cached_files(
+        &self,
+        files: Vec<(String, String)>,
+    ) -> (Vec<ReportedIssue>, Vec<(String, String)>) {
+        let Some(cache) = &self.response_cache else {
+            return (Vec::new(), files);
+        };
+
+        let mut cached_issues = Vec::new();
+        let mut remaining = Vec::new();
+        let mut hits = 0usize;
+        for (path, content) in files {
+            match cache.get(&self.config.model_name, self.config.temperature, &path, &content) {
+                Some(issues) => {
+                    hits += 1;
+                    cached_issues.extend(issues);
                 }
+                None => remaining.push((path, content)),
             }
         }
 
-        issues
+        if hits > 0 {
+            debug!(
+                "Response cache: {} file(s) hit, {} file(s) need analysis",
+                hits,
+                remaining.len()
+            );
+        }
+
+        (cached_issues, remaining)
     }
 
-    fn json_to_issue(&self, json: &Value) -> Option<ReportedIssue> {
-        Some(ReportedIssue {
-            file_path: json["file_path"].as_str()?.to_string(),
-            line_number: json["line_number"].as_u64().unwrap_or(0) as usize,
-            severity: json["severity"].as_str().unwrap_or("medium").to_string(),
-            category: json["category"].as_str().unwrap_or("general").to_string(),
-            title: json["title"].as_str().unwrap_or("Issue").to_string(),
-            description: json["description"].as_str().unwrap_or("").to_string(),
-            suggestion: json["suggestion"].as_str().unwrap_or("").to_string(),
-        })
+    /// Send a simple prompt (no tools) and get response. Kept for callers
+    /// that want a single one-off completion outside the chunked pipeline.
+    #[allow(dead_code)] // small, useful primitive not currently exercised by the CLI
+    async fn send_simple_prompt(&self, prompt: &str) -> Result<String> {
+        let messages = single_call_prompt_messages(prompt);
+        let options = self.chat_options();
+
+        let response = self.provider.chat(&messages, &[], &options).await?;
+
+        Ok(response.content)
     }
 
     /// Tool-calling mode: LLM uses tools to explore repository
@@ -284,9 +455,77 @@ impl CodeAnalysisAgent {
             tool_calls: None,
         });
 
-        // Agent loop
+        self.drive_agent_loop().await
+    }
+
+    /// Incrementally re-analyzes `changed_paths` for watch-driven modes
+    /// (see `watch::run_watch`), without re-scanning or re-reading the rest
+    /// of the repository.
+    ///
+    /// In single-call mode this just delegates to `analyze_files`, which
+    /// never goes through `ToolExecutor` and so has no stale cache to
+    /// invalidate. In tool-calling mode, it widens `changed_paths` to any
+    /// file that textually references them (see `search::find_dependents`),
+    /// invalidates the tool executor's cached reads/searches and any
+    /// issues already reported for that set, then drives one more agent
+    /// loop scoped to re-checking just those files.
+    pub(crate) async fn rerun_changed_files(&mut self, changed_paths: &[String]) -> Result<Vec<ReportedIssue>> {
+        if changed_paths.is_empty() {
+            return Ok(self.tool_executor.get_issues().to_vec());
+        }
+
+        if self.config.single_call_mode {
+            let files: Vec<(String, String)> = changed_paths
+                .iter()
+                .filter_map(|path| {
+                    std::fs::read_to_string(self.repo_root.join(path))
+                        .ok()
+                        .map(|content| (path.clone(), content))
+                })
+                .collect();
+            return self.analyze_files(files).await;
+        }
+
+        if self.messages.is_empty() {
+            self.messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: AGENT_SYSTEM_PROMPT.to_string(),
+                tool_calls: None,
+            });
+        }
+
+        let scanner = FileScanner::new(self.repo_root.clone(), self.scan_config.clone());
+        let dependents = crate::agent::search::find_dependents(&self.repo_root, &scanner, changed_paths);
+
+        let mut affected: Vec<String> = changed_paths.to_vec();
+        affected.extend(dependents);
+        affected.sort();
+        affected.dedup();
+
+        self.tool_executor.invalidate_paths(&affected);
+
+        self.messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: format!(
+                "These files changed on disk and must be re-checked from scratch (earlier \
+                 findings for them no longer apply): {}. Re-read them, report any issues you \
+                 find, then call finish_analysis.",
+                affected.join(", ")
+            ),
+            tool_calls: None,
+        });
+
+        self.drive_agent_loop().await
+    }
+
+    /// Runs the tool-calling agent loop (chat, dispatch tool calls, repeat
+    /// until `finish_analysis` or `max_iterations`) over whatever's already
+    /// in `self.messages`. Shared by the initial full-repo pass and
+    /// `rerun_changed_files`'s incremental passes.
+    async fn drive_agent_loop(&mut self) -> Result<Vec<ReportedIssue>> {
         for iteration in 0..self.config.max_iterations {
             debug!("Agent iteration {}", iteration + 1);
+            self.last_run_iterations = iteration + 1;
 
             // Get LLM response
             let response = self.chat_with_tools().await?;
@@ -327,6 +566,7 @@ impl CodeAnalysisAgent {
 
                     // Sliding window: prune old tool messages to save context
                     self.prune_old_messages();
+                    self.refresh_explored_summary();
 
                     info!("Tool {} executed", tool_name);
                 }
@@ -384,69 +624,274 @@ impl CodeAnalysisAgent {
         }
     }
 
-    /// Send a chat request with tools to Ollama.
-    async fn chat_with_tools(&mut self) -> Result<ResponseMessage> {
-        let url = format!("{}/api/chat", self.config.ollama_url);
+    /// Re-stamp the system message with a compact summary of what's already
+    /// been explored (files read, searches run), so that pruning the
+    /// sliding window doesn't erase the agent's map of the repository along
+    /// with the raw tool results it summarizes.
+    fn refresh_explored_summary(&mut self) {
+        let summary = self.tool_executor.explored_summary();
+
+        if let Some(system_msg) = self.messages.first_mut() {
+            system_msg.content = match summary {
+                Some(summary) => format!(
+                    "{}\n\n## Context From Earlier Iterations\n\n{}",
+                    AGENT_SYSTEM_PROMPT, summary
+                ),
+                None => AGENT_SYSTEM_PROMPT.to_string(),
+            };
+        }
+    }
 
+    /// Send a chat request with tools to the configured provider. Tool calls
+    /// are only dispatched to `ToolExecutor` once fully assembled (i.e. after
+    /// this returns), even when the underlying provider streams fragments.
+    async fn chat_with_tools(&mut self) -> Result<ResponseMessage> {
         let tools = get_tool_definitions();
         let tools_json: Vec<Value> = tools
             .iter()
             .map(|t| serde_json::to_value(t).unwrap())
             .collect();
 
-        let request = OllamaChatRequest {
-            model: self.config.model_name.clone(),
-            messages: self.messages.clone(),
-            tools: tools_json,
-            stream: false,
-            options: OllamaOptions {
-                temperature: self.config.temperature,
-            },
-        };
+        let options = self.chat_options();
 
         debug!("Sending chat request with {} messages", self.messages.len());
 
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    anyhow::anyhow!(
-                        "Request timed out after {}s. Try a different model.",
-                        self.config.timeout_seconds
-                    )
-                } else if e.is_connect() {
-                    anyhow::anyhow!(
-                        "Cannot connect to Ollama at {}. Is Ollama running?",
-                        self.config.ollama_url
-                    )
-                } else {
-                    anyhow::anyhow!("Failed to send request: {}", e)
-                }
-            })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!("Ollama API error {}: {}", status, body));
-        }
-
-        let chat_response: OllamaChatResponse = response
-            .json()
-            .await
-            .context("Failed to parse Ollama response")?;
+        let provider = &self.provider;
+        let messages = &self.messages;
+        let response = if let Some(tx) = self.stream_tx.clone() {
+            let mut on_event = move |event: StreamEvent| {
+                let _ = tx.send(event);
+            };
+            provider
+                .chat_stream(messages, &tools_json, &options, &mut on_event)
+                .await?
+        } else {
+            provider.chat(messages, &tools_json, &options).await?
+        };
 
         self.messages.push(ChatMessage {
             role: "assistant".to_string(),
-            content: chat_response.message.content.clone(),
-            tool_calls: chat_response.message.tool_calls.clone(),
+            content: response.content.clone(),
+            tool_calls: response.tool_calls.clone(),
         });
 
-        Ok(chat_response.message)
+        Ok(response)
+    }
+}
+
+/// Parse issues from a full LLM response (JSON lines format).
+fn parse_issues_from_response(response: &str) -> Vec<ReportedIssue> {
+    response
+        .lines()
+        .filter_map(|line| parse_issue_line(line.trim()))
+        .collect()
+}
+
+/// Parse a single JSON-line issue, ignoring non-JSON or malformed lines.
+fn parse_issue_line(line: &str) -> Option<ReportedIssue> {
+    if line.is_empty() || !line.starts_with('{') {
+        return None;
+    }
+
+    let json: Value = serde_json::from_str(line).ok()?;
+    Some(ReportedIssue {
+        file_path: json["file_path"].as_str()?.to_string(),
+        line_number: json["line_number"].as_u64().unwrap_or(0) as usize,
+        severity: json["severity"].as_str().unwrap_or("medium").to_string(),
+        category: json["category"].as_str().unwrap_or("general").to_string(),
+        title: json["title"].as_str().unwrap_or("Issue").to_string(),
+        description: json["description"].as_str().unwrap_or("").to_string(),
+        suggestion: json["suggestion"].as_str().unwrap_or("").to_string(),
+        code_snippet: None,
+    })
+}
+
+/// Rough token estimate for a set of chat messages, using a 4-bytes-per-token
+/// rule of thumb. Good enough to warn about context-window overflow; not
+/// meant to match any particular tokenizer exactly.
+fn estimate_message_tokens(messages: &[ChatMessage]) -> usize {
+    messages.iter().map(|m| m.content.len() / 4).sum()
+}
+
+/// Group files into chunks whose combined byte size stays within `budget`,
+/// used as a rough proxy for the model's context window. A single file
+/// larger than the budget still gets its own chunk rather than being split.
+fn chunk_files_by_byte_budget(
+    files: Vec<(String, String)>,
+    budget: usize,
+) -> Vec<Vec<(String, String)>> {
+    let mut chunks = Vec::new();
+    let mut current = Vec::new();
+    let mut current_size = 0usize;
+
+    for file in files {
+        let file_size = file.1.len();
+
+        if !current.is_empty() && current_size + file_size > budget {
+            chunks.push(std::mem::take(&mut current));
+            current_size = 0;
+        }
+
+        current_size += file_size;
+        current.push(file);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// Build the system + user messages for a single-call prompt.
+fn single_call_prompt_messages(prompt: &str) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: SINGLE_CALL_SYSTEM_PROMPT.to_string(),
+            tool_calls: None,
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+            tool_calls: None,
+        },
+    ]
+}
+
+/// Build the single-call prompt messages for one chunk of files.
+fn single_call_chunk_messages(chunk: &[(String, String)]) -> Vec<ChatMessage> {
+    let mut prompt = String::new();
+    prompt.push_str("Analyze the following code files and report any issues.\n\n");
+    prompt.push_str("For each issue found, output it in this exact JSON format:\n");
+    prompt.push_str(r#"{"file_path": "path/to/file.rs", "line_number": 42, "severity": "high", "category": "security", "title": "Issue Title", "description": "Description", "suggestion": "How to fix"}"#);
+    prompt.push_str("\n\nOutput one JSON object per line for each issue. Only output JSON, no other text.\n\n");
+    prompt.push_str("=== FILES TO ANALYZE ===\n\n");
+
+    for (path, content) in chunk {
+        prompt.push_str(&format!("### FILE: {}\n```\n{}\n```\n\n", path, content));
+    }
+
+    prompt.push_str("=== END OF FILES ===\n\n");
+    prompt.push_str("Now analyze and output issues as JSON (one per line):");
+
+    single_call_prompt_messages(&prompt)
+}
+
+/// Run one single-call chunk to completion, parsing issue JSON lines live as
+/// they stream in when `stream_tx` is set, falling back to a single blocking
+/// call otherwise. Runs as an owned, 'static future so it can be spawned
+/// concurrently across chunks via `JoinSet`.
+async fn run_single_call_chunk(
+    provider: Arc<dyn ChatProvider>,
+    messages: Vec<ChatMessage>,
+    options: ChatOptions,
+    stream_tx: Option<mpsc::UnboundedSender<StreamEvent>>,
+) -> Result<Vec<ReportedIssue>> {
+    let Some(tx) = stream_tx else {
+        let response = provider.chat(&messages, &[], &options).await?;
+        return Ok(parse_issues_from_response(&response.content));
+    };
+
+    let mut line_buffer = String::new();
+    let mut issues = Vec::new();
+    let mut on_event = |event: StreamEvent| {
+        if let StreamEvent::ContentDelta(ref delta) = event {
+            line_buffer.push_str(delta);
+            while let Some(pos) = line_buffer.find('\n') {
+                let line = line_buffer[..pos].trim().to_string();
+                line_buffer.drain(..=pos);
+                if let Some(issue) = parse_issue_line(&line) {
+                    issues.push(issue);
+                }
+            }
+        }
+        let _ = tx.send(event);
+    };
+
+    provider
+        .chat_stream(&messages, &[], &options, &mut on_event)
+        .await?;
+
+    if let Some(issue) = parse_issue_line(line_buffer.trim()) {
+        issues.push(issue);
     }
+
+    Ok(issues)
+}
+
+/// Run one chunk's LLM call bounded by `file_timeout`, retrying up to
+/// `file_retries` times after a timeout before giving up on the files it
+/// covers. A non-timeout error (e.g. a connection failure) is not retried
+/// here; it propagates immediately since it's unlikely to be specific to
+/// this chunk's content.
+#[allow(clippy::too_many_arguments)]
+async fn run_chunk_with_timeout(
+    provider: Arc<dyn ChatProvider>,
+    paths: Vec<String>,
+    messages: Vec<ChatMessage>,
+    options: ChatOptions,
+    stream_tx: Option<mpsc::UnboundedSender<StreamEvent>>,
+    file_timeout: Duration,
+    file_retries: usize,
+    chunk_index: usize,
+    total_chunks: usize,
+) -> Result<ChunkOutcome> {
+    let max_attempts = file_retries + 1;
+
+    for attempt in 1..=max_attempts {
+        let call = run_single_call_chunk(
+            Arc::clone(&provider),
+            messages.clone(),
+            options.clone(),
+            stream_tx.clone(),
+        );
+
+        match tokio::time::timeout(file_timeout, call).await {
+            Ok(result) => return Ok(ChunkOutcome::Issues(result?)),
+            Err(_) => {
+                warn!(
+                    "Chunk {}/{} ({}) timed out after {:?} (attempt {}/{})",
+                    chunk_index + 1,
+                    total_chunks,
+                    paths.join(", "),
+                    file_timeout,
+                    attempt,
+                    max_attempts
+                );
+            }
+        }
+    }
+
+    let error = format!(
+        "Analysis timed out after {} attempt(s) of {:?} each",
+        max_attempts, file_timeout
+    );
+    Ok(ChunkOutcome::Failed(
+        paths
+            .into_iter()
+            .map(|path| FailedFile {
+                path,
+                error: error.clone(),
+            })
+            .collect(),
+    ))
+}
+
+/// Deduplicate issues that share `(file_path, line_number, title)`, keeping
+/// the first occurrence.
+fn dedupe_issues(issues: Vec<ReportedIssue>) -> Vec<ReportedIssue> {
+    let mut seen = HashSet::new();
+    issues
+        .into_iter()
+        .filter(|issue| {
+            seen.insert((
+                issue.file_path.clone(),
+                issue.line_number,
+                issue.title.clone(),
+            ))
+        })
+        .collect()
 }
 
 /// System prompt for single-call mode
@@ -495,5 +940,144 @@ mod tests {
         let config = AgentConfig::default();
         assert_eq!(config.model_name, "llama3.2:latest");
         assert!(!config.single_call_mode);
+        assert_eq!(config.provider, Provider::Ollama);
+        assert!(config.api_key.is_none());
+        assert!(config.max_concurrency >= 1);
+        assert!(config.chunk_byte_budget > 0);
+        assert!(config.context_length.is_none());
+        assert!(config.file_timeout_seconds > 0);
+    }
+
+    #[test]
+    fn test_estimate_message_tokens() {
+        let messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: "x".repeat(400),
+            tool_calls: None,
+        }];
+        assert_eq!(estimate_message_tokens(&messages), 100);
+    }
+
+    #[test]
+    fn test_chunk_files_by_byte_budget() {
+        let files = vec![
+            ("a.rs".to_string(), "x".repeat(100)),
+            ("b.rs".to_string(), "x".repeat(100)),
+            ("c.rs".to_string(), "x".repeat(100)),
+        ];
+
+        let chunks = chunk_files_by_byte_budget(files, 150);
+        assert_eq!(chunks.len(), 3);
+
+        let files = vec![
+            ("a.rs".to_string(), "x".repeat(100)),
+            ("b.rs".to_string(), "x".repeat(100)),
+        ];
+        let chunks = chunk_files_by_byte_budget(files, 1000);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_dedupe_issues() {
+        let make_issue = |line: usize| ReportedIssue {
+            file_path: "a.rs".to_string(),
+            line_number: line,
+            severity: "medium".to_string(),
+            category: "general".to_string(),
+            title: "Duplicate".to_string(),
+            description: String::new(),
+            suggestion: String::new(),
+            code_snippet: None,
+        };
+
+        let issues = vec![make_issue(1), make_issue(1), make_issue(2)];
+        let deduped = dedupe_issues(issues);
+        assert_eq!(deduped.len(), 2);
+    }
+
+    /// A provider whose every `chat` call sleeps longer than the test's
+    /// configured timeout, used to exercise the give-up-after-N-retries path
+    /// without a real network call.
+    struct SlowProvider;
+
+    #[async_trait::async_trait]
+    impl ChatProvider for SlowProvider {
+        async fn chat(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: &[Value],
+            _options: &ChatOptions,
+        ) -> Result<ResponseMessage> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(ResponseMessage::default())
+        }
+    }
+
+    /// A provider that always responds immediately with one issue line.
+    struct FastProvider;
+
+    #[async_trait::async_trait]
+    impl ChatProvider for FastProvider {
+        async fn chat(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: &[Value],
+            _options: &ChatOptions,
+        ) -> Result<ResponseMessage> {
+            Ok(ResponseMessage {
+                content: r#"{"file_path": "a.rs", "line_number": 1, "severity": "high", "category": "bug", "title": "Found it", "description": "", "suggestion": ""}"#.to_string(),
+                tool_calls: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_chunk_with_timeout_gives_up_after_retries() {
+        let provider: Arc<dyn ChatProvider> = Arc::new(SlowProvider);
+        let outcome = run_chunk_with_timeout(
+            provider,
+            vec!["slow.rs".to_string()],
+            vec![],
+            ChatOptions::default(),
+            None,
+            Duration::from_millis(10),
+            1, // one retry => 2 attempts total
+            0,
+            1,
+        )
+        .await
+        .unwrap();
+
+        match outcome {
+            ChunkOutcome::Failed(files) => {
+                assert_eq!(files.len(), 1);
+                assert_eq!(files[0].path, "slow.rs");
+                assert!(files[0].error.contains("timed out"));
+            }
+            ChunkOutcome::Issues(_) => panic!("expected the chunk to be marked failed"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_chunk_with_timeout_succeeds_without_retrying() {
+        let provider: Arc<dyn ChatProvider> = Arc::new(FastProvider);
+        let outcome = run_chunk_with_timeout(
+            provider,
+            vec!["a.rs".to_string()],
+            vec![],
+            ChatOptions::default(),
+            None,
+            Duration::from_secs(5),
+            2,
+            0,
+            1,
+        )
+        .await
+        .unwrap();
+
+        match outcome {
+            ChunkOutcome::Issues(issues) => assert_eq!(issues.len(), 1),
+            ChunkOutcome::Failed(_) => panic!("expected the chunk to succeed"),
+        }
     }
 }
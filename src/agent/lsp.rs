@@ -0,0 +1,476 @@
+//! Minimal JSON-RPC client for talking to a language server (rust-analyzer,
+//! pyright, typescript-language-server, gopls), backing the `lsp_query`
+//! tool.
+//!
+//! This speaks the LSP wire format directly via `serde_json` rather than
+//! pulling in a full `lsp-types`/tower-lsp stack: requests/responses are
+//! plain `Content-Length`-framed JSON over the server's stdio, and only the
+//! handful of request shapes `lsp_query` needs (`initialize`, `hover`,
+//! `definition`, `references`, `documentSymbol`, and reading published
+//! `textDocument/publishDiagnostics` notifications) are modeled. The LSP
+//! protocol is 0-indexed for both lines and columns; the rest of this crate
+//! is 1-indexed, so every position crosses `to_lsp_position`/
+//! `from_lsp_position` at the boundary.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// How long a single blocking read (a request's reply, or a
+/// `diagnostics` query's notification) may take before `LspClient` gives
+/// up on a slow-starting or wedged language server. There's no existing
+/// `--timeout`/`--file-timeout` plumbing into `ToolExecutor`, so this is a
+/// fixed, generous bound rather than a configurable one.
+const LSP_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long `Drop` waits for a clean `shutdown`/`exit` handshake before
+/// giving up and killing the child outright.
+const LSP_SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// Picks the language server binary (and its args) for a given language
+/// name, as produced by `language_for_extension`.
+fn server_command_for_language(language: &str) -> Option<(&'static str, &'static [&'static str])> {
+    match language {
+        "Rust" => Some(("rust-analyzer", &[])),
+        "Python" => Some(("pyright-langserver", &["--stdio"])),
+        "JavaScript" | "TypeScript" => Some(("typescript-language-server", &["--stdio"])),
+        "Go" => Some(("gopls", &["serve"])),
+        _ => None,
+    }
+}
+
+/// Converts a 1-indexed (line, column) pair, as used elsewhere in this
+/// crate, into an LSP `Position` (0-indexed).
+fn to_lsp_position(line: usize, column: usize) -> Value {
+    json!({
+        "line": line.saturating_sub(1),
+        "character": column.saturating_sub(1),
+    })
+}
+
+/// Converts an LSP `Position` (0-indexed) back into the 1-indexed (line,
+/// column) pair the rest of this crate expects.
+fn from_lsp_position(position: &Value) -> (usize, usize) {
+    let line = position["line"].as_u64().unwrap_or(0) as usize + 1;
+    let character = position["character"].as_u64().unwrap_or(0) as usize + 1;
+    (line, character)
+}
+
+/// Renders a `file://` URI for `rel_path`, relative to `repo_root`.
+fn uri_for(repo_root: &Path, rel_path: &str) -> String {
+    format!("file://{}", repo_root.join(rel_path).display())
+}
+
+/// Strips a `file://` prefix back down to a path relative to `repo_root`,
+/// for formatting results as `file:line:column` the way the rest of this
+/// crate's tools do.
+fn rel_path_from_uri(repo_root: &Path, uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    Path::new(path)
+        .strip_prefix(repo_root)
+        .unwrap_or(Path::new(path))
+        .display()
+        .to_string()
+}
+
+/// Reads one `Content-Length`-framed JSON-RPC message from `stdout`,
+/// blocking for as long as the server takes. Run only on the reader
+/// thread spawned by `spawn_reader`, never directly from `LspClient`.
+fn read_framed_message(stdout: &mut BufReader<ChildStdout>) -> Result<Value> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        let bytes_read = stdout.read_line(&mut line)?;
+        if bytes_read == 0 {
+            bail!("Language server closed its stdout");
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length: ") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let content_length = content_length.context("Language server response missing Content-Length")?;
+    let mut buf = vec![0u8; content_length];
+    stdout.read_exact(&mut buf)?;
+    serde_json::from_slice(&buf).context("Failed to parse language server response")
+}
+
+/// Spawns the dedicated thread that owns `stdout` and feeds parsed
+/// messages back over a channel, so `LspClient::read_message` can bound
+/// its wait with `recv_timeout` instead of blocking directly on I/O that
+/// has no timeout of its own. The thread exits after the first read error
+/// (closed pipe, malformed frame), at which point the channel disconnects
+/// and subsequent `recv_timeout` calls fail with `Disconnected`.
+fn spawn_reader(mut stdout: BufReader<ChildStdout>) -> Receiver<Result<Value>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || loop {
+        let message = read_framed_message(&mut stdout);
+        let failed = message.is_err();
+        if tx.send(message).is_err() || failed {
+            break;
+        }
+    });
+    rx
+}
+
+/// A running language server process and the JSON-RPC plumbing to talk to
+/// it. Spawned lazily on the first `lsp_query` call and torn down when
+/// dropped.
+pub struct LspClient {
+    child: Child,
+    stdin: ChildStdin,
+    /// Fed by a dedicated reader thread (spawned alongside the child) so
+    /// that every blocking read against the server's stdout can be bounded
+    /// with `recv_timeout` instead of hanging the tool-calling loop on a
+    /// slow-starting or wedged server.
+    receiver: Receiver<Result<Value>>,
+    next_id: i64,
+    repo_root: std::path::PathBuf,
+}
+
+impl LspClient {
+    /// Spawns the language server for `language` and completes the
+    /// `initialize`/`initialized` handshake with `repo_root` as the
+    /// workspace root.
+    pub fn spawn(repo_root: &Path, language: &str) -> Result<Self> {
+        let (command, args) = server_command_for_language(language)
+            .ok_or_else(|| anyhow!("No language server configured for {}", language))?;
+
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to launch language server '{}'", command))?;
+
+        let stdin = child.stdin.take().context("Language server has no stdin")?;
+        let stdout = BufReader::new(child.stdout.take().context("Language server has no stdout")?);
+        let receiver = spawn_reader(stdout);
+
+        let mut client = Self {
+            child,
+            stdin,
+            receiver,
+            next_id: 1,
+            repo_root: repo_root.to_path_buf(),
+        };
+
+        let root_uri = format!("file://{}", repo_root.display());
+        client.request(
+            "initialize",
+            json!({
+                "processId": std::process::id(),
+                "rootUri": root_uri,
+                "capabilities": {},
+            }),
+        )?;
+        client.notify("initialized", json!({}))?;
+
+        Ok(client)
+    }
+
+    /// Sends a JSON-RPC request and blocks for its response, skipping over
+    /// any server-initiated notifications (e.g. `publishDiagnostics`) seen
+    /// in between -- except for `diagnostics` queries, which read those
+    /// notifications directly instead of calling this.
+    fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.get("id").and_then(Value::as_i64) == Some(id) {
+                if let Some(error) = message.get("error") {
+                    bail!("Language server error for {}: {}", method, error);
+                }
+                return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+            }
+            // Not our response (a notification, or a stale reply) -- keep reading.
+        }
+    }
+
+    /// Sends a JSON-RPC notification (no response expected).
+    fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+    }
+
+    fn write_message(&mut self, message: &Value) -> Result<()> {
+        let body = serde_json::to_string(message)?;
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Pulls the next parsed message off the reader thread's channel,
+    /// bounded by `LSP_READ_TIMEOUT` -- the actual blocking `read_line`/
+    /// `read_exact` loop runs on that thread, not here, so a slow or wedged
+    /// server can only ever stall this call for `LSP_READ_TIMEOUT`, not
+    /// forever.
+    fn read_message(&mut self) -> Result<Value> {
+        match self.receiver.recv_timeout(LSP_READ_TIMEOUT) {
+            Ok(message) => message,
+            Err(RecvTimeoutError::Timeout) => bail!(
+                "Language server did not respond within {:?}",
+                LSP_READ_TIMEOUT
+            ),
+            Err(RecvTimeoutError::Disconnected) => {
+                bail!("Language server connection closed unexpectedly")
+            }
+        }
+    }
+
+    /// `textDocument/hover`: type/signature information at `path:line:column`.
+    pub fn hover(&mut self, path: &str, line: usize, column: usize) -> Result<String> {
+        let result = self.request(
+            "textDocument/hover",
+            json!({
+                "textDocument": {"uri": uri_for(&self.repo_root, path)},
+                "position": to_lsp_position(line, column),
+            }),
+        )?;
+
+        match result.get("contents") {
+            Some(Value::Null) | None => Ok("No hover information available".to_string()),
+            Some(contents) => Ok(render_hover_contents(contents)),
+        }
+    }
+
+    /// `textDocument/definition`: where the symbol at `path:line:column` is
+    /// defined, as `file:line:column` entries.
+    pub fn definition(&mut self, path: &str, line: usize, column: usize) -> Result<String> {
+        let result = self.request(
+            "textDocument/definition",
+            json!({
+                "textDocument": {"uri": uri_for(&self.repo_root, path)},
+                "position": to_lsp_position(line, column),
+            }),
+        )?;
+
+        Ok(self.render_locations(&result))
+    }
+
+    /// `textDocument/references`: every usage of the symbol at
+    /// `path:line:column`, as `file:line:column` entries.
+    pub fn references(&mut self, path: &str, line: usize, column: usize) -> Result<String> {
+        let result = self.request(
+            "textDocument/references",
+            json!({
+                "textDocument": {"uri": uri_for(&self.repo_root, path)},
+                "position": to_lsp_position(line, column),
+                "context": {"includeDeclaration": true},
+            }),
+        )?;
+
+        Ok(self.render_locations(&result))
+    }
+
+    /// `textDocument/documentSymbol`: an outline of `path` (name, kind, line).
+    pub fn document_symbols(&mut self, path: &str) -> Result<String> {
+        let result = self.request(
+            "textDocument/documentSymbol",
+            json!({"textDocument": {"uri": uri_for(&self.repo_root, path)}}),
+        )?;
+
+        let symbols = result.as_array().cloned().unwrap_or_default();
+        if symbols.is_empty() {
+            return Ok("No symbols found".to_string());
+        }
+
+        let mut lines = Vec::new();
+        for symbol in &symbols {
+            let name = symbol.get("name").and_then(Value::as_str).unwrap_or("?");
+            let kind = symbol.get("kind").and_then(Value::as_u64).unwrap_or(0);
+            // `range` for a DocumentSymbol, `location.range` for the older SymbolInformation shape.
+            let range = symbol
+                .get("range")
+                .or_else(|| symbol.get("location").and_then(|l| l.get("range")));
+            let line = range
+                .and_then(|r| r.get("start"))
+                .map(from_lsp_position)
+                .map(|(line, _)| line)
+                .unwrap_or(0);
+            lines.push(format!("{}:{} {} (kind {})", path, line, name, kind));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Diagnostics the server has already published for `path`. Unlike the
+    /// other ops, this doesn't send a request: diagnostics arrive
+    /// unsolicited via `textDocument/publishDiagnostics`, so this opens the
+    /// document (triggering analysis) and waits for that notification.
+    pub fn diagnostics(&mut self, path: &str) -> Result<String> {
+        let uri = uri_for(&self.repo_root, path);
+        let content = std::fs::read_to_string(self.repo_root.join(path))
+            .with_context(|| format!("Failed to read {}", path))?;
+
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": "",
+                    "version": 1,
+                    "text": content,
+                }
+            }),
+        )?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.get("method").and_then(Value::as_str) == Some("textDocument/publishDiagnostics")
+                && message["params"]["uri"].as_str() == Some(uri.as_str())
+            {
+                let diagnostics = message["params"]["diagnostics"].as_array().cloned().unwrap_or_default();
+                if diagnostics.is_empty() {
+                    return Ok("No diagnostics reported".to_string());
+                }
+                let lines: Vec<String> = diagnostics
+                    .iter()
+                    .map(|d| {
+                        let (line, column) = d
+                            .get("range")
+                            .and_then(|r| r.get("start"))
+                            .map(from_lsp_position)
+                            .unwrap_or((0, 0));
+                        let message = d.get("message").and_then(Value::as_str).unwrap_or("");
+                        format!("{}:{}:{}: {}", path, line, column, message)
+                    })
+                    .collect();
+                return Ok(lines.join("\n"));
+            }
+        }
+    }
+
+    /// Renders an LSP `Location | Location[] | LocationLink[] | null`
+    /// result as `file:line:column` entries.
+    fn render_locations(&self, result: &Value) -> String {
+        let locations: Vec<&Value> = match result {
+            Value::Array(items) => items.iter().collect(),
+            Value::Object(_) => vec![result],
+            _ => vec![],
+        };
+
+        if locations.is_empty() {
+            return "No results found".to_string();
+        }
+
+        locations
+            .iter()
+            .map(|loc| {
+                // `Location` has `uri`/`range`; `LocationLink` has `targetUri`/`targetRange`.
+                let uri = loc
+                    .get("uri")
+                    .or_else(|| loc.get("targetUri"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let range = loc.get("range").or_else(|| loc.get("targetRange"));
+                let (line, column) = range
+                    .and_then(|r| r.get("start"))
+                    .map(from_lsp_position)
+                    .unwrap_or((0, 0));
+                format!("{}:{}:{}", rel_path_from_uri(&self.repo_root, uri), line, column)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        // Best-effort clean shutdown: ask nicely, but don't let a wedged
+        // server block process teardown -- the `shutdown` request is
+        // written directly (not via `self.request`), and we wait at most
+        // `LSP_SHUTDOWN_GRACE` for one message back before sending `exit`
+        // and killing the child outright.
+        let id = self.next_id;
+        let _ = self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "shutdown",
+            "params": Value::Null,
+        }));
+        let _ = self.receiver.recv_timeout(LSP_SHUTDOWN_GRACE);
+        let _ = self.notify("exit", json!({}));
+        let _ = self.child.kill();
+    }
+}
+
+/// Renders a hover result's `contents` (a `MarkupContent`, a bare string, or
+/// a `MarkedString`/`MarkedString[]`) down to plain text.
+fn render_hover_contents(contents: &Value) -> String {
+    match contents {
+        Value::String(s) => s.clone(),
+        Value::Object(obj) => obj
+            .get("value")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| contents.to_string()),
+        Value::Array(items) => items
+            .iter()
+            .map(render_hover_contents)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_lsp_position_is_zero_indexed() {
+        assert_eq!(to_lsp_position(1, 1), json!({"line": 0, "character": 0}));
+        assert_eq!(to_lsp_position(10, 5), json!({"line": 9, "character": 4}));
+    }
+
+    #[test]
+    fn test_from_lsp_position_is_one_indexed() {
+        assert_eq!(from_lsp_position(&json!({"line": 0, "character": 0})), (1, 1));
+        assert_eq!(from_lsp_position(&json!({"line": 9, "character": 4})), (10, 5));
+    }
+
+    #[test]
+    fn test_server_command_for_language() {
+        assert_eq!(server_command_for_language("Rust"), Some(("rust-analyzer", &[][..])));
+        assert!(server_command_for_language("C").is_none());
+    }
+
+    #[test]
+    fn test_render_hover_contents_plain_string() {
+        assert_eq!(render_hover_contents(&json!("fn main()")), "fn main()");
+    }
+
+    #[test]
+    fn test_render_hover_contents_markup_content() {
+        let contents = json!({"kind": "markdown", "value": "```rust\nfn main()\n```"});
+        assert_eq!(render_hover_contents(&contents), "```rust\nfn main()\n```");
+    }
+
+    #[test]
+    fn test_rel_path_from_uri_strips_repo_root() {
+        let repo_root = Path::new("/repo");
+        assert_eq!(rel_path_from_uri(repo_root, "file:///repo/src/main.rs"), "src/main.rs");
+    }
+}
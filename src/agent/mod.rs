@@ -3,6 +3,12 @@
 //! This module provides the tool-calling agent for analyzing code repositories.
 
 pub mod agent_loop;
+pub mod lsp;
+pub mod outline;
+pub mod provider;
+pub mod search;
+pub mod snippet;
 pub mod tools;
 
-pub use agent_loop::{AgentConfig, CodeAnalysisAgent};
+pub use agent_loop::{AgentConfig, CodeAnalysisAgent, FailedFile};
+pub use provider::{Provider, StreamEvent};
@@ -0,0 +1,224 @@
+//! Tree-sitter-backed code outline, backing the `outline_file` tool and
+//! replacing the hardcoded extension map `get_file_info` used to carry
+//! around on its own.
+//!
+//! Each supported language gets a `tree-sitter` grammar and a small query
+//! that picks out its declaration nodes (functions, methods,
+//! classes/structs, impl blocks); `outline` runs that query over the
+//! parsed tree and renders the matches as a compact, indented tree keyed
+//! by nesting depth. This gives the LLM a cheap map of a large file so it
+//! can decide what's worth a `read_file`/`read_snippet` call instead of
+//! reading everything up front.
+
+use anyhow::{anyhow, Context, Result};
+use tree_sitter::{Language, Parser, Query, QueryCursor};
+
+/// Maps a file extension (without the dot) to a human-readable language
+/// name. Shared by `get_file_info` and `outline_file`; the grammar and
+/// declaration query for that name come from `grammar_for_language`.
+pub fn language_for_extension(ext: &str) -> &'static str {
+    match ext {
+        "rs" => "Rust",
+        "py" => "Python",
+        "js" => "JavaScript",
+        "ts" => "TypeScript",
+        "go" => "Go",
+        "java" => "Java",
+        "c" | "h" => "C",
+        "cpp" | "hpp" => "C++",
+        _ => "Unknown",
+    }
+}
+
+/// Grammar and declaration query for a language outline supports. Returns
+/// `None` for languages without a wired-up grammar (e.g. Java, C, C++),
+/// in which case `outline_file` falls back to an error rather than a
+/// silently empty outline.
+fn grammar_for_language(language: &str) -> Option<(Language, &'static str)> {
+    match language {
+        "Rust" => Some((tree_sitter_rust::LANGUAGE.into(), RUST_QUERY)),
+        "Python" => Some((tree_sitter_python::LANGUAGE.into(), PYTHON_QUERY)),
+        "JavaScript" => Some((tree_sitter_javascript::LANGUAGE.into(), JS_QUERY)),
+        "TypeScript" => Some((tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(), JS_QUERY)),
+        "Go" => Some((tree_sitter_go::LANGUAGE.into(), GO_QUERY)),
+        _ => None,
+    }
+}
+
+const RUST_QUERY: &str = r#"
+(function_item name: (identifier) @name) @item
+(struct_item name: (type_identifier) @name) @item
+(enum_item name: (type_identifier) @name) @item
+(trait_item name: (type_identifier) @name) @item
+(impl_item type: (type_identifier) @name) @item
+"#;
+
+const PYTHON_QUERY: &str = r#"
+(function_definition name: (identifier) @name) @item
+(class_definition name: (identifier) @name) @item
+"#;
+
+const JS_QUERY: &str = r#"
+(function_declaration name: (identifier) @name) @item
+(class_declaration name: (_) @name) @item
+(method_definition name: (property_identifier) @name) @item
+"#;
+
+const GO_QUERY: &str = r#"
+(function_declaration name: (identifier) @name) @item
+(method_declaration name: (field_identifier) @name) @item
+(type_declaration (type_spec name: (type_identifier) @name)) @item
+"#;
+
+/// A single declaration extracted from the parse tree.
+struct Declaration {
+    name: String,
+    kind: &'static str,
+    start_line: usize,
+    end_line: usize,
+    depth: usize,
+}
+
+/// Parses `source` as `language` and renders its top-level and nested
+/// declarations as a compact indented tree, one `kind name start-end` line
+/// per declaration. Returns an error if `language` has no grammar wired up
+/// or the source fails to parse.
+pub fn outline(source: &str, language: &str) -> Result<String> {
+    let (grammar, query_src) =
+        grammar_for_language(language).ok_or_else(|| anyhow!("No outline support for language: {}", language))?;
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar)
+        .context("Failed to load tree-sitter grammar")?;
+
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow!("Failed to parse source"))?;
+
+    let query = Query::new(&grammar, query_src).context("Invalid outline query")?;
+    let item_idx = query
+        .capture_index_for_name("item")
+        .context("Outline query missing @item capture")?;
+    let name_idx = query
+        .capture_index_for_name("name")
+        .context("Outline query missing @name capture")?;
+
+    let mut cursor = QueryCursor::new();
+    let mut declarations = Vec::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+    while let Some(m) = matches.next() {
+        let item_node = m.captures.iter().find(|c| c.index == item_idx).map(|c| c.node);
+        let name_node = m.captures.iter().find(|c| c.index == name_idx).map(|c| c.node);
+        let (Some(item_node), Some(name_node)) = (item_node, name_node) else {
+            continue;
+        };
+
+        let name = name_node.utf8_text(source.as_bytes()).unwrap_or("?").to_string();
+        let depth = ancestor_depth(item_node);
+
+        declarations.push(Declaration {
+            name,
+            kind: item_node.kind(),
+            start_line: item_node.start_position().row + 1,
+            end_line: item_node.end_position().row + 1,
+            depth,
+        });
+    }
+
+    declarations.sort_by_key(|d| d.start_line);
+
+    if declarations.is_empty() {
+        return Ok("No declarations found".to_string());
+    }
+
+    let mut out = String::new();
+    for decl in &declarations {
+        let indent = "  ".repeat(decl.depth);
+        out.push_str(&format!(
+            "{}{} {} ({}-{})\n",
+            indent, decl.kind, decl.name, decl.start_line, decl.end_line
+        ));
+    }
+    Ok(out.trim_end().to_string())
+}
+
+/// Counts how many of `node`'s ancestors are themselves declaration nodes
+/// (one of the kinds the queries above name), so e.g. a method inside an
+/// `impl` block nests under it in the rendered tree instead of sitting at
+/// the same depth.
+fn ancestor_depth(node: tree_sitter::Node) -> usize {
+    let mut depth = 0;
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if is_declaration_kind(ancestor.kind()) {
+            depth += 1;
+        }
+        current = ancestor.parent();
+    }
+    depth
+}
+
+/// Whether `kind` is one of the node kinds the outline queries above
+/// capture as `@item`.
+fn is_declaration_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "function_item"
+            | "struct_item"
+            | "enum_item"
+            | "trait_item"
+            | "impl_item"
+            | "function_definition"
+            | "class_definition"
+            | "function_declaration"
+            | "class_declaration"
+            | "method_definition"
+            | "method_declaration"
+            | "type_declaration"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_rust_top_level_items() {
+        let source = "fn foo() {}\nstruct Bar {\n    x: i32,\n}\n";
+        let outline = outline(source, "Rust").unwrap();
+
+        assert!(outline.contains("function_item foo (1-1)"));
+        assert!(outline.contains("struct_item Bar (2-4)"));
+    }
+
+    #[test]
+    fn test_outline_rust_nests_methods_under_impl() {
+        let source = "impl Bar {\n    fn baz(&self) {}\n}\n";
+        let outline = outline(source, "Rust").unwrap();
+        let lines: Vec<&str> = outline.lines().collect();
+
+        assert_eq!(lines[0], "impl_item Bar (1-3)");
+        assert!(lines[1].starts_with("  function_item baz"));
+    }
+
+    #[test]
+    fn test_outline_python_function_and_class() {
+        let source = "def foo():\n    pass\n\nclass Bar:\n    pass\n";
+        let outline = outline(source, "Python").unwrap();
+
+        assert!(outline.contains("function_definition foo"));
+        assert!(outline.contains("class_definition Bar"));
+    }
+
+    #[test]
+    fn test_outline_empty_file_has_no_declarations() {
+        let outline = outline("", "Rust").unwrap();
+        assert_eq!(outline, "No declarations found");
+    }
+
+    #[test]
+    fn test_outline_unsupported_language_errors() {
+        assert!(outline("int main() {}", "C").is_err());
+    }
+}
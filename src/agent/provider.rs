@@ -0,0 +1,512 @@
+//! Chat provider abstraction.
+//!
+//! Lets `CodeAnalysisAgent` target different chat-completion backends (a local
+//! Ollama server or any OpenAI-compatible cloud API) behind a single trait,
+//! translating tool definitions and tool-call shapes per provider.
+
+use crate::agent::agent_loop::{AgentConfig, ChatMessage, ResponseMessage};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+/// Which chat-completion backend to talk to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Provider {
+    /// Local Ollama server (`/api/chat`).
+    #[default]
+    Ollama,
+    /// Any OpenAI-compatible endpoint (`/v1/chat/completions`).
+    OpenAiCompatible,
+}
+
+/// Per-request sampling options, independent of the wire format of any one provider.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChatOptions {
+    pub temperature: f32,
+    /// Context window size to request from the backend, if it supports one.
+    pub context_length: Option<u32>,
+    pub top_p: Option<f32>,
+    pub seed: Option<i64>,
+    pub num_predict: Option<i32>,
+}
+
+/// An incremental event emitted while streaming a chat completion.
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// A fragment of assistant text content.
+    ContentDelta(String),
+    /// A tool call's name became available. May arrive before its arguments
+    /// are fully known.
+    ToolCallStarted { index: usize, name: String },
+    /// A fragment of a tool call's arguments.
+    ToolCallArgumentDelta { index: usize, delta: String },
+    /// The stream has finished; carries the fully-assembled response.
+    Done(ResponseMessage),
+}
+
+/// A chat-completion backend capable of (optionally tool-calling) conversation turns.
+#[async_trait]
+pub trait ChatProvider: Send + Sync {
+    /// Send a full conversation plus available tool definitions and get the
+    /// model's next message back, including any tool calls it made.
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        options: &ChatOptions,
+    ) -> Result<ResponseMessage>;
+
+    /// Like [`ChatProvider::chat`], but emits [`StreamEvent`]s via `on_event` as
+    /// they arrive instead of only returning once the full response is in.
+    ///
+    /// The default implementation falls back to a single non-streaming call
+    /// and emits its content as one `ContentDelta` followed by `Done`, so
+    /// providers that don't support streaming still work behind this trait.
+    async fn chat_stream(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        options: &ChatOptions,
+        on_event: &mut (dyn FnMut(StreamEvent) + Send),
+    ) -> Result<ResponseMessage> {
+        let response = self.chat(messages, tools, options).await?;
+        if !response.content.is_empty() {
+            on_event(StreamEvent::ContentDelta(response.content.clone()));
+        }
+        on_event(StreamEvent::Done(response.clone()));
+        Ok(response)
+    }
+
+    /// Confirm the backend is reachable and the configured model is actually
+    /// available, before the agent sends its first real request.
+    ///
+    /// Providers that can't introspect installed models (most OpenAI-compatible
+    /// endpoints) default to a no-op.
+    async fn validate_model(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Build the configured provider from `AgentConfig`.
+pub fn build_provider(config: &AgentConfig, http_client: reqwest::Client) -> Box<dyn ChatProvider> {
+    match config.provider {
+        Provider::Ollama => Box::new(OllamaProvider {
+            http_client,
+            base_url: config.ollama_url.clone(),
+            model_name: config.model_name.clone(),
+        }),
+        Provider::OpenAiCompatible => Box::new(OpenAiProvider {
+            http_client,
+            base_url: config.ollama_url.clone(),
+            model_name: config.model_name.clone(),
+            api_key: config.api_key.clone(),
+        }),
+    }
+}
+
+// === Ollama ===
+
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tools: &'a [Value],
+    stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<i32>,
+}
+
+impl From<&ChatOptions> for OllamaOptions {
+    fn from(options: &ChatOptions) -> Self {
+        Self {
+            temperature: options.temperature,
+            num_ctx: options.context_length,
+            top_p: options.top_p,
+            seed: options.seed,
+            num_predict: options.num_predict,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaChatResponse {
+    message: OllamaResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaResponseMessage {
+    content: String,
+    #[serde(default)]
+    tool_calls: Option<Vec<crate::agent::agent_loop::ToolCallMessage>>,
+}
+
+/// One NDJSON line of a streamed Ollama `/api/chat` response.
+#[derive(Debug, Deserialize)]
+struct OllamaStreamChunk {
+    message: OllamaResponseMessage,
+    #[serde(default)]
+    done: bool,
+}
+
+pub struct OllamaProvider {
+    http_client: reqwest::Client,
+    base_url: String,
+    model_name: String,
+}
+
+#[async_trait]
+impl ChatProvider for OllamaProvider {
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        options: &ChatOptions,
+    ) -> Result<ResponseMessage> {
+        let url = format!("{}/api/chat", self.base_url);
+
+        let request = OllamaChatRequest {
+            model: &self.model_name,
+            messages,
+            tools,
+            stream: false,
+            options: OllamaOptions::from(options),
+        };
+
+        debug!("Sending Ollama chat request with {} messages", messages.len());
+
+        let response = self
+            .http_client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| describe_request_error(e, &self.base_url))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Ollama API error {}: {}", status, body));
+        }
+
+        let chat_response: OllamaChatResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama response")?;
+
+        Ok(ResponseMessage {
+            content: chat_response.message.content,
+            tool_calls: chat_response.message.tool_calls,
+        })
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        options: &ChatOptions,
+        on_event: &mut (dyn FnMut(StreamEvent) + Send),
+    ) -> Result<ResponseMessage> {
+        use futures_util::StreamExt;
+
+        let url = format!("{}/api/chat", self.base_url);
+
+        let request = OllamaChatRequest {
+            model: &self.model_name,
+            messages,
+            tools,
+            stream: true,
+            options: OllamaOptions::from(options),
+        };
+
+        debug!("Streaming Ollama chat request with {} messages", messages.len());
+
+        let response = self
+            .http_client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| describe_request_error(e, &self.base_url))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Ollama API error {}: {}", status, body));
+        }
+
+        let mut content = String::new();
+        let mut tool_calls: Vec<crate::agent::agent_loop::ToolCallMessage> = Vec::new();
+        let mut line_buffer = String::new();
+        let mut byte_stream = response.bytes_stream();
+
+        while let Some(chunk) = byte_stream.next().await {
+            let chunk = chunk.context("Failed to read Ollama stream chunk")?;
+            line_buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = line_buffer.find('\n') {
+                let line = line_buffer[..newline_pos].trim().to_string();
+                line_buffer.drain(..=newline_pos);
+                if line.is_empty() {
+                    continue;
+                }
+
+                let parsed: OllamaStreamChunk = serde_json::from_str(&line)
+                    .context("Failed to parse Ollama stream chunk")?;
+
+                if !parsed.message.content.is_empty() {
+                    content.push_str(&parsed.message.content);
+                    on_event(StreamEvent::ContentDelta(parsed.message.content));
+                }
+
+                if let Some(calls) = parsed.message.tool_calls {
+                    for call in calls {
+                        let index = tool_calls.len();
+                        on_event(StreamEvent::ToolCallStarted {
+                            index,
+                            name: call.function.name.clone(),
+                        });
+                        on_event(StreamEvent::ToolCallArgumentDelta {
+                            index,
+                            delta: call.function.arguments.to_string(),
+                        });
+                        tool_calls.push(call);
+                    }
+                }
+            }
+        }
+
+        let response = ResponseMessage {
+            content,
+            tool_calls: if tool_calls.is_empty() {
+                None
+            } else {
+                Some(tool_calls)
+            },
+        };
+        on_event(StreamEvent::Done(response.clone()));
+        Ok(response)
+    }
+
+    async fn validate_model(&self) -> Result<()> {
+        let url = format!("{}/api/tags", self.base_url);
+
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| describe_request_error(e, &self.base_url))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!(
+                "Ollama API error {} while listing installed models",
+                status
+            ));
+        }
+
+        let tags: OllamaTagsResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama tags response")?;
+        let available: Vec<String> = tags.models.into_iter().map(|m| m.name).collect();
+
+        if available.iter().any(|name| name == &self.model_name) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Model '{}' is not installed on the Ollama server at {}. Available models: {}",
+                self.model_name,
+                self.base_url,
+                if available.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ))
+        }
+    }
+}
+
+/// Response body of Ollama's `GET /api/tags`.
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaModelInfo {
+    name: String,
+}
+
+// === OpenAI-compatible ===
+
+#[derive(Debug, Serialize)]
+struct OpenAiChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<OpenAiMessage<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<Value>,
+    temperature: f32,
+    stream: bool,
+}
+
+/// OpenAI request message shape: role and content only. Tool calls from
+/// earlier assistant turns are not forwarded.
+#[derive(Debug, Serialize)]
+struct OpenAiMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiChatResponse {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiChoice {
+    message: OpenAiResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<OpenAiToolCall>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiToolCall {
+    function: OpenAiFunctionCall,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiFunctionCall {
+    name: String,
+    /// OpenAI sends arguments as a JSON-encoded string, not a nested object.
+    arguments: String,
+}
+
+pub struct OpenAiProvider {
+    http_client: reqwest::Client,
+    base_url: String,
+    model_name: String,
+    api_key: Option<String>,
+}
+
+impl OpenAiProvider {
+    /// Wrap our internal tool definitions in OpenAI's `{type, function}` shape.
+    ///
+    /// Our tool definitions are already serialized as `{"type": "function", "function": {...}}`
+    /// (see `get_tool_definitions`), so no translation is needed beyond passing them through.
+    fn translate_tools(tools: &[Value]) -> Vec<Value> {
+        tools.to_vec()
+    }
+}
+
+#[async_trait]
+impl ChatProvider for OpenAiProvider {
+    async fn chat(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        options: &ChatOptions,
+    ) -> Result<ResponseMessage> {
+        let url = format!("{}/v1/chat/completions", self.base_url);
+
+        let openai_messages = messages
+            .iter()
+            .map(|m| OpenAiMessage {
+                role: &m.role,
+                content: &m.content,
+            })
+            .collect();
+
+        let request = OpenAiChatRequest {
+            model: &self.model_name,
+            messages: openai_messages,
+            tools: Self::translate_tools(tools),
+            temperature: options.temperature,
+            stream: false,
+        };
+
+        debug!("Sending OpenAI-compatible chat request with {} messages", messages.len());
+
+        let mut req_builder = self.http_client.post(&url).json(&request);
+        if let Some(ref api_key) = self.api_key {
+            req_builder = req_builder.bearer_auth(api_key);
+        }
+
+        let response = req_builder
+            .send()
+            .await
+            .map_err(|e| describe_request_error(e, &self.base_url))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("OpenAI-compatible API error {}: {}", status, body));
+        }
+
+        let chat_response: OpenAiChatResponse = response
+            .json()
+            .await
+            .context("Failed to parse OpenAI-compatible response")?;
+
+        let choice = chat_response
+            .choices
+            .into_iter()
+            .next()
+            .context("OpenAI-compatible response had no choices")?;
+
+        // Translate OpenAI's stringified tool-call arguments into our internal
+        // Value-based ToolCallMessage shape.
+        let tool_calls = choice.message.tool_calls.map(|calls| {
+            calls
+                .into_iter()
+                .map(|call| crate::agent::agent_loop::ToolCallMessage {
+                    function: crate::agent::agent_loop::ToolCallFunction {
+                        name: call.function.name,
+                        arguments: serde_json::from_str(&call.function.arguments)
+                            .unwrap_or(Value::Null),
+                    },
+                })
+                .collect()
+        });
+
+        Ok(ResponseMessage {
+            content: choice.message.content.unwrap_or_default(),
+            tool_calls,
+        })
+    }
+}
+
+/// Turn a `reqwest::Error` into a user-facing message mentioning the configured endpoint.
+fn describe_request_error(e: reqwest::Error, base_url: &str) -> anyhow::Error {
+    if e.is_timeout() {
+        anyhow::anyhow!("Request timed out")
+    } else if e.is_connect() {
+        anyhow::anyhow!("Cannot connect to {}", base_url)
+    } else {
+        anyhow::anyhow!("Failed to send request: {}", e)
+    }
+}
+
@@ -0,0 +1,387 @@
+//! Regex-capable, gitignore-aware code search backing the `search_code`
+//! tool.
+//!
+//! Unlike a naive "collect every file, then filter", this walks the tree
+//! once and decides at each directory/file whether to descend/read it
+//! before doing so: excluded subtrees (the scanner's configured excludes,
+//! hidden entries, and any extra `exclude` globs) are pruned immediately,
+//! and when `include` globs are given, directories that can't possibly
+//! contain a match are skipped by comparing against each glob's literal
+//! base directory (the path segment before its first wildcard).
+
+use crate::scanner::{glob_matches, FileScanner};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Parameters for a single `search_code` call.
+pub struct SearchOptions<'a> {
+    pub pattern: &'a str,
+    pub is_regex: bool,
+    pub include: &'a [String],
+    pub exclude: &'a [String],
+    /// Restrict the walk to this subdirectory of the repo, if given.
+    pub scope: Option<&'a str>,
+    pub max_results: usize,
+}
+
+/// Searches the repo for `options.pattern`, returning up to
+/// `options.max_results` matches formatted as `file:line:column: text`.
+pub fn search_code(repo_root: &Path, scanner: &FileScanner, options: &SearchOptions) -> Result<Vec<String>> {
+    let pattern_src = if options.is_regex {
+        options.pattern.to_string()
+    } else {
+        regex::escape(options.pattern)
+    };
+    let pattern = regex::Regex::new(&pattern_src)
+        .with_context(|| format!("Invalid search pattern: {}", options.pattern))?;
+
+    let include_bases: Vec<String> = options.include.iter().map(|g| glob_base_dir(g)).collect();
+
+    let start_dir = match options.scope {
+        Some(scope) => repo_root.join(scope),
+        None => repo_root.to_path_buf(),
+    };
+
+    let mut results = Vec::new();
+    walk(repo_root, &start_dir, scanner, &pattern, options, &include_bases, &mut results);
+    Ok(results)
+}
+
+/// Best-effort "who references this file" lookup: for each path in
+/// `changed_paths`, searches the repo for its file stem (e.g. `foo` for
+/// `foo.rs`) appearing as plain text elsewhere. This is a cheap proxy for
+/// "this file imports/calls into it" without building a real dependency
+/// graph, used to widen incremental re-analysis (see
+/// `agent_loop::CodeAnalysisAgent::rerun_changed_files`) beyond just the
+/// edited files.
+pub fn find_dependents(repo_root: &Path, scanner: &FileScanner, changed_paths: &[String]) -> Vec<String> {
+    let mut dependents = std::collections::BTreeSet::new();
+
+    for changed in changed_paths {
+        let Some(stem) = Path::new(changed).file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if stem.is_empty() {
+            continue;
+        }
+
+        let options = SearchOptions {
+            pattern: stem,
+            is_regex: false,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 200,
+        };
+
+        let Ok(results) = search_code(repo_root, scanner, &options) else {
+            continue;
+        };
+
+        for result in results {
+            let Some(path) = result.split(':').next() else {
+                continue;
+            };
+            if !changed_paths.iter().any(|c| c == path) {
+                dependents.insert(path.to_string());
+            }
+        }
+    }
+
+    dependents.into_iter().collect()
+}
+
+/// Walks `dir`, matching `pattern` against every included file's lines and
+/// recursing into subdirectories that survive the exclude/include checks.
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    repo_root: &Path,
+    dir: &Path,
+    scanner: &FileScanner,
+    pattern: &regex::Regex,
+    options: &SearchOptions,
+    include_bases: &[String],
+    results: &mut Vec<String>,
+) {
+    if results.len() >= options.max_results || !dir.is_dir() {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if results.len() >= options.max_results {
+            return;
+        }
+
+        let path = entry.path();
+        if scanner.is_excluded_path(&path) {
+            continue;
+        }
+
+        let rel_path = scanner.relative_path(&path);
+        if options.exclude.iter().any(|glob| glob_matches(glob, &rel_path)) {
+            continue;
+        }
+
+        if path.is_dir() {
+            if !include_bases.is_empty() && !dir_can_contain_match(&rel_path, include_bases) {
+                continue;
+            }
+            walk(repo_root, &path, scanner, pattern, options, include_bases, results);
+        } else if path.is_file() {
+            let included = if options.include.is_empty() {
+                scanner.matches(&path)
+            } else {
+                options.include.iter().any(|glob| glob_matches(glob, &rel_path))
+            };
+            if !included {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            for (line_idx, line) in content.lines().enumerate() {
+                if let Some(m) = pattern.find(line) {
+                    results.push(format!("{}:{}:{}: {}", rel_path, line_idx + 1, m.start() + 1, line.trim()));
+                    if results.len() >= options.max_results {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The literal directory prefix of a glob, up to its first wildcard
+/// character, e.g. `"src/**/*.rs"` -> `"src"`, `"*.toml"` -> `""` (repo
+/// root, meaning the glob isn't confined to any subdirectory).
+fn glob_base_dir(glob: &str) -> String {
+    let wildcard_pos = glob.find(['*', '?']).unwrap_or(glob.len());
+    match glob[..wildcard_pos].rfind('/') {
+        Some(idx) => glob[..idx].to_string(),
+        None => String::new(),
+    }
+}
+
+/// Whether a directory at `rel_path` could still lead to a file matching
+/// one of `include_bases`: it's an ancestor of a base, the base itself, or
+/// nested inside a base.
+fn dir_can_contain_match(rel_path: &str, include_bases: &[String]) -> bool {
+    include_bases
+        .iter()
+        .any(|base| base.is_empty() || path_relates(rel_path, base))
+}
+
+fn path_relates(a: &str, b: &str) -> bool {
+    a == b || a.starts_with(&format!("{}/", b)) || b.starts_with(&format!("{}/", a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::ScanConfig;
+    use tempfile::TempDir;
+
+    fn scanner_for(root: &Path) -> FileScanner {
+        FileScanner::new(root.to_path_buf(), ScanConfig::default())
+    }
+
+    #[test]
+    fn test_search_code_plain_text_match() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "fn main() {\n    // TODO: fix this\n}").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "TODO",
+            is_regex: false,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0], "main.rs:2:8: // TODO: fix this");
+    }
+
+    #[test]
+    fn test_search_code_regex_match() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "let x = 1;\nlet y = 22;\n").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: r"\d+",
+            is_regex: true,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].starts_with("main.rs:1:9:"));
+    }
+
+    #[test]
+    fn test_search_code_invalid_regex_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "(unclosed",
+            is_regex: true,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 10,
+        };
+
+        assert!(search_code(temp_dir.path(), &scanner, &options).is_err());
+    }
+
+    #[test]
+    fn test_search_code_prunes_excluded_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+        std::fs::write(temp_dir.path().join("target/gen.rs"), "needle").unwrap();
+        std::fs::write(temp_dir.path().join("lib.rs"), "needle").unwrap();
+
+        let scanner = FileScanner::new(
+            temp_dir.path().to_path_buf(),
+            ScanConfig {
+                excludes: vec!["target".to_string()],
+                ..ScanConfig::default()
+            },
+        );
+        let options = SearchOptions {
+            pattern: "needle",
+            is_regex: false,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].starts_with("lib.rs"));
+    }
+
+    #[test]
+    fn test_search_code_honors_extra_exclude_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("thirdparty")).unwrap();
+        std::fs::write(temp_dir.path().join("thirdparty/dep.rs"), "needle").unwrap();
+        std::fs::write(temp_dir.path().join("lib.rs"), "needle").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "needle",
+            is_regex: false,
+            include: &[],
+            exclude: &["thirdparty".to_string()],
+            scope: None,
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].starts_with("lib.rs"));
+    }
+
+    #[test]
+    fn test_search_code_include_glob_skips_unrelated_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("docs")).unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "needle").unwrap();
+        std::fs::write(temp_dir.path().join("docs/notes.md"), "needle").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "needle",
+            is_regex: false,
+            include: &["src/**/*.rs".to_string()],
+            exclude: &[],
+            scope: None,
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].starts_with("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_search_code_scope_restricts_walk() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("a")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("b")).unwrap();
+        std::fs::write(temp_dir.path().join("a/one.rs"), "needle").unwrap();
+        std::fs::write(temp_dir.path().join("b/two.rs"), "needle").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "needle",
+            is_regex: false,
+            include: &[],
+            exclude: &[],
+            scope: Some("a"),
+            max_results: 10,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].starts_with("a/one.rs"));
+    }
+
+    #[test]
+    fn test_search_code_respects_max_results() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("many.rs"), "needle\nneedle\nneedle\n").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let options = SearchOptions {
+            pattern: "needle",
+            is_regex: false,
+            include: &[],
+            exclude: &[],
+            scope: None,
+            max_results: 2,
+        };
+
+        let results = search_code(temp_dir.path(), &scanner, &options).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_find_dependents_matches_file_stem_elsewhere() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("helper.rs"), "pub fn helper() {}").unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "mod helper;\nfn main() { helper::helper(); }").unwrap();
+        std::fs::write(temp_dir.path().join("unrelated.rs"), "fn main() {}").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let dependents = find_dependents(temp_dir.path(), &scanner, &["helper.rs".to_string()]);
+
+        assert_eq!(dependents, vec!["main.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_find_dependents_excludes_the_changed_files_themselves() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("helper.rs"), "pub fn helper() {}").unwrap();
+
+        let scanner = scanner_for(temp_dir.path());
+        let dependents = find_dependents(temp_dir.path(), &scanner, &["helper.rs".to_string()]);
+
+        assert!(dependents.is_empty());
+    }
+}
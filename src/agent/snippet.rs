@@ -0,0 +1,84 @@
+//! Shared source-snippet renderer: formats a numbered, gutter-padded
+//! excerpt around a target line, with an optional caret/underline row
+//! spanning a column range. Used by both the `read_snippet` tool (so the
+//! LLM can inspect a location it already found via `search_code` without
+//! re-reading the whole file) and `ToolExecutor::report_issue` (to embed
+//! the offending line straight into each `ReportedIssue` instead of
+//! leaving the report to show a bare line number).
+
+use std::fmt::Write;
+
+/// Renders `context` lines before and after `line` (1-indexed) from
+/// `content`, each prefixed with its line number and a `|` gutter. If
+/// `span` (1-indexed, inclusive start/end columns) is given, an underline
+/// row of `^` is emitted beneath the target line. Returns `None` if `line`
+/// is out of range.
+pub fn render_snippet(content: &str, line: usize, context: usize, span: Option<(usize, usize)>) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if line == 0 || line > lines.len() {
+        return None;
+    }
+
+    let start = line.saturating_sub(context).max(1);
+    let end = (line + context).min(lines.len());
+    let gutter_width = end.to_string().len();
+
+    let mut out = String::new();
+    for (idx, text) in lines.iter().enumerate().take(end).skip(start - 1) {
+        let line_no = idx + 1;
+        let _ = writeln!(out, "{:>width$} | {}", line_no, text, width = gutter_width);
+
+        if line_no == line {
+            if let Some((start_col, end_col)) = span {
+                let pad = " ".repeat(gutter_width + 3 + start_col.saturating_sub(1));
+                let carets = "^".repeat(end_col.saturating_sub(start_col) + 1);
+                let _ = writeln!(out, "{}{}", pad, carets);
+            }
+        }
+    }
+
+    Some(out.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_snippet_includes_context_lines() {
+        let content = "one\ntwo\nthree\nfour\nfive";
+        let snippet = render_snippet(content, 3, 1, None).unwrap();
+
+        assert!(snippet.contains("2 | two"));
+        assert!(snippet.contains("3 | three"));
+        assert!(snippet.contains("4 | four"));
+        assert!(!snippet.contains("one"));
+        assert!(!snippet.contains("five"));
+    }
+
+    #[test]
+    fn test_render_snippet_emits_caret_row_for_span() {
+        let content = "let x = foo.unwrap();";
+        let snippet = render_snippet(content, 1, 0, Some((9, 20))).unwrap();
+        let lines: Vec<&str> = snippet.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with(&"^".repeat(12)));
+    }
+
+    #[test]
+    fn test_render_snippet_clamps_to_file_bounds() {
+        let content = "one\ntwo\nthree";
+        let snippet = render_snippet(content, 1, 5, None).unwrap();
+
+        assert!(snippet.starts_with("1 | one"));
+        assert!(snippet.contains("3 | three"));
+    }
+
+    #[test]
+    fn test_render_snippet_out_of_range_line_returns_none() {
+        let content = "one\ntwo";
+        assert!(render_snippet(content, 10, 3, None).is_none());
+        assert!(render_snippet(content, 0, 3, None).is_none());
+    }
+}
@@ -5,7 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 use tracing::debug;
 
 /// Tool definition for Ollama's tool-calling API.
@@ -70,10 +71,174 @@ pub struct ToolExecutor {
     issues: Vec<ReportedIssue>,
     /// File scanner for respecting config.
     scanner: crate::scanner::FileScanner,
+    /// Memoized outputs of read-only tool calls, keyed by `(tool_name,
+    /// canonicalized arguments)`, so re-exploring the same file or search
+    /// within a run doesn't pay the cost twice.
+    cache: HashMap<(String, String), String>,
+    /// Human-readable log of read-only calls already made this run (e.g.
+    /// `read_file src/main.rs`), used to summarize what's been explored
+    /// after the sliding window prunes the raw tool results.
+    explored: Vec<String>,
+    /// Language server for `lsp_query`, spawned lazily on first use and
+    /// shut down when this executor (and so this field) is dropped.
+    lsp: Option<crate::agent::lsp::LspClient>,
+}
+
+/// Tool names whose output is safe to memoize: pure reads with no side
+/// effects beyond the filesystem. `report_issue` and `finish_analysis`
+/// are intentionally excluded.
+const CACHEABLE_TOOLS: &[&str] = &[
+    "list_files",
+    "read_file",
+    "search_code",
+    "get_file_info",
+    "read_snippet",
+    "outline_file",
+];
+
+/// Recursively sort object keys so that arguments differing only in JSON
+/// key order hash to the same cache entry.
+fn canonicalize_value(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> = map
+                .iter()
+                .map(|(k, v)| (k.clone(), canonicalize_value(v)))
+                .collect();
+            serde_json::to_value(sorted).unwrap_or(Value::Null)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize_value).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Build a cache key from a tool name and its (possibly unordered) arguments.
+fn cache_key(name: &str, args: &Value) -> (String, String) {
+    (name.to_string(), canonicalize_value(args).to_string())
+}
+
+/// Short description of a call used in the "already explored" log, e.g.
+/// `read_file src/main.rs` or `search_code "TODO"`.
+fn describe_call(name: &str, args: &Value) -> String {
+    match name {
+        "read_file" | "get_file_info" => {
+            let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("?");
+            format!("{} {}", name, path)
+        }
+        "list_files" => {
+            let dir = args
+                .get("directory")
+                .and_then(|v| v.as_str())
+                .unwrap_or(".");
+            format!("{} {}", name, dir)
+        }
+        "search_code" => {
+            let pattern = args.get("pattern").and_then(|v| v.as_str()).unwrap_or("?");
+            format!("{} \"{}\"", name, pattern)
+        }
+        "read_snippet" => {
+            let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("?");
+            let line = args.get("line").and_then(|v| v.as_u64()).unwrap_or(0);
+            format!("{} {}:{}", name, path, line)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Validates `args` against a tool's declared `parameters` JSON schema (the
+/// `object`/`properties`/`required`/`type`/`enum` subset `get_tool_definitions`
+/// actually uses), returning the first violation as a precise,
+/// model-correctable message naming the offending field. `None` if `args`
+/// satisfies the schema.
+fn validate_against_schema(schema: &Value, args: &Value) -> Option<String> {
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for field in required {
+            let field_name = field.as_str().unwrap_or_default();
+            if args.get(field_name).is_none() {
+                return Some(format!("Missing required parameter: {}", field_name));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object)?;
+    let provided = args.as_object()?;
+
+    for (key, value) in provided {
+        let Some(spec) = properties.get(key) else {
+            continue;
+        };
+
+        if let Some(expected_type) = spec.get("type").and_then(Value::as_str) {
+            if !value_matches_schema_type(value, expected_type) {
+                return Some(format!(
+                    "Parameter \"{}\" should be of type {}, got {}",
+                    key,
+                    expected_type,
+                    json_type_name(value)
+                ));
+            }
+        }
+
+        if let Some(allowed) = spec.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                let choices: Vec<&str> = allowed.iter().filter_map(Value::as_str).collect();
+                return Some(format!(
+                    "Parameter \"{}\" must be one of [{}], got {}",
+                    key,
+                    choices.join(", "),
+                    value
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether `value` matches a JSON-schema `type` name (the subset this
+/// crate's tool schemas use: string/integer/number/boolean/array/object).
+fn value_matches_schema_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+/// The JSON-schema type name for a `serde_json::Value`, used to report what
+/// was actually passed when a parameter fails type validation.
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// For an unknown tool name, finds the closest known tool name by
+/// Levenshtein distance and returns it if it's close enough to plausibly be
+/// a typo (distance <= 3, or <= a third of the longer name's length).
+fn suggest_tool_name(name: &str) -> Option<String> {
+    get_tool_definitions()
+        .into_iter()
+        .map(|def| {
+            let distance = crate::analysis::aggregator::levenshtein_distance(name, &def.function.name);
+            (def.function.name, distance)
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .filter(|(candidate, distance)| *distance <= 3 || *distance * 3 <= candidate.len().max(name.len()))
+        .map(|(candidate, _)| candidate)
 }
 
 /// An issue reported by the LLM via the report_issue tool.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReportedIssue {
     pub file_path: String,
     pub line_number: usize,
@@ -82,6 +247,10 @@ pub struct ReportedIssue {
     pub title: String,
     pub description: String,
     pub suggestion: String,
+    /// The offending line rendered via `snippet::render_snippet`, if the
+    /// file was readable at report time. Carried straight into
+    /// `Issue::code_snippet` so the report shows code inline.
+    pub code_snippet: Option<String>,
 }
 
 impl ToolExecutor {
@@ -92,6 +261,9 @@ impl ToolExecutor {
             repo_root,
             issues: Vec::new(),
             scanner,
+            cache: HashMap::new(),
+            explored: Vec::new(),
+            lsp: None,
         }
     }
 
@@ -100,18 +272,102 @@ impl ToolExecutor {
         &self.issues
     }
 
-    /// Execute a tool call and return the result.
+    /// Compact, human-readable summary of reads/searches already performed
+    /// this run, meant to be re-injected into the system prompt after the
+    /// sliding window prunes the raw tool results it summarizes.
+    pub fn explored_summary(&self) -> Option<String> {
+        if self.explored.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Already explored this run (do not repeat, re-request only if you need fresh content): {}",
+            self.explored.join(", ")
+        ))
+    }
+
+    /// Invalidate cached reads/searches and previously reported issues that
+    /// touch `changed_paths`, so a subsequent `execute` for those files
+    /// returns fresh results instead of a stale cache hit or silently
+    /// re-reporting an issue in a line that no longer exists. Used by
+    /// incremental watch-driven re-analysis (see
+    /// `agent_loop::CodeAnalysisAgent::rerun_changed_files`).
+    pub fn invalidate_paths(&mut self, changed_paths: &[String]) {
+        if changed_paths.is_empty() {
+            return;
+        }
+
+        self.cache.retain(|(name, args_json), _| {
+            // `search_code`/`list_files` results can surface any file in the
+            // repo, so there's no cheap way to tell which cached results are
+            // now stale -- flush them wholesale rather than risk missing one.
+            if matches!(name.as_str(), "search_code" | "list_files") {
+                return false;
+            }
+            !changed_paths.iter().any(|path| args_json.contains(path.as_str()))
+        });
+
+        self.explored
+            .retain(|entry| !changed_paths.iter().any(|path| entry.contains(path.as_str())));
+
+        self.issues.retain(|issue| !changed_paths.contains(&issue.file_path));
+    }
+
+    /// Execute a tool call and return the result. Read-only tools are
+    /// memoized for the lifetime of this executor: a repeated call with the
+    /// same (canonicalized) arguments returns a short reference instead of
+    /// re-reading the file or re-running the search.
     pub fn execute(&mut self, tool_call: &ToolCall) -> ToolResult {
-        let name = &tool_call.function.name;
-        let args = &tool_call.function.arguments;
+        let name = tool_call.function.name.clone();
+        let args = tool_call.function.arguments.clone();
 
         debug!("Executing tool: {} with args: {:?}", name, args);
 
-        match name.as_str() {
+        match get_tool_definitions().into_iter().find(|def| def.function.name == name) {
+            None => {
+                let mut message = format!("Unknown tool: {}", name);
+                if let Some(suggestion) = suggest_tool_name(&name) {
+                    message.push_str(&format!(" (did you mean '{}'?)", suggestion));
+                }
+                return ToolResult::error(message);
+            }
+            Some(def) => {
+                if let Some(violation) = validate_against_schema(&def.function.parameters, &args) {
+                    return ToolResult::error(violation);
+                }
+            }
+        }
+
+        if CACHEABLE_TOOLS.contains(&name.as_str()) {
+            let key = cache_key(&name, &args);
+            if self.cache.contains_key(&key) {
+                debug!("Cache hit for tool: {}", name);
+                return ToolResult::success(format!(
+                    "(already retrieved earlier: {} - see prior result, not re-fetched)",
+                    describe_call(&name, &args)
+                ));
+            }
+
+            let result = self.execute_uncached(&name, &args);
+            if result.success {
+                self.explored.push(describe_call(&name, &args));
+                self.cache.insert(key, result.output.clone());
+            }
+            return result;
+        }
+
+        self.execute_uncached(&name, &args)
+    }
+
+    /// Dispatch a tool call without consulting the cache.
+    fn execute_uncached(&mut self, name: &str, args: &Value) -> ToolResult {
+        match name {
             "list_files" => self.list_files(args),
             "read_file" => self.read_file(args),
             "search_code" => self.search_code(args),
+            "read_snippet" => self.read_snippet(args),
             "get_file_info" => self.get_file_info(args),
+            "lsp_query" => self.lsp_query(args),
+            "outline_file" => self.outline_file(args),
             "report_issue" => self.report_issue(args),
             "finish_analysis" => ToolResult::success("done".to_string()),
             _ => ToolResult::error(format!("Unknown tool: {}", name)),
@@ -131,13 +387,10 @@ impl ToolExecutor {
         }
     }
 
-    /// Read the contents of a file.
-    fn read_file(&self, args: &Value) -> ToolResult {
-        let path = match args.get("path").and_then(|v| v.as_str()) {
-            Some(p) => p,
-            None => return ToolResult::error("Missing required parameter: path".to_string()),
-        };
-
+    /// Resolves `path` (relative to the repo root) to an absolute path,
+    /// rejecting anything that escapes the repository via `..` or a
+    /// symlink. Shared by every tool that takes a file path.
+    fn resolve_repo_path(&self, path: &str) -> Result<PathBuf, ToolResult> {
         let full_path = self.repo_root.join(path);
 
         // Security check with canonicalization
@@ -146,25 +399,42 @@ impl ToolExecutor {
                 match std::fs::canonicalize(&full_path) {
                     Ok(canonical_path) => {
                         if !canonical_path.starts_with(&canonical_repo) {
-                            return ToolResult::error("Access denied: path outside repository".to_string());
+                            return Err(ToolResult::error("Access denied: path outside repository".to_string()));
                         }
                     }
                     Err(_) => {
-                        // Path doesn't exist or can't be canonicalized
-                        if !full_path.starts_with(&self.repo_root) {
-                            return ToolResult::error("Access denied: path outside repository".to_string());
+                        // Path doesn't exist yet (or can't be canonicalized);
+                        // `starts_with` is component-wise and won't collapse
+                        // `..`, so normalize lexically first.
+                        if !crate::pathutil::lexically_normalize(&full_path).starts_with(&canonical_repo) {
+                            return Err(ToolResult::error("Access denied: path outside repository".to_string()));
                         }
                     }
                 }
             }
             Err(_) => {
-                // Fallback to basic check
-                if !full_path.starts_with(&self.repo_root) {
-                    return ToolResult::error("Access denied: path outside repository".to_string());
+                // Fallback: repo_root itself couldn't be canonicalized.
+                if !crate::pathutil::lexically_normalize(&full_path).starts_with(&self.repo_root) {
+                    return Err(ToolResult::error("Access denied: path outside repository".to_string()));
                 }
             }
         }
 
+        Ok(full_path)
+    }
+
+    /// Read the contents of a file.
+    fn read_file(&self, args: &Value) -> ToolResult {
+        let path = match args.get("path").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: path".to_string()),
+        };
+
+        let full_path = match self.resolve_repo_path(path) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+
         if !full_path.exists() {
             return ToolResult::error(format!("File not found: {}", path));
         }
@@ -188,93 +458,186 @@ impl ToolExecutor {
         }
     }
 
-    /// Search for a pattern in the codebase.
-    fn search_code(&self, args: &Value) -> ToolResult {
-        let pattern = match args.get("pattern").and_then(|v| v.as_str()) {
+    /// Read just the region of a file around `line`, rendered like a
+    /// compiler diagnostic (numbered gutter, optional caret underline),
+    /// instead of forcing the model to `read_file` the whole thing.
+    fn read_snippet(&self, args: &Value) -> ToolResult {
+        let path = match args.get("path").and_then(|v| v.as_str()) {
             Some(p) => p,
-            None => return ToolResult::error("Missing required parameter: pattern".to_string()),
+            None => return ToolResult::error("Missing required parameter: path".to_string()),
+        };
+        let line = match args.get("line").and_then(|v| v.as_u64()) {
+            Some(l) => l as usize,
+            None => return ToolResult::error("Missing required parameter: line".to_string()),
+        };
+        let context = args.get("context").and_then(|v| v.as_u64()).unwrap_or(3) as usize;
+        let span = match (
+            args.get("column").and_then(|v| v.as_u64()),
+            args.get("span").and_then(|v| v.as_u64()),
+        ) {
+            (Some(column), Some(span_len)) => {
+                Some((column as usize, (column as usize) + (span_len as usize).saturating_sub(1)))
+            }
+            (Some(column), None) => Some((column as usize, column as usize)),
+            (None, _) => None,
         };
 
-        let max_results = args
-            .get("max_results")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(10) as usize;
+        let full_path = match self.resolve_repo_path(path) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
 
-        let mut results = Vec::new();
-        self.search_in_dir(&self.repo_root, pattern, &mut results, max_results);
+        if !full_path.is_file() {
+            return ToolResult::error(format!("File not found: {}", path));
+        }
 
-        if results.is_empty() {
-            ToolResult::success(String::new())
-        } else {
-            ToolResult::success(results.join("\n"))
+        let content = match std::fs::read_to_string(&full_path) {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(format!("Failed to read file: {}", e)),
+        };
+
+        match crate::agent::snippet::render_snippet(&content, line, context, span) {
+            Some(snippet) => ToolResult::success(snippet),
+            None => ToolResult::error(format!("Line {} is out of range for {}", line, path)),
         }
     }
 
-    fn search_in_dir(&self, dir: &Path, pattern: &str, results: &mut Vec<String>, max: usize) {
-        if results.len() >= max {
-            return;
+    /// Ask the language server for `path`'s language something it can
+    /// answer with real compiler/analyzer data instead of text search:
+    /// `op` selects `hover`, `definition`, `references`,
+    /// `document_symbols`, or `diagnostics`. The server is spawned on the
+    /// first call and reused for the rest of this executor's lifetime.
+    fn lsp_query(&mut self, args: &Value) -> ToolResult {
+        let path = match args.get("path").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: path".to_string()),
+        };
+        // LspClient joins `path` onto repo_root itself (it needs the
+        // relative form for its own URIs and `file:line:column` output), so
+        // resolve_repo_path is only consulted here to reject escapes.
+        if let Err(e) = self.resolve_repo_path(path) {
+            return e;
         }
+        let op = args.get("op").and_then(|v| v.as_str()).unwrap_or("hover");
 
-        let Ok(entries) = std::fs::read_dir(dir) else {
-            return;
-        };
+        if self.lsp.is_none() {
+            let language = std::path::Path::new(path)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(crate::agent::outline::language_for_extension)
+                .unwrap_or("Unknown");
 
-        for entry in entries.flatten() {
-            if results.len() >= max {
-                break;
+            match crate::agent::lsp::LspClient::spawn(&self.repo_root, language) {
+                Ok(client) => self.lsp = Some(client),
+                Err(e) => return ToolResult::error(format!("Failed to start language server: {}", e)),
             }
+        }
 
-            let path = entry.path();
-
-            if path.is_dir() {
-                self.search_in_dir(&path, pattern, results, max);
-            } else if path.is_file() && self.scanner.matches(&path) {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    for (line_num, line) in content.lines().enumerate() {
-                        if line.contains(pattern) {
-                            let rel_path = path.strip_prefix(&self.repo_root).unwrap_or(&path);
-                            results.push(format!("{}:{}", rel_path.display(), line_num + 1));
-                            if results.len() >= max {
-                                break;
-                            }
-                        }
-                    }
+        let client = self.lsp.as_mut().expect("just spawned above");
+
+        let result = match op {
+            "document_symbols" => client.document_symbols(path),
+            "diagnostics" => client.diagnostics(path),
+            "hover" | "definition" | "references" => {
+                let line = match args.get("line").and_then(|v| v.as_u64()) {
+                    Some(l) => l as usize,
+                    None => return ToolResult::error(format!("Missing required parameter: line (for op=\"{}\")", op)),
+                };
+                let column = match args.get("column").and_then(|v| v.as_u64()) {
+                    Some(c) => c as usize,
+                    None => return ToolResult::error(format!("Missing required parameter: column (for op=\"{}\")", op)),
+                };
+                match op {
+                    "hover" => client.hover(path, line, column),
+                    "definition" => client.definition(path, line, column),
+                    _ => client.references(path, line, column),
                 }
             }
+            other => return ToolResult::error(format!("Unknown lsp_query op: {}", other)),
+        };
+
+        match result {
+            Ok(output) => ToolResult::success(output),
+            Err(e) => ToolResult::error(e.to_string()),
         }
     }
 
-    /// Get information about a file.
-    fn get_file_info(&self, args: &Value) -> ToolResult {
+    /// Outline of `path`'s top-level and nested declarations (functions,
+    /// methods, classes/structs, impl blocks), one per line with its kind,
+    /// name, and start/end line, via `agent::outline`'s tree-sitter
+    /// queries. Cheaper than `read_file` when the model just needs to
+    /// decide what in a large file is worth reading in full.
+    fn outline_file(&self, args: &Value) -> ToolResult {
         let path = match args.get("path").and_then(|v| v.as_str()) {
             Some(p) => p,
             None => return ToolResult::error("Missing required parameter: path".to_string()),
         };
 
-        let full_path = self.repo_root.join(path);
+        let full_path = match self.resolve_repo_path(path) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
 
-        // Security check with canonicalization
-        match std::fs::canonicalize(&self.repo_root) {
-            Ok(canonical_repo) => {
-                match std::fs::canonicalize(&full_path) {
-                    Ok(canonical_path) => {
-                        if !canonical_path.starts_with(&canonical_repo) {
-                            return ToolResult::error("Access denied: path outside repository".to_string());
-                        }
-                    }
-                    Err(_) => {
-                        if !full_path.starts_with(&self.repo_root) {
-                            return ToolResult::error("Access denied: path outside repository".to_string());
-                        }
-                    }
-                }
-            }
-            Err(_) => {
-                if !full_path.starts_with(&self.repo_root) {
-                    return ToolResult::error("Access denied: path outside repository".to_string());
-                }
-            }
+        let language = full_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(crate::agent::outline::language_for_extension)
+            .unwrap_or("Unknown");
+
+        let content = match std::fs::read_to_string(&full_path) {
+            Ok(content) => content,
+            Err(e) => return ToolResult::error(format!("Failed to read file: {}", e)),
+        };
+
+        match crate::agent::outline::outline(&content, language) {
+            Ok(outline) => ToolResult::success(outline),
+            Err(e) => ToolResult::error(e.to_string()),
         }
+    }
+
+    /// Search for a pattern in the codebase. See `agent::search` for the
+    /// traversal/pruning logic; this just unpacks the tool-call arguments.
+    fn search_code(&self, args: &Value) -> ToolResult {
+        let pattern = match args.get("pattern").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: pattern".to_string()),
+        };
+
+        let is_regex = args.get("regex").and_then(|v| v.as_bool()).unwrap_or(false);
+        let max_results = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10) as usize;
+        let include = string_array(args, "include");
+        let exclude = string_array(args, "exclude");
+        let scope = args.get("path").and_then(|v| v.as_str());
+
+        let options = crate::agent::search::SearchOptions {
+            pattern,
+            is_regex,
+            include: &include,
+            exclude: &exclude,
+            scope,
+            max_results,
+        };
+
+        match crate::agent::search::search_code(&self.repo_root, &self.scanner, &options) {
+            Ok(results) => ToolResult::success(results.join("\n")),
+            Err(e) => ToolResult::error(e.to_string()),
+        }
+    }
+
+    /// Get information about a file.
+    fn get_file_info(&self, args: &Value) -> ToolResult {
+        let path = match args.get("path").and_then(|v| v.as_str()) {
+            Some(p) => p,
+            None => return ToolResult::error("Missing required parameter: path".to_string()),
+        };
+
+        let full_path = match self.resolve_repo_path(path) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
 
         if !full_path.exists() {
             return ToolResult::error(format!("File not found: {}", path));
@@ -288,17 +651,7 @@ impl ToolExecutor {
         let language = full_path
             .extension()
             .and_then(|e| e.to_str())
-            .map(|ext| match ext {
-                "rs" => "Rust",
-                "py" => "Python",
-                "js" => "JavaScript",
-                "ts" => "TypeScript",
-                "go" => "Go",
-                "java" => "Java",
-                "c" | "h" => "C",
-                "cpp" | "hpp" => "C++",
-                _ => ext,
-            })
+            .map(crate::agent::outline::language_for_extension)
             .unwrap_or("Unknown");
 
         let line_count = if full_path.is_file() {
@@ -315,16 +668,20 @@ impl ToolExecutor {
 
     /// Report an issue found in the code.
     fn report_issue(&mut self, args: &Value) -> ToolResult {
+        let file_path = args
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let line_number = args
+            .get("line_number")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize;
+        let code_snippet = self.render_issue_snippet(&file_path, line_number);
+
         let issue = ReportedIssue {
-            file_path: args
-                .get("file_path")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string(),
-            line_number: args
-                .get("line_number")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0) as usize,
+            file_path,
+            line_number,
             severity: args
                 .get("severity")
                 .and_then(|v| v.as_str())
@@ -350,6 +707,7 @@ impl ToolExecutor {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string(),
+            code_snippet,
         };
 
         debug!("Reported issue: {:?}", issue);
@@ -357,6 +715,24 @@ impl ToolExecutor {
 
         ToolResult::success("ok".to_string())
     }
+
+    /// Renders the snippet to embed in a reported issue: 3 lines of
+    /// context around `line`, no caret (report_issue doesn't take a
+    /// column). `None` if the file can't be read or `line` is invalid.
+    fn render_issue_snippet(&self, file_path: &str, line: usize) -> Option<String> {
+        let full_path = self.resolve_repo_path(file_path).ok()?;
+        let content = std::fs::read_to_string(full_path).ok()?;
+        crate::agent::snippet::render_snippet(&content, line, 3, None)
+    }
+}
+
+/// Reads a JSON array of strings at `key`, or an empty `Vec` if the key is
+/// absent or isn't an array of strings.
+fn string_array(args: &Value, key: &str) -> Vec<String> {
+    args.get(key)
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
 }
 
 /// Get the tool definitions for the Ollama API.
@@ -400,13 +776,31 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
             tool_type: "function".to_string(),
             function: FunctionDefinition {
                 name: "search_code".to_string(),
-                description: "Search for a pattern in the codebase. Returns matching lines with file and line numbers.".to_string(),
+                description: "Search for a pattern in the codebase. Returns matching lines as file:line:column: text.".to_string(),
                 parameters: json!({
                     "type": "object",
                     "properties": {
                         "pattern": {
                             "type": "string",
-                            "description": "Text pattern to search for"
+                            "description": "Text pattern to search for, or a regex if \"regex\" is true"
+                        },
+                        "regex": {
+                            "type": "boolean",
+                            "description": "Treat pattern as a regular expression instead of plain text (default: false)"
+                        },
+                        "include": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Only search files matching one of these glob patterns, e.g. [\"src/**/*.rs\"]"
+                        },
+                        "exclude": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Skip files/directories matching any of these glob patterns"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Restrict the search to this directory relative to repository root"
                         },
                         "max_results": {
                             "type": "integer",
@@ -417,6 +811,39 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 }),
             },
         },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "read_snippet".to_string(),
+                description: "Read just the region of a file around a line, rendered with a numbered gutter and an optional caret underline. Use this instead of read_file when you already know the location (e.g. from search_code).".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path to the file relative to repository root"
+                        },
+                        "line": {
+                            "type": "integer",
+                            "description": "1-indexed line number to center the snippet on"
+                        },
+                        "context": {
+                            "type": "integer",
+                            "description": "Number of lines to show before and after the target line (default: 3)"
+                        },
+                        "column": {
+                            "type": "integer",
+                            "description": "1-indexed starting column to underline with carets"
+                        },
+                        "span": {
+                            "type": "integer",
+                            "description": "Number of columns to underline, starting at \"column\" (default: 1)"
+                        }
+                    },
+                    "required": ["path", "line"]
+                }),
+            },
+        },
         ToolDefinition {
             tool_type: "function".to_string(),
             function: FunctionDefinition {
@@ -434,6 +861,53 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 }),
             },
         },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "lsp_query".to_string(),
+                description: "Ask the repo's language server (rust-analyzer, pyright, typescript-language-server, or gopls, chosen by file extension) a semantic question grounded in real compiler/analyzer data instead of text search.".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "op": {
+                            "type": "string",
+                            "enum": ["hover", "definition", "references", "document_symbols", "diagnostics"],
+                            "description": "Which language-server query to run"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Path to the file relative to repository root"
+                        },
+                        "line": {
+                            "type": "integer",
+                            "description": "1-indexed line number (required for hover/definition/references)"
+                        },
+                        "column": {
+                            "type": "integer",
+                            "description": "1-indexed column number (required for hover/definition/references)"
+                        }
+                    },
+                    "required": ["op", "path"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "outline_file".to_string(),
+                description: "Get a structure-aware outline of a file's top-level and nested declarations (functions, methods, classes/structs, impl blocks) with their start/end lines, without reading the whole file.".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Path to the file relative to repository root"
+                        }
+                    },
+                    "required": ["path"]
+                }),
+            },
+        },
         ToolDefinition {
             tool_type: "function".to_string(),
             function: FunctionDefinition {
@@ -529,15 +1003,240 @@ mod tests {
         // Raw content, no line numbers - minimal tokens
     }
 
+    #[test]
+    fn test_lsp_query_rejects_path_outside_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}").unwrap();
+
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let result = executor.lsp_query(&json!({"path": "../outside.rs", "op": "hover", "line": 1, "column": 1}));
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("outside repository"));
+    }
+
+    #[test]
+    fn test_tool_executor_caches_read_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}").unwrap();
+
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "read_file".to_string(),
+                arguments: json!({"path": "test.rs"}),
+            },
+        };
+
+        let first = executor.execute(&call);
+        assert!(first.success);
+        assert!(first.output.contains("fn main()"));
+
+        let second = executor.execute(&call);
+        assert!(second.success);
+        assert!(second.output.contains("already retrieved earlier"));
+        assert_eq!(executor.explored_summary().unwrap().matches("test.rs").count(), 1);
+    }
+
+    #[test]
+    fn test_invalidate_paths_clears_cache_and_reruns_read() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}").unwrap();
+
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "read_file".to_string(),
+                arguments: json!({"path": "test.rs"}),
+            },
+        };
+
+        executor.execute(&call);
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() { edited() }").unwrap();
+        executor.invalidate_paths(&["test.rs".to_string()]);
+
+        let result = executor.execute(&call);
+        assert!(result.success);
+        assert!(result.output.contains("edited()"));
+    }
+
+    #[test]
+    fn test_invalidate_paths_drops_stale_issues_for_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "report_issue".to_string(),
+                arguments: json!({
+                    "file_path": "a.rs", "line_number": 1, "severity": "low",
+                    "category": "style", "title": "x", "description": "y"
+                }),
+            },
+        };
+
+        executor.execute(&call);
+        assert_eq!(executor.get_issues().len(), 1);
+
+        executor.invalidate_paths(&["a.rs".to_string()]);
+        assert!(executor.get_issues().is_empty());
+    }
+
+    #[test]
+    fn test_tool_executor_does_not_cache_report_issue() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "report_issue".to_string(),
+                arguments: json!({"file_path": "a.rs", "line_number": 1, "severity": "low", "category": "style", "title": "x", "description": "y"}),
+            },
+        };
+
+        executor.execute(&call);
+        executor.execute(&call);
+
+        assert_eq!(executor.get_issues().len(), 2);
+        assert!(executor.explored_summary().is_none());
+    }
+
     #[test]
     fn test_tool_definitions() {
         let tools = get_tool_definitions();
-        assert_eq!(tools.len(), 6);
+        assert_eq!(tools.len(), 9);
 
         let names: Vec<_> = tools.iter().map(|t| t.function.name.as_str()).collect();
         assert!(names.contains(&"list_files"));
         assert!(names.contains(&"read_file"));
+        assert!(names.contains(&"read_snippet"));
+        assert!(names.contains(&"lsp_query"));
+        assert!(names.contains(&"outline_file"));
         assert!(names.contains(&"report_issue"));
         assert!(names.contains(&"finish_analysis"));
     }
+
+    #[test]
+    fn test_execute_rejects_missing_required_parameter() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "read_file".to_string(),
+                arguments: json!({}),
+            },
+        };
+
+        let result = executor.execute(&call);
+        assert!(!result.success);
+        assert_eq!(result.error.unwrap(), "Missing required parameter: path");
+    }
+
+    #[test]
+    fn test_execute_rejects_enum_violation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "report_issue".to_string(),
+                arguments: json!({
+                    "file_path": "a.rs", "line_number": 1, "severity": "extreme",
+                    "category": "style", "title": "x", "description": "y"
+                }),
+            },
+        };
+
+        let result = executor.execute(&call);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("\"severity\" must be one of"));
+    }
+
+    #[test]
+    fn test_execute_rejects_wrong_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "read_snippet".to_string(),
+                arguments: json!({"path": "a.rs", "line": "two"}),
+            },
+        };
+
+        let result = executor.execute(&call);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("\"line\" should be of type integer"));
+    }
+
+    #[test]
+    fn test_execute_unknown_tool_suggests_closest_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "read_fil".to_string(),
+                arguments: json!({"path": "a.rs"}),
+            },
+        };
+
+        let result = executor.execute(&call);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("did you mean 'read_file'?"));
+    }
+
+    #[test]
+    fn test_execute_unknown_tool_far_from_any_name_has_no_suggestion() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let call = ToolCall {
+            function: FunctionCall {
+                name: "completely_unrelated_tool_name".to_string(),
+                arguments: json!({}),
+            },
+        };
+
+        let result = executor.execute(&call);
+        assert!(!result.success);
+        assert!(!result.error.unwrap().contains("did you mean"));
+    }
+
+    #[test]
+    fn test_tool_executor_read_snippet() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {\n    let x = 1;\n}").unwrap();
+
+        let executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let result = executor.read_snippet(&json!({"path": "test.rs", "line": 2, "column": 9, "span": 1}));
+
+        assert!(result.success);
+        assert!(result.output.contains("2 | "));
+        assert!(result.output.contains('^'));
+    }
+
+    #[test]
+    fn test_tool_executor_read_snippet_out_of_range() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}").unwrap();
+
+        let executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        let result = executor.read_snippet(&json!({"path": "test.rs", "line": 50}));
+
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn test_report_issue_embeds_code_snippet() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn main() {\n    foo.unwrap();\n}").unwrap();
+
+        let mut executor = ToolExecutor::new(temp_dir.path().to_path_buf(), ScanConfig::default());
+        executor.report_issue(&json!({
+            "file_path": "a.rs",
+            "line_number": 2,
+            "severity": "low",
+            "category": "style",
+            "title": "x"
+        }));
+
+        let issues = executor.get_issues();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].code_snippet.as_ref().unwrap().contains("foo.unwrap()"));
+    }
 }
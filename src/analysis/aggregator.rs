@@ -18,6 +18,26 @@ pub fn sort_issues_by_severity(issues: &mut [Issue]) {
     issues.sort_by(|a, b| b.severity.cmp(&a.severity));
 }
 
+/// Deduplicate issues that share `(file_path, start_line, normalized title)`,
+/// keeping the first occurrence. Used to merge the deterministic rule
+/// engine's findings with the LLM agent's reported issues without
+/// double-counting the same finding surfaced by both (mirrors
+/// `agent::agent_loop::dedupe_issues`, but over the final `Issue` type
+/// rather than the agent's own `ReportedIssue`).
+pub fn dedupe_by_fingerprint(issues: Vec<Issue>) -> Vec<Issue> {
+    let mut seen = std::collections::HashSet::new();
+    issues
+        .into_iter()
+        .filter(|issue| {
+            seen.insert((
+                issue.file_path.clone(),
+                issue.start_line,
+                issue.title.trim().to_lowercase(),
+            ))
+        })
+        .collect()
+}
+
 /// Group issues by file path.
 pub fn group_by_file(issues: &[Issue]) -> HashMap<String, Vec<Issue>> {
     let mut grouped: HashMap<String, Vec<Issue>> = HashMap::new();
@@ -157,26 +177,115 @@ pub fn most_problematic_files(files: &[AnalyzedFile], n: usize) -> Vec<(&Analyze
     file_issues
 }
 
-/// Identify patterns in issues (common titles/categories).
-#[allow(dead_code)] // Utility for pattern analysis
+/// Identify patterns in issues (common titles/categories), clustering
+/// near-duplicate titles (e.g. "Possible null dereference" vs "Possibly
+/// null dereference") rather than requiring an exact match.
 pub fn identify_patterns(issues: &[Issue]) -> Vec<(String, usize)> {
-    let mut title_counts: HashMap<String, usize> = HashMap::new();
+    identify_patterns_with_threshold(issues, None)
+}
+
+/// Like `identify_patterns`, but allows overriding the clustering
+/// aggressiveness. `max_distance_override` is the maximum Levenshtein
+/// distance between a title and a cluster representative for them to be
+/// merged; when `None`, a length-relative default is used (`max(2, len /
+/// 8)`) so short titles must match strictly and long ones tolerate more
+/// drift.
+pub fn identify_patterns_with_threshold(
+    issues: &[Issue],
+    max_distance_override: Option<usize>,
+) -> Vec<(String, usize)> {
+    let mut clusters: Vec<TitleCluster> = Vec::new();
 
     for issue in issues {
-        // Normalize the title for grouping
-        let normalized = issue.title.to_lowercase();
-        *title_counts.entry(normalized).or_default() += 1;
+        let normalized = normalize_title(&issue.title);
+
+        let existing = clusters.iter_mut().find(|cluster| {
+            let threshold =
+                max_distance_override.unwrap_or_else(|| cluster_threshold(&cluster.representative));
+            levenshtein_distance(&normalized, &cluster.representative) <= threshold
+        });
+
+        match existing {
+            Some(cluster) => cluster.count += 1,
+            None => clusters.push(TitleCluster {
+                representative: normalized,
+                count: 1,
+            }),
+        }
     }
 
-    let mut patterns: Vec<_> = title_counts
+    let mut patterns: Vec<(String, usize)> = clusters
         .into_iter()
-        .filter(|(_, count)| *count > 1) // Only show repeated patterns
+        .filter(|cluster| cluster.count > 1) // Only show repeated patterns
+        .map(|cluster| (cluster.representative, cluster.count))
         .collect();
 
     patterns.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
     patterns
 }
 
+/// A greedily-built cluster of near-duplicate titles: the first title seen
+/// becomes the representative, and the running count covers every issue
+/// whose title matched within the distance threshold.
+struct TitleCluster {
+    representative: String,
+    count: usize,
+}
+
+/// Default clustering distance for a given representative: short titles
+/// must match almost exactly, longer ones tolerate proportionally more
+/// drift in phrasing.
+fn cluster_threshold(representative: &str) -> usize {
+    std::cmp::max(2, representative.len() / 8)
+}
+
+/// Lowercase, collapse runs of whitespace, and strip trailing punctuation
+/// so titles that differ only in formatting cluster together.
+fn normalize_title(title: &str) -> String {
+    let collapsed = title
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    collapsed
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_string()
+}
+
+/// Levenshtein edit distance between two strings, computed with the
+/// classic DP recurrence using only two rows of O(min(len)) memory. Also
+/// used by `agent::tools` to suggest a close tool name when an unknown one
+/// is requested.
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let (a, b) = if a.chars().count() <= b.chars().count() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev_row: Vec<usize> = (0..=a.len()).collect();
+    let mut curr_row = vec![0usize; a.len() + 1];
+
+    for (j, &b_char) in b.iter().enumerate() {
+        curr_row[0] = j + 1;
+
+        for (i, &a_char) in a.iter().enumerate() {
+            let cost = if a_char == b_char { 0 } else { 1 };
+            curr_row[i + 1] = (prev_row[i + 1] + 1)
+                .min(curr_row[i] + 1)
+                .min(prev_row[i] + cost);
+        }
+
+        std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+
+    prev_row[a.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +301,11 @@ mod tests {
             description: "Test description".to_string(),
             suggestion: "Fix it".to_string(),
             code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
         }
     }
 
@@ -223,6 +337,24 @@ mod tests {
         assert_eq!(issues.len(), 3);
     }
 
+    #[test]
+    fn test_dedupe_by_fingerprint() {
+        let mut duplicate = create_test_issue(Severity::High, "Bug");
+        duplicate.title = "Unchecked unwrap".to_string();
+        let mut same_finding_different_case = duplicate.clone();
+        same_finding_different_case.title = "unchecked UNWRAP ".to_string();
+        let mut distinct = duplicate.clone();
+        distinct.start_line = 2;
+
+        let deduped = dedupe_by_fingerprint(vec![
+            duplicate,
+            same_finding_different_case,
+            distinct,
+        ]);
+
+        assert_eq!(deduped.len(), 2);
+    }
+
     #[test]
     fn test_sort_issues_by_severity() {
         let mut issues = vec![
@@ -267,6 +399,45 @@ mod tests {
         assert_eq!(top[1].severity, Severity::High);
     }
 
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_identify_patterns_clusters_near_duplicates() {
+        let mut issue_a = create_test_issue(Severity::High, "Bug");
+        issue_a.title = "Possible null dereference".to_string();
+        let mut issue_b = create_test_issue(Severity::High, "Bug");
+        issue_b.title = "Possibly null dereference".to_string();
+        let mut issue_c = create_test_issue(Severity::Low, "Style");
+        issue_c.title = "Unused import".to_string();
+
+        let patterns = identify_patterns(&[issue_a, issue_b, issue_c]);
+
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].1, 2);
+    }
+
+    #[test]
+    fn test_identify_patterns_threshold_override() {
+        let mut issue_a = create_test_issue(Severity::High, "Bug");
+        issue_a.title = "Off by one".to_string();
+        let mut issue_b = create_test_issue(Severity::High, "Bug");
+        issue_b.title = "Off by two".to_string();
+
+        // Strict override: these differ by more than 0 edits, so no cluster.
+        let strict = identify_patterns_with_threshold(&[issue_a.clone(), issue_b.clone()], Some(0));
+        assert!(strict.is_empty());
+
+        // Loose override: tolerate the drift and merge them.
+        let loose = identify_patterns_with_threshold(&[issue_a, issue_b], Some(5));
+        assert_eq!(loose.len(), 1);
+        assert_eq!(loose[0].1, 2);
+    }
+
     #[test]
     fn test_issue_density() {
         let files = vec![
@@ -0,0 +1,368 @@
+//! `--apply` mode: rewrite files on disk from a report's structured `Fix`es.
+//!
+//! This is the rust-analyzer "cook a structured diagnostic into a fixit"
+//! pattern: `Issue.fix` keeps the edit structured enough that the tool,
+//! not the user, can apply it. Edits are grouped by file and applied
+//! bottom-to-top so earlier edits don't invalidate the line numbers later
+//! edits were computed against, validated against the file's current
+//! contents, and skipped (with a reason) rather than applied if they
+//! overlap another edit or no longer fit within the file.
+
+use crate::models::{Report, TextEdit};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One file that had at least one fix attempted.
+#[derive(Debug, Clone)]
+pub struct FileOutcome {
+    /// Path of the file relative to `repo_root`.
+    pub file_path: String,
+    /// Number of edits applied (or that would be applied, in a dry run).
+    pub edits_applied: usize,
+    /// Unified-ish diff preview, set only when `apply_fixes` was called
+    /// with `dry_run: true`.
+    pub preview: Option<String>,
+}
+
+/// A file whose fixes could not be applied, and why.
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    pub file_path: String,
+    pub reason: String,
+}
+
+/// Result of an `apply_fixes` run.
+#[derive(Debug, Clone, Default)]
+pub struct ApplySummary {
+    pub applied: Vec<FileOutcome>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Apply every `Issue.fix` in `report` to the files under `repo_root`.
+///
+/// When `dry_run` is true, files are not written; each `FileOutcome` carries
+/// a diff preview instead.
+pub fn apply_fixes(report: &Report, repo_root: &Path, dry_run: bool) -> Result<ApplySummary> {
+    let mut edits_by_file: HashMap<String, Vec<TextEdit>> = HashMap::new();
+    for file in &report.files {
+        for issue in &file.issues {
+            if let Some(fix) = &issue.fix {
+                for edit in &fix.edits {
+                    edits_by_file
+                        .entry(edit.file_path.clone())
+                        .or_default()
+                        .push(edit.clone());
+                }
+            }
+        }
+    }
+
+    let mut file_paths: Vec<String> = edits_by_file.keys().cloned().collect();
+    file_paths.sort();
+
+    let mut summary = ApplySummary::default();
+
+    for file_path in file_paths {
+        let mut edits = edits_by_file.remove(&file_path).unwrap();
+        // Bottom-to-top so applying one edit never shifts the line numbers
+        // another edit in the same file was computed against.
+        edits.sort_by(|a, b| b.start_line.cmp(&a.start_line));
+
+        if let Some((a, b)) = first_overlap(&edits) {
+            summary.skipped.push(SkippedFile {
+                file_path,
+                reason: format!(
+                    "overlapping edits at lines {}-{} and {}-{}",
+                    a.start_line, a.end_line, b.start_line, b.end_line
+                ),
+            });
+            continue;
+        }
+
+        let full_path = match resolve_within_repo(repo_root, &file_path) {
+            Some(p) => p,
+            None => {
+                summary.skipped.push(SkippedFile {
+                    file_path,
+                    reason: "file_path escapes the repository root".to_string(),
+                });
+                continue;
+            }
+        };
+        let original = std::fs::read_to_string(&full_path)
+            .with_context(|| format!("Failed to read {} to apply fixes", full_path.display()))?;
+        let mut lines: Vec<&str> = original.lines().collect();
+        let total_lines = lines.len();
+
+        if let Some(bad) = edits
+            .iter()
+            .find(|e| e.start_line == 0 || e.start_line > e.end_line || e.end_line > total_lines)
+        {
+            summary.skipped.push(SkippedFile {
+                file_path,
+                reason: format!(
+                    "edit for lines {}-{} is out of bounds for a {}-line file",
+                    bad.start_line, bad.end_line, total_lines
+                ),
+            });
+            continue;
+        }
+
+        let edits_applied = edits.len();
+        for edit in &edits {
+            let replacement: Vec<&str> = edit.replacement.lines().collect();
+            lines.splice(edit.start_line - 1..edit.end_line, replacement);
+        }
+        let updated = join_with_trailing_newline(&lines, original.ends_with('\n'));
+
+        if dry_run {
+            summary.applied.push(FileOutcome {
+                file_path,
+                edits_applied,
+                preview: Some(diff_preview(&original, &updated)),
+            });
+        } else {
+            std::fs::write(&full_path, &updated)
+                .with_context(|| format!("Failed to write fixed contents to {}", full_path.display()))?;
+            summary.applied.push(FileOutcome {
+                file_path,
+                edits_applied,
+                preview: None,
+            });
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Joins `file_path` (straight off the deserialized `Report` JSON) onto
+/// `repo_root`, rejecting anything that escapes it via an absolute path or a
+/// `..` segment (possibly through a symlink) — same canonicalize-and-check
+/// as `resolve_repo_path` in `agent/tools.rs` and `is_within_repo` in
+/// `scanner/mod.rs`. Returns `None` rather than erroring so the caller can
+/// skip just this file, the same as any other unfit edit.
+fn resolve_within_repo(repo_root: &Path, file_path: &str) -> Option<std::path::PathBuf> {
+    let full_path = repo_root.join(file_path);
+
+    let canonical_repo = std::fs::canonicalize(repo_root).unwrap_or_else(|_| repo_root.to_path_buf());
+    match std::fs::canonicalize(&full_path) {
+        Ok(canonical_path) => canonical_path.starts_with(&canonical_repo).then_some(full_path),
+        // File doesn't exist yet (or can't be canonicalized); `starts_with`
+        // is component-wise and won't collapse `..`, so normalize lexically
+        // first rather than comparing the raw joined path.
+        Err(_) => crate::pathutil::lexically_normalize(&full_path)
+            .starts_with(&canonical_repo)
+            .then_some(full_path),
+    }
+}
+
+/// Edits must already be sorted by descending `start_line`; two edits
+/// overlap when the earlier (higher-line) one starts at or before the
+/// later one ends.
+fn first_overlap(edits_desc: &[TextEdit]) -> Option<(TextEdit, TextEdit)> {
+    edits_desc
+        .windows(2)
+        .find(|w| w[0].start_line <= w[1].end_line)
+        .map(|w| (w[1].clone(), w[0].clone()))
+}
+
+fn join_with_trailing_newline(lines: &[&str], trailing_newline: bool) -> String {
+    let mut out = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// A minimal diff: the common prefix/suffix lines are dropped, and the
+/// changed middle is shown as removed (`-`) then added (`+`) lines.
+fn diff_preview(before: &str, after: &str) -> String {
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+
+    let mut prefix = 0;
+    while prefix < before_lines.len()
+        && prefix < after_lines.len()
+        && before_lines[prefix] == after_lines[prefix]
+    {
+        prefix += 1;
+    }
+
+    let mut suffix = 0;
+    while suffix < before_lines.len() - prefix
+        && suffix < after_lines.len() - prefix
+        && before_lines[before_lines.len() - 1 - suffix] == after_lines[after_lines.len() - 1 - suffix]
+    {
+        suffix += 1;
+    }
+
+    let mut out = String::new();
+    for line in &before_lines[prefix..before_lines.len() - suffix] {
+        out.push_str(&format!("- {}\n", line));
+    }
+    for line in &after_lines[prefix..after_lines.len() - suffix] {
+        out.push_str(&format!("+ {}\n", line));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AnalyzedFile, Fix, Issue, IssueSummary, ReportMetadata, Severity};
+    use chrono::Utc;
+
+    fn issue_with_fix(start_line: usize, end_line: usize, replacement: &str) -> Issue {
+        Issue {
+            file_path: "src/lib.rs".to_string(),
+            start_line,
+            end_line: Some(end_line),
+            severity: Severity::Medium,
+            category: "Style".to_string(),
+            title: "Use idiomatic form".to_string(),
+            description: "Non-idiomatic code".to_string(),
+            suggestion: "Rewrite as suggested".to_string(),
+            code_snippet: None,
+            fix: Some(Fix {
+                edits: vec![TextEdit {
+                    file_path: "src/lib.rs".to_string(),
+                    start_line,
+                    end_line,
+                    replacement: replacement.to_string(),
+                }],
+            }),
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        }
+    }
+
+    fn report_with_issues(issues: Vec<Issue>) -> Report {
+        Report {
+            metadata: ReportMetadata {
+                repo_url: "local".to_string(),
+                analysis_date: Utc::now(),
+                model_used: "test-model".to_string(),
+                files_analyzed: 1,
+                files_failed: 0,
+                total_issues: issues.len(),
+                duration_seconds: 1.0,
+                scoped_to_diff: None,
+            },
+            project_overview: String::new(),
+            files: vec![AnalyzedFile {
+                path: "src/lib.rs".to_string(),
+                language: "Rust".to_string(),
+                line_count: 3,
+                issues,
+                analysis_successful: true,
+                error: None,
+            }],
+            summary: IssueSummary::default(),
+            recommendations: vec![],
+            code_stats: crate::stats::CodeStats::default(),
+            logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes_rewrites_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "line1\nline2\nline3\n").unwrap();
+
+        let report = report_with_issues(vec![issue_with_fix(2, 2, "replaced")]);
+        let summary = apply_fixes(&report, dir.path(), false).unwrap();
+
+        assert_eq!(summary.applied.len(), 1);
+        assert!(summary.skipped.is_empty());
+        let updated = std::fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+        assert_eq!(updated, "line1\nreplaced\nline3\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_dry_run_does_not_write() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "line1\nline2\nline3\n").unwrap();
+
+        let report = report_with_issues(vec![issue_with_fix(2, 2, "replaced")]);
+        let summary = apply_fixes(&report, dir.path(), true).unwrap();
+
+        assert_eq!(summary.applied.len(), 1);
+        assert!(summary.applied[0].preview.as_ref().unwrap().contains("+ replaced"));
+        let unchanged = std::fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+        assert_eq!(unchanged, "line1\nline2\nline3\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_applies_bottom_to_top() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "a\nb\nc\n").unwrap();
+
+        let report = report_with_issues(vec![
+            issue_with_fix(1, 1, "A"),
+            issue_with_fix(3, 3, "C"),
+        ]);
+        let summary = apply_fixes(&report, dir.path(), false).unwrap();
+
+        assert_eq!(summary.applied[0].edits_applied, 2);
+        let updated = std::fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+        assert_eq!(updated, "A\nb\nC\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_overlapping_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "a\nb\nc\n").unwrap();
+
+        let report = report_with_issues(vec![
+            issue_with_fix(1, 2, "X"),
+            issue_with_fix(2, 3, "Y"),
+        ]);
+        let summary = apply_fixes(&report, dir.path(), false).unwrap();
+
+        assert!(summary.applied.is_empty());
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(summary.skipped[0].reason.contains("overlapping"));
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_out_of_bounds_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "a\nb\nc\n").unwrap();
+
+        let report = report_with_issues(vec![issue_with_fix(10, 10, "X")]);
+        let summary = apply_fixes(&report, dir.path(), false).unwrap();
+
+        assert!(summary.applied.is_empty());
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(summary.skipped[0].reason.contains("out of bounds"));
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_path_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "a\nb\nc\n").unwrap();
+        std::fs::write(dir.path().join("outside.txt"), "secret\n").unwrap();
+
+        let mut issue = issue_with_fix(1, 1, "pwned");
+        let edit = &mut issue.fix.as_mut().unwrap().edits[0];
+        edit.file_path = "../outside.txt".to_string();
+        issue.file_path = edit.file_path.clone();
+
+        let report = report_with_issues(vec![issue]);
+        let summary = apply_fixes(&report, &dir.path().join("src"), false).unwrap();
+
+        assert!(summary.applied.is_empty());
+        assert_eq!(summary.skipped.len(), 1);
+        assert!(summary.skipped[0].reason.contains("escapes the repository root"));
+        assert_eq!(std::fs::read_to_string(dir.path().join("outside.txt")).unwrap(), "secret\n");
+    }
+}
@@ -0,0 +1,184 @@
+//! The `--baseline`/`--update-baseline` exemptions file: lets repeated
+//! audits of an evolving repo fail CI only on newly introduced issues,
+//! rather than failing forever on findings the team has already triaged
+//! and accepted.
+//!
+//! Unlike `report::diff` (which compares two full JSON report snapshots),
+//! this is a small, persistent fingerprint file checked in alongside the
+//! repo. Each fingerprint hashes `file_path` + normalized `title` +
+//! `category` + a small window of surrounding code, deliberately excluding
+//! `start_line` so that unrelated edits above a finding don't invalidate it.
+
+use crate::models::Issue;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Lines of source included on either side of the issue when computing its
+/// fingerprint's code-context window.
+const CONTEXT_LINES: usize = 3;
+
+/// A set of accepted-issue fingerprints, persisted as `.yoauditor-baseline.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    fingerprints: HashSet<String>,
+}
+
+impl Baseline {
+    /// Loads the baseline from `path`. A missing file is treated as an
+    /// empty baseline (e.g. the first run before `--update-baseline`).
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline file: {}", path.display()))
+    }
+
+    /// Writes the baseline to `path` as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize baseline to JSON")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write baseline file: {}", path.display()))
+    }
+
+    /// Whether `issue` matches a fingerprint already accepted into this baseline.
+    pub fn contains(&self, issue: &Issue, file_contents: &HashMap<String, String>) -> bool {
+        self.fingerprints
+            .contains(&fingerprint(issue, file_contents))
+    }
+
+    /// Builds a fresh baseline containing every issue in `issues` (for
+    /// `--update-baseline`).
+    pub fn from_issues(issues: &[Issue], file_contents: &HashMap<String, String>) -> Self {
+        Self {
+            fingerprints: issues
+                .iter()
+                .map(|issue| fingerprint(issue, file_contents))
+                .collect(),
+        }
+    }
+}
+
+/// Hashes `file_path` + normalized `title` + `category` + a small window of
+/// surrounding code into a stable hex digest. `start_line` is deliberately
+/// excluded: edits elsewhere in the file shifting it shouldn't invalidate
+/// an already-accepted finding.
+fn fingerprint(issue: &Issue, file_contents: &HashMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(issue.file_path.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(normalize(&issue.title).as_bytes());
+    hasher.update(b"\0");
+    hasher.update(normalize(&issue.category).as_bytes());
+    hasher.update(b"\0");
+    hasher.update(surrounding_context(issue, file_contents).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Lowercases and trims, so a change in case or surrounding whitespace of a
+/// title/category doesn't churn the baseline. Any change of wording does.
+fn normalize(s: &str) -> String {
+    s.trim().to_lowercase()
+}
+
+/// The issue's line plus up to `CONTEXT_LINES` lines of source on either
+/// side of it.
+/// Returns an empty string if the file isn't available (e.g. it's been
+/// deleted, or the issue targets a synthetic location like `Cargo.lock`).
+fn surrounding_context(issue: &Issue, file_contents: &HashMap<String, String>) -> String {
+    let Some(content) = file_contents.get(&issue.file_path) else {
+        return String::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    let center = issue.start_line.saturating_sub(1).min(lines.len() - 1);
+    let start = center.saturating_sub(CONTEXT_LINES);
+    let end = (center + CONTEXT_LINES + 1).min(lines.len());
+    lines[start..end].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+
+    fn make_issue(file_path: &str, title: &str, category: &str, start_line: usize) -> Issue {
+        Issue {
+            file_path: file_path.to_string(),
+            start_line,
+            end_line: None,
+            severity: Severity::Medium,
+            category: category.to_string(),
+            title: title.to_string(),
+            description: "desc".to_string(),
+            suggestion: "fix it".to_string(),
+            code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        }
+    }
+
+    #[test]
+    fn test_baseline_contains_accepted_issue() {
+        let mut file_contents = HashMap::new();
+        file_contents.insert(
+            "src/lib.rs".to_string(),
+            (1..=20).map(|n| format!("line{}", n)).collect::<Vec<_>>().join("\n"),
+        );
+
+        let issue = make_issue("src/lib.rs", "Unchecked unwrap", "Bug", 10);
+        let baseline = Baseline::from_issues(&[issue.clone()], &file_contents);
+
+        assert!(baseline.contains(&issue, &file_contents));
+    }
+
+    #[test]
+    fn test_baseline_tolerates_unrelated_line_shift() {
+        let mut before = HashMap::new();
+        before.insert(
+            "src/lib.rs".to_string(),
+            (1..=20).map(|n| format!("line{}", n)).collect::<Vec<_>>().join("\n"),
+        );
+        let issue_before = make_issue("src/lib.rs", "Unchecked unwrap", "Bug", 10);
+        let baseline = Baseline::from_issues(&[issue_before], &before);
+
+        // Ten blank lines inserted above the finding: same surrounding code,
+        // different start_line.
+        let mut after = HashMap::new();
+        let mut shifted_lines: Vec<String> = vec![String::new(); 10];
+        shifted_lines.extend((1..=20).map(|n| format!("line{}", n)));
+        after.insert("src/lib.rs".to_string(), shifted_lines.join("\n"));
+        let issue_after = make_issue("src/lib.rs", "Unchecked unwrap", "Bug", 20);
+
+        assert!(baseline.contains(&issue_after, &after));
+    }
+
+    #[test]
+    fn test_baseline_rejects_unrelated_issue() {
+        let mut file_contents = HashMap::new();
+        file_contents.insert(
+            "src/lib.rs".to_string(),
+            (1..=20).map(|n| format!("line{}", n)).collect::<Vec<_>>().join("\n"),
+        );
+
+        let accepted = make_issue("src/lib.rs", "Unchecked unwrap", "Bug", 10);
+        let baseline = Baseline::from_issues(&[accepted], &file_contents);
+
+        let other = make_issue("src/lib.rs", "SQL Injection", "Security", 10);
+        assert!(!baseline.contains(&other, &file_contents));
+    }
+}
@@ -0,0 +1,334 @@
+//! `--workload` batch mode: run the full clone-analyze-report pipeline
+//! across a list of repos from one JSON file, aggregating the results into
+//! a single roll-up summary. Modeled after `eval::Workload` (the `--bench`
+//! runner's file format), but drives real audits instead of scoring
+//! against expected issues.
+
+use crate::cli::{Args, FailOnLevel, OutputFormat};
+use crate::models::Report;
+use crate::repo::{self, CloneOptions};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tracing::warn;
+
+/// One audit job within a `--workload` file. Anything left unset falls
+/// back to the base `Args` the workload was invoked with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJob {
+    /// Repository URL to clone. Mutually exclusive with `local`.
+    #[serde(default)]
+    pub repo_url: Option<String>,
+    /// Branch to check out, if not the default.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Local directory to analyze instead of cloning. Mutually exclusive
+    /// with `repo_url`.
+    #[serde(default)]
+    pub local: Option<PathBuf>,
+    /// Model override for this job.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// File extensions to include, overriding the base `Args`.
+    #[serde(default)]
+    pub extensions: Option<Vec<String>>,
+    /// Exclude patterns, overriding the base `Args`.
+    #[serde(default)]
+    pub excludes: Option<Vec<String>>,
+    /// `--fail-on` threshold for this job.
+    #[serde(default)]
+    pub fail_on: Option<FailOnLevel>,
+}
+
+/// A `--workload` file: a flat list of audit jobs to run in sequence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchWorkload {
+    pub jobs: Vec<BatchJob>,
+}
+
+impl BatchWorkload {
+    /// Load a workload from a JSON file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workload file: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse workload file: {}", path.display()))
+    }
+}
+
+/// One job's contribution to the roll-up summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJobSummary {
+    pub repo: String,
+    pub total_issues: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub exit_code: i32,
+}
+
+/// Combined result of a `--workload` run, written as a single JSON file
+/// (and, with `--report-url`, POSTed to an HTTP endpoint).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub jobs: Vec<BatchJobSummary>,
+    pub total_issues: usize,
+}
+
+/// Runs every job in `workload_path` through the normal audit pipeline,
+/// aggregates the results, writes them to `base_args.output` as JSON, and
+/// optionally POSTs them to `--report-url`. Returns the worst exit code
+/// across all jobs (2 if any job's `--fail-on` threshold was exceeded,
+/// 1 if any job errored outright, else 0).
+pub async fn run_workload(workload_path: &Path, base_args: &Args) -> Result<i32> {
+    let workload = BatchWorkload::load(workload_path)?;
+    println!("📦 Running workload: {} job(s)", workload.jobs.len());
+
+    // Pre-clone every job's `repo_url` concurrently instead of cloning one at
+    // a time as each job runs in sequence below. `_clone_scratch` just needs
+    // to outlive the loop so the pre-cloned directories aren't cleaned up
+    // from under it.
+    let (_clone_scratch, pre_cloned) = pre_clone_jobs(&workload.jobs, base_args)?;
+
+    let mut summaries = Vec::with_capacity(workload.jobs.len());
+    let mut worst_exit_code = 0;
+
+    for (index, job) in workload.jobs.iter().enumerate() {
+        let label = job_label(job);
+        println!(
+            "\n=== Job {}/{}: {} ===",
+            index + 1,
+            workload.jobs.len(),
+            label
+        );
+
+        let job_args = build_job_args(base_args, job, index, pre_cloned.as_ref())?;
+        let job_output = job_args.output.clone();
+
+        let exit_code = match crate::run_audit(job_args).await {
+            Ok(code) => code,
+            Err(e) => {
+                warn!("Job '{}' failed: {}", label, e);
+                eprintln!("   ❌ Job failed: {}", e);
+                summaries.push(BatchJobSummary {
+                    repo: label,
+                    total_issues: 0,
+                    critical: 0,
+                    high: 0,
+                    medium: 0,
+                    low: 0,
+                    exit_code: 1,
+                });
+                worst_exit_code = worst_exit_code.max(1);
+                continue;
+            }
+        };
+        worst_exit_code = worst_exit_code.max(exit_code);
+
+        let report = load_job_report(&job_output)?;
+        let _ = std::fs::remove_file(&job_output);
+        summaries.push(BatchJobSummary {
+            repo: label,
+            total_issues: report.summary.total,
+            critical: report.summary.critical,
+            high: report.summary.high,
+            medium: report.summary.medium,
+            low: report.summary.low,
+            exit_code,
+        });
+    }
+
+    let total_issues = summaries.iter().map(|s| s.total_issues).sum();
+    let result = BatchResult {
+        jobs: summaries,
+        total_issues,
+    };
+
+    let json =
+        serde_json::to_string_pretty(&result).context("Failed to serialize workload results")?;
+    std::fs::write(&base_args.output, &json).with_context(|| {
+        format!(
+            "Failed to write workload results to {}",
+            base_args.output.display()
+        )
+    })?;
+
+    println!(
+        "\n📊 Workload complete: {} total issue(s) across {} job(s)",
+        result.total_issues,
+        result.jobs.len()
+    );
+    println!("   Results saved to: {}", base_args.output.display());
+
+    if let Some(ref url) = base_args.report_url {
+        post_results(url, &json).await?;
+    }
+
+    Ok(worst_exit_code)
+}
+
+/// Pre-clones every job's `repo_url` concurrently via `repo::clone_repositories`
+/// (honoring `--clone-concurrency`), so the sequential per-job loop below can
+/// reuse an already-cloned directory instead of cloning one repo at a time.
+/// Jobs using `local`, or overriding `branch` (the shared `CloneOptions` here
+/// can't carry a per-job branch), are left for `run_audit` to clone itself.
+/// Returns the scratch directory holding the clones (the caller must keep it
+/// alive for the life of the run) and a map from `repo_url` to its cloned
+/// path; a job whose pre-clone failed is simply left out of the map, and
+/// `build_job_args` falls back to letting that job clone itself the usual way.
+fn pre_clone_jobs(
+    jobs: &[BatchJob],
+    base_args: &Args,
+) -> Result<(Option<TempDir>, Option<HashMap<String, PathBuf>>)> {
+    let urls: Vec<String> = jobs
+        .iter()
+        .filter(|j| j.branch.is_none())
+        .filter_map(|j| j.repo_url.clone())
+        .collect();
+    if urls.is_empty() {
+        return Ok((None, None));
+    }
+
+    println!(
+        "📥 Pre-cloning {} repo(s) with concurrency {}...",
+        urls.len(),
+        base_args.clone_concurrency
+    );
+
+    let scratch =
+        TempDir::new().context("Failed to create workload clone scratch directory")?;
+    let options = CloneOptions {
+        show_progress: !base_args.quiet,
+        target_dir: Some(scratch.path().to_path_buf()),
+        concurrency: base_args.clone_concurrency,
+        ssh_private_key: base_args.ssh_key.clone(),
+        ssh_passphrase: base_args.ssh_key_passphrase.clone(),
+        https_token: base_args.https_token.clone(),
+        username: base_args.username.clone(),
+        recurse_submodules: base_args.recurse_submodules,
+        ..Default::default()
+    };
+
+    let results = repo::clone_repositories(&urls, options);
+    let mut paths = HashMap::with_capacity(urls.len());
+    for (url, result) in urls.into_iter().zip(results) {
+        match result {
+            Ok(clone) => {
+                paths.insert(url, clone.into_path());
+            }
+            Err(e) => warn!("Pre-clone of {} failed, job will clone it itself: {}", url, e),
+        }
+    }
+
+    Ok((Some(scratch), Some(paths)))
+}
+
+/// A human-readable label for a job, for progress output and the roll-up
+/// summary: the repo URL if cloning, else the local path.
+fn job_label(job: &BatchJob) -> String {
+    job.repo_url
+        .clone()
+        .or_else(|| job.local.as_ref().map(|p| p.display().to_string()))
+        .unwrap_or_else(|| "<unnamed job>".to_string())
+}
+
+/// Builds the per-job `Args`: clones `base`, overrides whatever fields the
+/// job specifies, and forces a scratch JSON output path so the report can
+/// be read back for the roll-up (removed once folded in).
+///
+/// `pre_cloned` maps a `repo_url` to a directory `pre_clone_jobs` already
+/// cloned it into; when present, the job points straight at that directory
+/// via `local` instead of having `run_audit` clone it again.
+fn build_job_args(
+    base: &Args,
+    job: &BatchJob,
+    index: usize,
+    pre_cloned: Option<&HashMap<String, PathBuf>>,
+) -> Result<Args> {
+    let mut args = base.clone();
+    args.workload = None;
+    args.report_url = None;
+
+    if let Some(ref url) = job.repo_url {
+        args.repo = Some(url.clone());
+        args.local = pre_cloned.and_then(|m| m.get(url)).cloned();
+    } else if job.local.is_some() {
+        args.local = job.local.clone();
+        args.repo = None;
+    }
+
+    if job.branch.is_some() {
+        args.branch = job.branch.clone();
+    }
+    if let Some(ref model) = job.model {
+        args.model = model.clone();
+    }
+    if job.extensions.is_some() {
+        args.extensions = job.extensions.clone();
+    }
+    if job.excludes.is_some() {
+        args.exclude = job.excludes.clone();
+    }
+    if job.fail_on.is_some() {
+        args.fail_on = job.fail_on;
+    }
+
+    args.format = OutputFormat::Json;
+    // A predictable path under the shared temp directory is a symlink/clobber
+    // vector on a multi-user host -- another local user could pre-create
+    // `yoauditor-workload-job-{index}.json` before this run starts. Use a
+    // `tempfile`-created, exclusively-opened random path instead, same as
+    // `pre_clone_jobs`'s `TempDir`, then `keep()` it so it survives past the
+    // `NamedTempFile` handle for `run_audit` to write into and
+    // `load_job_report` to read back.
+    let named_temp = tempfile::Builder::new()
+        .prefix(&format!("yoauditor-workload-job-{}-", index))
+        .suffix(".json")
+        .tempfile()
+        .context("Failed to create scratch file for job report")?;
+    let (_file, output_path) = named_temp
+        .keep()
+        .context("Failed to persist scratch file for job report")?;
+    args.output = output_path;
+
+    args.validate().map_err(anyhow::Error::msg)?;
+    Ok(args)
+}
+
+/// Reads back a job's JSON report, written by `run_audit` to its scratch
+/// output path, so the roll-up summary can be computed from it.
+fn load_job_report(path: &Path) -> Result<Report> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read job report: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse job report: {}", path.display()))
+}
+
+/// POSTs the aggregated workload results to `--report-url` as JSON.
+async fn post_results(url: &str, body: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .with_context(|| format!("Failed to POST workload results to {}", url))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "Report endpoint {} returned {}: {}",
+            url,
+            status,
+            text
+        ));
+    }
+
+    println!("   Posted results to: {}", url);
+    Ok(())
+}
@@ -0,0 +1,281 @@
+//! Content-hash response cache for single-call LLM analysis, modeled on
+//! disk-backed compilation caches: each entry is keyed by a hash of (model
+//! name, temperature, prompt template version, file path, file content
+//! bytes), so
+//! re-auditing a repo after editing only a few files can reuse every
+//! unchanged file's analysis instead of resending it to Ollama/the cloud
+//! model. See `agent::agent_loop::CodeAnalysisAgent::analyze_files` for the
+//! read/write call sites, and `config::CacheConfig` for the `[cache]`
+//! section this is built from.
+
+use crate::agent::tools::ReportedIssue;
+use crate::config::CacheConfig;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// Bumped whenever a prompt change could alter the model's output for the
+/// same file content, invalidating every entry cached under the old prompt.
+const PROMPT_TEMPLATE_VERSION: u32 = 1;
+
+/// One cached analysis result for a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    issues: Vec<ReportedIssue>,
+    cached_at: u64,
+}
+
+/// A directory of `CacheEntry` JSON files, one per (model, temperature,
+/// prompt version, file path, file content) hash. See [`CacheConfig`] for how the
+/// directory, TTL, and size cap are configured.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl_seconds: Option<u64>,
+    max_entries: Option<usize>,
+}
+
+impl ResponseCache {
+    /// Build a `ResponseCache` from `config`, creating its directory if
+    /// needed. Returns `None` if caching is disabled, so call sites can
+    /// thread an `Option<ResponseCache>` through without an extra `enabled`
+    /// check at every use.
+    pub fn new(config: &CacheConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let dir = config.resolved_directory();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+
+        Ok(Some(Self {
+            dir,
+            ttl_seconds: config.ttl_seconds,
+            max_entries: config.max_entries,
+        }))
+    }
+
+    /// Look up a cached analysis for `path`/`content` under `model_name`/
+    /// `temperature`. A miss, an expired entry, or any I/O/parse error are
+    /// all treated the same way: `None`, so the caller just re-analyzes.
+    pub fn get(
+        &self,
+        model_name: &str,
+        temperature: f32,
+        path: &str,
+        content: &str,
+    ) -> Option<Vec<ReportedIssue>> {
+        let entry_path = self.entry_path(&cache_key(model_name, temperature, path, content));
+        let raw = std::fs::read_to_string(&entry_path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+
+        if let Some(ttl) = self.ttl_seconds {
+            if now_unix().saturating_sub(entry.cached_at) > ttl {
+                debug!("Cache entry for {} expired", path);
+                return None;
+            }
+        }
+
+        Some(entry.issues)
+    }
+
+    /// Store `issues` found for `path`/`content` under `model_name`/
+    /// `temperature`. Writes to a temp file in the cache directory first,
+    /// then renames it into place, so a reader never observes a partially
+    /// written entry even when several chunks are analyzed concurrently.
+    pub fn put(
+        &self,
+        model_name: &str,
+        temperature: f32,
+        path: &str,
+        content: &str,
+        issues: &[ReportedIssue],
+    ) -> Result<()> {
+        let entry = CacheEntry {
+            issues: issues.to_vec(),
+            cached_at: now_unix(),
+        };
+        let serialized =
+            serde_json::to_string(&entry).context("Failed to serialize cache entry")?;
+
+        let key = cache_key(model_name, temperature, path, content);
+        let final_path = self.entry_path(&key);
+        let tmp_path = self.dir.join(format!("{key}.tmp-{}", std::process::id()));
+
+        std::fs::write(&tmp_path, &serialized)
+            .with_context(|| format!("Failed to write cache entry: {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, &final_path).with_context(|| {
+            format!("Failed to finalize cache entry: {}", final_path.display())
+        })?;
+
+        if let Some(max_entries) = self.max_entries {
+            self.evict_oldest_over_cap(max_entries);
+        }
+
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// Best-effort enforcement of the `[cache].max_entries` size cap:
+    /// deletes the oldest entries (by mtime) once the directory holds more
+    /// than `max_entries` files. Failures to stat/remove an individual
+    /// entry are ignored -- the cap is an optimization, not a correctness
+    /// requirement.
+    fn evict_oldest_over_cap(&self, max_entries: usize) {
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut entries: Vec<(PathBuf, SystemTime)> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((entry.path(), modified))
+            })
+            .collect();
+
+        if entries.len() <= max_entries {
+            return;
+        }
+
+        entries.sort_by_key(|(_, modified)| *modified);
+        let overflow = entries.len() - max_entries;
+        for (path, _) in entries.into_iter().take(overflow) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Hash (model name, temperature, prompt template version, file path,
+/// content) into a stable hex digest. The path is part of the key (not
+/// just the content) so two identical files at different paths don't
+/// collide and silently swap each other's findings.
+fn cache_key(model_name: &str, temperature: f32, path: &str, content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(model_name.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(temperature.to_bits().to_le_bytes());
+    hasher.update(b"\0");
+    hasher.update(PROMPT_TEMPLATE_VERSION.to_le_bytes());
+    hasher.update(b"\0");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(dir: &Path) -> CacheConfig {
+        CacheConfig {
+            enabled: true,
+            directory: Some(dir.to_string_lossy().to_string()),
+            ttl_seconds: None,
+            max_entries: None,
+        }
+    }
+
+    #[test]
+    fn test_disabled_cache_returns_none() {
+        let config = CacheConfig {
+            enabled: false,
+            ..CacheConfig::default()
+        };
+        assert!(ResponseCache::new(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_put_then_get_hits() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(&test_config(temp_dir.path())).unwrap().unwrap();
+
+        let issues = vec![ReportedIssue {
+            file_path: "src/lib.rs".to_string(),
+            line_number: 10,
+            severity: "high".to_string(),
+            category: "Bug".to_string(),
+            title: "Unchecked unwrap".to_string(),
+            description: "desc".to_string(),
+            suggestion: "fix it".to_string(),
+            code_snippet: None,
+        }];
+
+        cache.put("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}", &issues).unwrap();
+        let hit = cache.get("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}");
+
+        assert_eq!(hit, Some(issues));
+    }
+
+    #[test]
+    fn test_get_misses_on_changed_content() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(&test_config(temp_dir.path())).unwrap().unwrap();
+
+        cache.put("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}", &[]).unwrap();
+        let hit = cache.get("llama3.2:latest", 0.1, "src/lib.rs", "fn main() { changed(); }");
+
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn test_get_misses_on_different_model() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(&test_config(temp_dir.path())).unwrap().unwrap();
+
+        cache.put("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}", &[]).unwrap();
+        let hit = cache.get("codellama:34b", 0.1, "src/lib.rs", "fn main() {}");
+
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn test_expired_entry_is_a_miss() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = test_config(temp_dir.path());
+        config.ttl_seconds = Some(0);
+        let cache = ResponseCache::new(&config).unwrap().unwrap();
+
+        cache.put("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}", &[]).unwrap();
+        // TTL of 0 means even an entry written moments ago has already aged out.
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        let hit = cache.get("llama3.2:latest", 0.1, "src/lib.rs", "fn main() {}");
+
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn test_evicts_oldest_entries_over_cap() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = test_config(temp_dir.path());
+        config.max_entries = Some(2);
+        let cache = ResponseCache::new(&config).unwrap().unwrap();
+
+        cache.put("m", 0.1, "a.rs", "a", &[]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cache.put("m", 0.1, "b.rs", "b", &[]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cache.put("m", 0.1, "c.rs", "c", &[]).unwrap();
+
+        let entries = std::fs::read_dir(temp_dir.path()).unwrap().count();
+        assert_eq!(entries, 2);
+        assert!(cache.get("m", 0.1, "a.rs", "a").is_none());
+        assert!(cache.get("m", 0.1, "c.rs", "c").is_some());
+    }
+}
@@ -3,7 +3,8 @@
 //! This module handles all CLI argument parsing using clap,
 //! including validation and default values.
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// YoAuditor - LLM-powered code auditor for GitHub repos
@@ -17,6 +18,8 @@ use std::path::PathBuf;
 ///   yoauditor --repo local --local ./my-project --format json
 ///   yoauditor --repo https://github.com/owner/repo.git --dry-run
 ///   yoauditor --init-config
+///   yoauditor --apply report.json --local ./my-project --dry-run
+///   yoauditor --serve --port 8080
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
@@ -25,7 +28,12 @@ pub struct Args {
     ///
     /// Supports HTTPS URLs (e.g., https://github.com/owner/repo.git).
     /// Not required when using --init-config or --dry-run with --local.
-    #[arg(short, long, value_name = "URL", required_unless_present = "init_config")]
+    #[arg(
+        short,
+        long,
+        value_name = "URL",
+        required_unless_present_any = ["init_config", "bench", "list_profiles", "list_runs", "apply", "workload", "serve"]
+    )]
     pub repo: Option<String>,
 
     /// Ollama model to use for analysis
@@ -73,6 +81,16 @@ pub struct Args {
     #[arg(short, long)]
     pub quiet: bool,
 
+    /// Also write every log record to this file as newline-delimited JSON
+    /// (no ANSI escapes), independent of console verbosity
+    ///
+    /// Useful when stderr is discarded (e.g. a server or CI invocation):
+    /// the console stays human-readable while this file stays structured
+    /// and greppable. The same records are always collected in-memory and
+    /// attached to the report's `logs` field regardless of this flag.
+    #[arg(long, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
     /// Specific branch to analyze
     ///
     /// If not specified, uses the default branch
@@ -103,7 +121,7 @@ pub struct Args {
     #[arg(long, value_name = "DIR")]
     pub local: Option<PathBuf>,
 
-    /// Output format (markdown, json)
+    /// Output format (markdown, json, sarif, html)
     #[arg(long, default_value = "markdown", value_name = "FORMAT")]
     pub format: OutputFormat,
 
@@ -126,6 +144,20 @@ pub struct Args {
     #[arg(long, value_name = "SECS")]
     pub timeout: Option<u64>,
 
+    /// Per-file (per-chunk) timeout in seconds
+    ///
+    /// Bounds a single chunk's LLM call in single-call mode, separate from
+    /// the overall --timeout. Default: from config or 120s.
+    #[arg(long, value_name = "SECS")]
+    pub file_timeout: Option<u64>,
+
+    /// Retries after the first attempt before a timed-out chunk is given up on
+    ///
+    /// The chunk's files are then marked failed instead of blocking the run.
+    /// Default: from config or 2.
+    #[arg(long, value_name = "N")]
+    pub file_retries: Option<usize>,
+
     /// Use single-call mode (send all files in one request)
     ///
     /// Efficient for cloud/large models. Overrides config file setting.
@@ -154,27 +186,314 @@ pub struct Args {
 
     /// Dry run: clone and scan files without calling the LLM
     ///
-    /// Shows which files would be analyzed and exits.
+    /// Shows which files would be analyzed and exits. Combined with
+    /// --apply, previews the resulting diff instead of writing it.
     #[arg(long)]
     pub dry_run: bool,
 
     /// Generate a default .yoauditor.toml configuration file
     #[arg(long)]
     pub init_config: bool,
+
+    /// Path to write with --init-config, instead of ./.yoauditor.toml
+    #[arg(long, value_name = "PATH", requires = "init_config")]
+    pub init_config_path: Option<PathBuf>,
+
+    /// Overwrite the target file with --init-config if it already exists
+    #[arg(long, requires = "init_config")]
+    pub force: bool,
+
+    /// Watch the local directory and re-audit automatically on file changes
+    ///
+    /// Requires --local. Keeps the process running; after each debounced
+    /// burst of edits, only the changed files are re-analyzed and spliced
+    /// into the aggregated report.
+    #[arg(long, requires = "local", conflicts_with_all = ["dry_run", "init_config", "bench"])]
+    pub watch: bool,
+
+    /// Restrict analysis to files that differ from this git ref
+    ///
+    /// Accepts a branch, tag, or commit SHA. Computed as a committed
+    /// tree-to-tree diff against HEAD (`git diff --name-only` semantics), so
+    /// only the changed files (plus their same-language dependents) are
+    /// analyzed. Requires a real git checkout. Mutually exclusive with
+    /// --changed-only.
+    #[arg(long, value_name = "GITREF", conflicts_with = "changed_only")]
+    pub since: Option<String>,
+
+    /// Restrict analysis to files changed since the merge-base with the default branch
+    ///
+    /// Convenience form of --since: resolves the base ref automatically
+    /// (tries origin/HEAD, then origin/main, origin/master, main, master).
+    /// Requires a real git checkout, same as --since.
+    #[arg(long, conflicts_with = "since")]
+    pub changed_only: bool,
+
+    /// Run a benchmark workload instead of a normal audit
+    ///
+    /// Reads a JSON workload file describing a target repo, an agent
+    /// configuration, and expected issues, runs the analysis, and scores
+    /// the result. See `eval::Workload` for the file format.
+    #[arg(long, value_name = "FILE")]
+    pub bench: Option<PathBuf>,
+
+    /// Where to write the benchmark results (JSON)
+    #[arg(
+        long,
+        default_value = "yoaudit_bench_results.json",
+        value_name = "FILE"
+    )]
+    pub bench_output: PathBuf,
+
+    /// Apply a named flag bundle defined in `.yoauditor.toml`'s `[profiles.<name>]`
+    ///
+    /// Resolution order is: built-in defaults, then the profile's values,
+    /// then any flags passed explicitly on the command line (which always
+    /// win). See `--list-profiles` to see what a profile expands to.
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Print the profiles defined in `.yoauditor.toml` and their resolved flag sets, then exit
+    #[arg(long)]
+    pub list_profiles: bool,
+
+    /// Apply structured fixes from a previously generated JSON report
+    ///
+    /// Reads a JSON report (produced with --format json) and rewrites each
+    /// file's `Issue.fix` edits in place against the directory given by
+    /// --local. Edits within a file are applied bottom-to-top so earlier
+    /// edits don't shift later line numbers; edits that overlap another
+    /// edit, or whose line range no longer matches the file, are skipped
+    /// and reported instead of risking a corrupted file. Combine with
+    /// --dry-run to preview the resulting diff without writing anything.
+    #[arg(
+        long,
+        value_name = "REPORT",
+        requires = "local",
+        conflicts_with_all = ["bench", "init_config", "watch"]
+    )]
+    pub apply: Option<PathBuf>,
+
+    /// Skip the RustSec advisory-database audit of Cargo.lock
+    ///
+    /// By default, a Rust repository's Cargo.lock is checked against the
+    /// RustSec advisory database and any known vulnerabilities are added to
+    /// the report as "Dependency" category issues. Set this to skip that
+    /// pass (e.g. when offline, since it fetches the advisory database).
+    #[arg(long)]
+    pub skip_dependency_audit: bool,
+
+    /// Skip the deterministic rule-engine pass
+    ///
+    /// By default, a built-in set of regex/glob rules (plus any configured
+    /// via `[rules]` in .yoauditor.toml) runs against every scanned file
+    /// and merges its findings into the report alongside the LLM agent's.
+    /// Set this to rely on the agent alone. Has no effect on `--dry-run`'s
+    /// own rule preview beyond suppressing it too.
+    #[arg(long)]
+    pub skip_rules: bool,
+
+    /// Run the supply-chain manifest/lockfile audit
+    ///
+    /// Checks dependency manifests (Cargo.toml/Cargo.lock, package.json/
+    /// package-lock.json, requirements.txt, go.mod/go.sum) for version-
+    /// pinning hygiene -- loose version ranges, git dependencies pinned to
+    /// a mutable branch, manifest/lockfile drift -- and adds findings as
+    /// "Supply Chain" category issues. Off by default since it adds a
+    /// manifest parse pass most runs don't need.
+    #[arg(long)]
+    pub supply_chain: bool,
+
+    /// Diff this audit against a previously generated JSON report
+    ///
+    /// Reads a JSON report (produced with --format json) and, instead of
+    /// writing a full snapshot, classifies each issue as New, Fixed, or
+    /// Persisting relative to it. Issues are matched on file path,
+    /// category, title, and a line number within a few lines of each
+    /// other, so small refactors don't register as churn. With this set,
+    /// --fail-on only considers newly-introduced issues.
+    #[arg(long, value_name = "REPORT")]
+    pub diff_against: Option<PathBuf>,
+
+    /// Path to the baseline/exemptions file
+    ///
+    /// Issues matching a fingerprint in this file are tagged `known` and
+    /// excluded from the --fail-on check, so re-auditing an evolving repo
+    /// only fails CI on genuinely new findings. Still shown in the report.
+    /// Fingerprints are hashed from file path, category, title, and a small
+    /// window of surrounding code, deliberately excluding the line number
+    /// so unrelated edits above a finding don't invalidate it.
+    #[arg(long, default_value = ".yoauditor-baseline.json", value_name = "FILE")]
+    pub baseline: PathBuf,
+
+    /// Rewrite the baseline file from this run's issues
+    ///
+    /// The rest of the run is unchanged: the report is still written, and
+    /// --fail-on is checked against the baseline as it was loaded before
+    /// this rewrite.
+    #[arg(long)]
+    pub update_baseline: bool,
+
+    /// Audit many repos from one JSON workload file instead of a single repo
+    ///
+    /// Reads a JSON file listing audit jobs (repo_url, optional branch,
+    /// local path, and per-job overrides for model/extensions/excludes/
+    /// fail_on) and runs the normal clone-analyze-report pipeline for each,
+    /// in sequence. See `batch::BatchWorkload` for the file format. Results
+    /// are written as a single aggregated JSON roll-up to --output.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["bench", "apply", "init_config", "watch", "repo", "local"]
+    )]
+    pub workload: Option<PathBuf>,
+
+    /// POST the aggregated --workload results to this URL as JSON once done
+    #[arg(long, value_name = "URL", requires = "workload")]
+    pub report_url: Option<String>,
+
+    /// Run as a long-lived HTTP service instead of a single audit
+    ///
+    /// Exposes `POST /v1/audit` (submit a job, body like `{"repo_url": "...",
+    /// "branch": "...", "format": "json", "min_severity": "medium"}`,
+    /// returns a job id) and `GET /v1/audit/{id}` (poll for the resulting
+    /// `Report` JSON). Each job reuses the normal clone-analyze-report
+    /// pipeline; fields left out of the job body fall back to whatever this
+    /// process was started with. See `server` for the route handlers.
+    #[arg(
+        long,
+        conflicts_with_all = ["bench", "workload", "apply", "init_config", "watch", "repo", "local"]
+    )]
+    pub serve: bool,
+
+    /// Port to listen on with --serve
+    #[arg(long, default_value = "8080", value_name = "PORT", requires = "serve")]
+    pub port: u16,
+
+    /// Allow scanner.max_files/scanner.max_file_size above the built-in
+    /// ceilings (10k files / 10MB)
+    ///
+    /// `Config::validate` otherwise rejects a config that would scan that
+    /// much, so an accidental repo-wide scan against a metered cloud model
+    /// can't silently run up a huge bill.
+    #[arg(long)]
+    pub allow_large_scan: bool,
+
+    /// Disable the content-hash response cache, even if `[cache]` enables
+    /// it in config
+    ///
+    /// By default, single-call analysis results are cached by a hash of
+    /// (model, temperature, prompt version, file path, file content) under
+    /// the user cache dir, so
+    /// re-running an audit after editing only a few files skips calling the
+    /// LLM for everything unchanged. Set this to force a full re-analysis.
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Print recorded audit runs as a table, then exit
+    ///
+    /// Reads run records written under `[history].directory` by previous
+    /// audits (see `history::HistoryStore`) so issue counts and duration can
+    /// be compared across runs over time. See --list-runs-repo/--list-runs-model
+    /// to narrow the list.
+    #[arg(long)]
+    pub list_runs: bool,
+
+    /// Only show --list-runs rows for this repo URL
+    #[arg(long, value_name = "URL", requires = "list_runs")]
+    pub list_runs_repo: Option<String>,
+
+    /// Only show --list-runs rows that used this model
+    #[arg(long, value_name = "MODEL", requires = "list_runs")]
+    pub list_runs_model: Option<String>,
+
+    /// Recursively initialize and check out submodules after cloning
+    ///
+    /// Off by default since it adds a pass over the submodule tree that
+    /// most audits don't need. See `repo::CloneOptions::recurse_submodules`.
+    #[arg(long)]
+    pub recurse_submodules: bool,
+
+    /// Path to an SSH private key, for `git@host:owner/repo` style URLs
+    ///
+    /// Falls back to the SSH agent when not set. Ignored for HTTPS URLs.
+    #[arg(long, value_name = "PATH")]
+    pub ssh_key: Option<PathBuf>,
+
+    /// Passphrase for --ssh-key, if it is encrypted
+    #[arg(long, value_name = "PASSPHRASE", requires = "ssh_key")]
+    pub ssh_key_passphrase: Option<String>,
+
+    /// Personal access token for HTTPS authentication
+    ///
+    /// Prefer the YOAUDITOR_HTTPS_TOKEN env var over this flag so the
+    /// token doesn't end up in shell history or process listings.
+    #[arg(long, value_name = "TOKEN", env = "YOAUDITOR_HTTPS_TOKEN")]
+    pub https_token: Option<String>,
+
+    /// Username to pair with --https-token (defaults to "x-access-token")
+    #[arg(long, value_name = "NAME")]
+    pub username: Option<String>,
+
+    /// Number of repos to pre-clone concurrently with --workload
+    ///
+    /// Jobs with a `repo_url` are cloned up front via `repo::clone_repositories`
+    /// instead of one at a time as each job runs. Has no effect on jobs using
+    /// `local`, or without --workload.
+    #[arg(long, default_value = "4", value_name = "NUM", requires = "workload")]
+    pub clone_concurrency: usize,
+
+    /// Pin the clone to a specific commit SHA, tag, or ref instead of the
+    /// default branch tip
+    ///
+    /// Overrides --branch. Accepts anything `git2::Repository::revparse_single`
+    /// understands (e.g. `v1.2.3`, `refs/heads/main`, a full or short SHA).
+    /// Forces a full (non-shallow) clone, since the pinned commit may not be
+    /// reachable from a shallow history. See
+    /// `repo::CloneOptions::reference`.
+    #[arg(long = "ref", value_name = "REF")]
+    pub git_ref: Option<String>,
+
+    /// Keep polling the cloned repo's origin for new commits and re-audit
+    /// incrementally, instead of producing one report and exiting
+    ///
+    /// Like --watch, but for a remote repo rather than a local directory:
+    /// periodically fetches "origin" (see `repo::sync_repository`) and, when
+    /// new commits land, re-analyzes only the files that changed between the
+    /// old and new HEAD. Requires --repo; mutually exclusive with --watch
+    /// (which watches a --local directory's filesystem instead).
+    #[arg(
+        long,
+        conflicts_with_all = ["watch", "local", "dry_run", "init_config", "bench"]
+    )]
+    pub watch_remote: bool,
+
+    /// Seconds between origin fetches with --watch-remote
+    #[arg(long, default_value = "60", value_name = "SECS", requires = "watch_remote")]
+    pub poll_interval: u64,
 }
 
 /// Output format for the report.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     /// Markdown format (default)
     #[default]
     Markdown,
     /// JSON format
     Json,
+    /// SARIF 2.1.0 format, for GitHub code scanning and similar dashboards
+    Sarif,
+    /// Self-contained HTML format, viewable in a browser without any
+    /// external Markdown toolchain
+    Html,
 }
 
 /// Severity level for --fail-on and --min-severity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
 pub enum FailOnLevel {
     Low,
     Medium,
@@ -182,12 +501,69 @@ pub enum FailOnLevel {
     Critical,
 }
 
+/// Whether `id` was set directly on the command line, as opposed to sitting
+/// at its clap default (or coming from `env`). Used to make `--profile`
+/// values lose to an explicit flag even when the flag's value happens to
+/// equal the default.
+fn is_explicit(matches: &clap::ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine)
+}
+
 impl Args {
     /// Parse command-line arguments.
     pub fn parse_args() -> Self {
         Self::parse()
     }
 
+    /// Parse command-line arguments, keeping the `ArgMatches` around so
+    /// `--profile` resolution can tell a flag the user actually typed from
+    /// one just sitting at its default value. See `apply_profile`.
+    pub fn parse_args_with_matches() -> (Self, clap::ArgMatches) {
+        let matches = Self::command().get_matches();
+        let args = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        (args, matches)
+    }
+
+    /// Apply a `--profile`'s values on top of this `Args`, but only for
+    /// fields the user didn't pass explicitly on the command line.
+    /// Resolution order: built-in defaults (already in `self`), then the
+    /// profile, then explicit CLI flags (which always win and are left
+    /// untouched here).
+    pub fn apply_profile(&mut self, profile: &crate::config::Profile, matches: &clap::ArgMatches) {
+        if !is_explicit(matches, "fail_on") {
+            if let Some(level) = profile.fail_on {
+                self.fail_on = Some(level);
+            }
+        }
+        if !is_explicit(matches, "min_severity") {
+            if let Some(level) = profile.min_severity {
+                self.min_severity = Some(level);
+            }
+        }
+        if !is_explicit(matches, "format") {
+            if let Some(format) = profile.format {
+                self.format = format;
+            }
+        }
+        if !is_explicit(matches, "concurrency") {
+            if let Some(concurrency) = profile.concurrency {
+                self.concurrency = concurrency;
+            }
+        }
+        if !is_explicit(matches, "max_chunk_lines") {
+            if let Some(max_chunk_lines) = profile.max_chunk_lines {
+                self.max_chunk_lines = max_chunk_lines;
+            }
+        }
+        if !is_explicit(matches, "single_call") && !is_explicit(matches, "no_single_call") {
+            match profile.single_call {
+                Some(true) => self.single_call = true,
+                Some(false) => self.no_single_call = true,
+                None => {}
+            }
+        }
+    }
+
     /// Get the repo URL, panicking if not set (should be validated first).
     pub fn repo_url(&self) -> &str {
         self.repo.as_deref().unwrap_or("")
@@ -200,6 +576,53 @@ impl Args {
             return Ok(());
         }
 
+        // --serve takes jobs over HTTP instead of from --repo/--local, so the
+        // usual requirements don't apply.
+        if self.serve {
+            return Ok(());
+        }
+
+        // --workload carries its own per-job repo paths, so the usual
+        // --repo/--local requirements don't apply.
+        if let Some(ref workload_path) = self.workload {
+            if !workload_path.exists() {
+                return Err(format!(
+                    "Workload file does not exist: {}",
+                    workload_path.display()
+                ));
+            }
+            return Ok(());
+        }
+
+        // Bench workloads carry their own target repo path, so the usual
+        // --repo/--local requirements don't apply.
+        if let Some(ref bench_path) = self.bench {
+            if !bench_path.exists() {
+                return Err(format!(
+                    "Bench workload file does not exist: {}",
+                    bench_path.display()
+                ));
+            }
+            return Ok(());
+        }
+
+        // --apply rewrites files under --local, so the usual repo/Ollama
+        // checks don't apply. clap's `requires` already enforces --local,
+        // but double-check here so library callers that bypass clap still
+        // get a clear error.
+        if let Some(ref report_path) = self.apply {
+            if self.local.is_none() {
+                return Err("--apply requires --local".to_string());
+            }
+            if !report_path.exists() {
+                return Err(format!(
+                    "Report file does not exist: {}",
+                    report_path.display()
+                ));
+            }
+            return Ok(());
+        }
+
         let repo = self.repo.as_deref().unwrap_or("");
 
         // Validate repository URL format
@@ -226,6 +649,11 @@ impl Args {
             return Err("Concurrency must be at least 1".to_string());
         }
 
+        // Validate clone concurrency
+        if self.clone_concurrency == 0 {
+            return Err("Clone concurrency must be at least 1".to_string());
+        }
+
         // Validate max files
         if self.max_files == 0 {
             return Err("Max files must be at least 1".to_string());
@@ -243,6 +671,66 @@ impl Args {
             }
         }
 
+        // Validate per-file timeout if provided
+        if let Some(file_timeout) = self.file_timeout {
+            if file_timeout == 0 {
+                return Err("File timeout must be at least 1 second".to_string());
+            }
+        }
+
+        // --watch requires --local (clap's `requires` already enforces this,
+        // but double-check here so library callers that bypass clap still
+        // get a clear error).
+        if self.watch && self.local.is_none() {
+            return Err("--watch requires --local".to_string());
+        }
+
+        // --watch-remote requires --repo (clap's `conflicts_with_all` already
+        // enforces this is not also --local, but double-check here so
+        // library callers that bypass clap still get a clear error).
+        if self.watch_remote && self.repo.is_none() {
+            return Err("--watch-remote requires --repo".to_string());
+        }
+
+        // Validate poll interval
+        if self.watch_remote && self.poll_interval == 0 {
+            return Err("Poll interval must be at least 1 second".to_string());
+        }
+
+        // --since/--changed-only need a real git checkout. A fresh clone
+        // always is one; --local needs a quick sanity check here since we
+        // won't touch it again before deciding the file set.
+        if (self.since.is_some() || self.changed_only) && self.local.is_some() {
+            let local_path = self.local.as_ref().unwrap();
+            if !local_path.join(".git").exists() {
+                return Err(format!(
+                    "--since/--changed-only require a git checkout, but {} has no .git directory",
+                    local_path.display()
+                ));
+            }
+        }
+
+        // --diff-against only has a Markdown/JSON rendering; Sarif/Html
+        // don't carry New/Fixed/Persisting status, so reject the
+        // combination instead of silently writing the wrong format.
+        if self.diff_against.is_some()
+            && matches!(self.format, OutputFormat::Sarif | OutputFormat::Html)
+        {
+            return Err(
+                "--diff-against only supports --format markdown or --format json".to_string(),
+            );
+        }
+
+        // Validate the SSH key file if provided
+        if let Some(ref ssh_key) = self.ssh_key {
+            if !ssh_key.exists() {
+                return Err(format!(
+                    "SSH key file does not exist: {}",
+                    ssh_key.display()
+                ));
+            }
+        }
+
         // Validate local directory if provided
         if let Some(ref local_path) = self.local {
             if !local_path.exists() {
@@ -330,6 +818,7 @@ mod tests {
             config: None,
             verbose: false,
             quiet: false,
+            log_file: None,
             branch: None,
             extensions: None,
             exclude: None,
@@ -340,12 +829,48 @@ mod tests {
             temperature: 0.1,
             max_chunk_lines: 4000,
             timeout: None,
+            file_timeout: None,
+            file_retries: None,
             single_call: false,
             no_single_call: false,
             fail_on: None,
             min_severity: None,
             dry_run: false,
             init_config: false,
+            init_config_path: None,
+            force: false,
+            bench: None,
+            bench_output: PathBuf::from("yoaudit_bench_results.json"),
+            watch: false,
+            since: None,
+            changed_only: false,
+            profile: None,
+            list_profiles: false,
+            apply: None,
+            skip_dependency_audit: false,
+            skip_rules: false,
+            supply_chain: false,
+            diff_against: None,
+            baseline: PathBuf::from(".yoauditor-baseline.json"),
+            update_baseline: false,
+            workload: None,
+            report_url: None,
+            serve: false,
+            port: 8080,
+            allow_large_scan: false,
+            no_cache: false,
+            list_runs: false,
+            list_runs_repo: None,
+            list_runs_model: None,
+            recurse_submodules: false,
+            ssh_key: None,
+            ssh_key_passphrase: None,
+            https_token: None,
+            username: None,
+            clone_concurrency: 4,
+            git_ref: None,
+            watch_remote: false,
+            poll_interval: 60,
         }
     }
 
@@ -373,6 +898,114 @@ mod tests {
         assert!(args.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_missing_bench_file() {
+        let mut args = make_args();
+        args.repo = None;
+        args.bench = Some(PathBuf::from("does-not-exist.json"));
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_zero_file_timeout() {
+        let mut args = make_args();
+        args.file_timeout = Some(0);
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_watch_requires_local() {
+        let mut args = make_args();
+        args.watch = true;
+        args.local = None;
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_apply_requires_local() {
+        let mut args = make_args();
+        args.apply = Some(PathBuf::from("report.json"));
+        args.local = None;
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_missing_apply_report_file() {
+        let mut args = make_args();
+        args.apply = Some(PathBuf::from("does-not-exist.json"));
+        args.local = Some(PathBuf::from("."));
+        assert!(args.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_serve_skips_repo_requirement() {
+        let mut args = make_args();
+        args.repo = None;
+        args.serve = true;
+        assert!(args.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validation_changed_only_requires_git_checkout_when_local() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().unwrap();
+        let mut args = make_args();
+        args.local = Some(temp.path().to_path_buf());
+        args.changed_only = true;
+        assert!(args.validate().is_err());
+
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        assert!(args.validate().is_ok());
+    }
+
+    #[test]
+    fn test_apply_profile_fills_in_unset_fields() {
+        let matches = Args::command().get_matches_from([
+            "yoauditor",
+            "--repo",
+            "https://github.com/test/repo",
+        ]);
+        let mut args = make_args();
+        let profile = crate::config::Profile {
+            fail_on: Some(FailOnLevel::High),
+            min_severity: Some(FailOnLevel::Medium),
+            format: Some(OutputFormat::Json),
+            single_call: Some(true),
+            concurrency: None,
+            max_chunk_lines: None,
+        };
+
+        args.apply_profile(&profile, &matches);
+
+        assert_eq!(args.fail_on, Some(FailOnLevel::High));
+        assert_eq!(args.min_severity, Some(FailOnLevel::Medium));
+        assert_eq!(args.format, OutputFormat::Json);
+        assert!(args.single_call);
+        assert!(!args.no_single_call);
+    }
+
+    #[test]
+    fn test_apply_profile_does_not_override_explicit_flags() {
+        let matches = Args::command().get_matches_from([
+            "yoauditor",
+            "--repo",
+            "https://github.com/test/repo",
+            "--fail-on",
+            "low",
+        ]);
+        let mut args = make_args();
+        args.fail_on = Some(FailOnLevel::Low);
+        let profile = crate::config::Profile {
+            fail_on: Some(FailOnLevel::Critical),
+            ..Default::default()
+        };
+
+        args.apply_profile(&profile, &matches);
+
+        assert_eq!(args.fail_on, Some(FailOnLevel::Low));
+    }
+
     #[test]
     fn test_log_level() {
         let mut args = make_args();
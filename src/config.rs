@@ -1,11 +1,15 @@
 //! Configuration file handling.
 //!
 //! This module handles loading and merging configuration from
-//! `.yoauditor.toml` files.
+//! `.yoauditor.toml` files. [`Config::discover`] layers a global config, a
+//! repo-level `.yoauditor.toml`, and a CWD `.yoauditor.toml` together
+//! field-by-field; see `PartialConfig`.
 
+use crate::cli::{FailOnLevel, OutputFormat};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Root configuration structure.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -25,6 +29,28 @@ pub struct Config {
     /// Report settings.
     #[serde(default)]
     pub report: ReportConfig,
+
+    /// Deterministic rule-engine settings (see `crate::rules`).
+    #[serde(default)]
+    pub rules: RulesConfig,
+
+    /// Supply-chain manifest/lockfile audit settings (see
+    /// `crate::supply_chain`).
+    #[serde(default)]
+    pub supply_chain: SupplyChainConfig,
+
+    /// Named flag bundles, activated with `--profile <name>`, e.g.
+    /// `[profiles.ci]`. See `Profile`.
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+
+    /// Content-hash response cache settings (see `crate::cache::ResponseCache`).
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Persisted run-history settings (see `crate::history::HistoryStore`).
+    #[serde(default)]
+    pub history: HistoryConfig,
 }
 
 /// General application settings.
@@ -41,6 +67,10 @@ pub struct GeneralConfig {
     /// Number of concurrent file analyses.
     #[serde(default = "default_concurrency")]
     pub concurrency: usize,
+
+    /// Path to the baseline/exemptions file (see `baseline::Baseline`).
+    #[serde(default = "default_baseline")]
+    pub baseline: String,
 }
 
 impl Default for GeneralConfig {
@@ -49,6 +79,7 @@ impl Default for GeneralConfig {
             output: default_output(),
             verbose: false,
             concurrency: default_concurrency(),
+            baseline: default_baseline(),
         }
     }
 }
@@ -61,6 +92,10 @@ fn default_concurrency() -> usize {
     4
 }
 
+fn default_baseline() -> String {
+    ".yoauditor-baseline.json".to_string()
+}
+
 /// LLM model settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
@@ -93,6 +128,17 @@ pub struct ModelConfig {
     /// If false: uses tool-calling (many API calls).
     #[serde(default = "default_single_call")]
     pub single_call_mode: bool,
+
+    /// Per-chunk timeout in single-call mode, separate from the overall
+    /// request `timeout_seconds`. Bounds how long one pathological file (or
+    /// small group of files) can hold up the run before it's retried.
+    #[serde(default = "default_file_timeout")]
+    pub file_timeout_seconds: u64,
+
+    /// Retries after the first attempt before a chunk that keeps timing out
+    /// is given up on and marked failed.
+    #[serde(default = "default_file_retries")]
+    pub file_retries: usize,
 }
 
 impl Default for ModelConfig {
@@ -105,6 +151,8 @@ impl Default for ModelConfig {
             timeout_seconds: default_timeout(),
             retries: default_retries(),
             single_call_mode: true, // Default to efficient mode
+            file_timeout_seconds: default_file_timeout(),
+            file_retries: default_file_retries(),
         }
     }
 }
@@ -133,6 +181,14 @@ fn default_single_call() -> bool {
     true
 }
 
+fn default_file_timeout() -> u64 {
+    120 // 2 min per chunk; one slow file shouldn't burn the whole run
+}
+
+fn default_file_retries() -> usize {
+    2
+}
+
 /// File scanner settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScannerConfig {
@@ -155,6 +211,11 @@ pub struct ScannerConfig {
     /// Maximum file size in bytes.
     #[serde(default = "default_max_file_size")]
     pub max_file_size: usize,
+
+    /// Parse `.gitignore` files encountered while walking the tree and
+    /// apply their rules hierarchically, in addition to `excludes`.
+    #[serde(default)]
+    pub respect_gitignore: bool,
 }
 
 impl Default for ScannerConfig {
@@ -165,6 +226,7 @@ impl Default for ScannerConfig {
             extensions: default_extensions(),
             excludes: default_excludes(),
             max_file_size: default_max_file_size(),
+            respect_gitignore: false,
         }
     }
 }
@@ -249,6 +311,576 @@ fn default_snippet_lines() -> usize {
     10
 }
 
+/// Deterministic rule-engine settings (see `crate::rules::RuleSet`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulesConfig {
+    /// Run the deterministic rule pass alongside (or, in `--dry-run`,
+    /// instead of) the LLM agent.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Path to an additional TOML or JSON file of user-defined rules
+    /// (`.json` extension selects the JSON parser, anything else TOML),
+    /// merged with the shipped defaults rather than replacing them.
+    #[serde(default)]
+    pub extra_rules_file: Option<String>,
+
+    /// Rule `id`s to skip, including shipped defaults.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            extra_rules_file: None,
+            disabled: Vec::new(),
+        }
+    }
+}
+
+/// Supply-chain manifest/lockfile audit settings (see
+/// `crate::supply_chain::audit_supply_chain`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplyChainConfig {
+    /// Run the supply-chain audit. Off by default: unlike the rule engine,
+    /// it adds a manifest/lockfile parse pass per ecosystem that most runs
+    /// don't need; enable with `--supply-chain` or this setting.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Path to an optional local advisory list (TOML or JSON, `.json`
+    /// extension selects the JSON parser) of package names to flag if
+    /// found in any detected manifest.
+    #[serde(default)]
+    pub advisory_file: Option<String>,
+}
+
+impl Default for SupplyChainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            advisory_file: None,
+        }
+    }
+}
+
+/// Content-hash response cache settings (see `crate::cache::ResponseCache`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Cache single-call analysis results keyed by file content, model, and
+    /// temperature, and reuse them instead of re-sending unchanged files to
+    /// the LLM. On by default since it only ever skips work that would
+    /// produce the same result; disable with `--no-cache` or this setting.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Cache directory. Defaults to a `yoauditor/responses` subdirectory of
+    /// the platform user cache dir (e.g. `~/.cache` on Linux) when unset.
+    #[serde(default)]
+    pub directory: Option<String>,
+
+    /// Entries older than this many seconds are treated as a miss and
+    /// re-analyzed. `None` (the default) means entries never expire on
+    /// their own.
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
+
+    /// Maximum number of cache entries to keep; once exceeded, the oldest
+    /// entries are evicted. `None` (the default) means no cap.
+    #[serde(default)]
+    pub max_entries: Option<usize>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+            ttl_seconds: None,
+            max_entries: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Resolve the directory cache entries are read from/written to:
+    /// `directory` if set, else `yoauditor/responses` under the platform
+    /// user cache dir, falling back to the system temp dir if that can't be
+    /// resolved (e.g. no `HOME` set).
+    pub fn resolved_directory(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::cache_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join("yoauditor")
+                .join("responses"),
+        }
+    }
+}
+
+/// Serialization format for persisted run-history records (see
+/// `crate::history::HistoryStore`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryFormat {
+    /// One JSON object per run record file (default).
+    #[default]
+    Json,
+    /// One TOML document per run record file.
+    Toml,
+}
+
+impl HistoryFormat {
+    /// File extension used for a record written in this format, so
+    /// `HistoryStore::list` knows which files in the runs directory are
+    /// its own.
+    pub fn extension(self) -> &'static str {
+        match self {
+            HistoryFormat::Json => "json",
+            HistoryFormat::Toml => "toml",
+        }
+    }
+}
+
+/// Persisted run-history settings (see `crate::history::HistoryStore`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Write a small metadata record for each completed audit, so `--list-runs`
+    /// can compare issue counts across runs over time. On by default.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Directory run records are written to. Defaults to a `yoauditor/runs`
+    /// subdirectory of the platform user data dir when unset.
+    #[serde(default)]
+    pub directory: Option<String>,
+
+    /// Serialization format for run record files.
+    #[serde(default)]
+    pub format: HistoryFormat,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+            format: HistoryFormat::default(),
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Resolve the directory run records are read from/written to:
+    /// `directory` if set, else `yoauditor/runs` under the platform user
+    /// data dir, falling back to the system temp dir if that can't be
+    /// resolved.
+    pub fn resolved_directory(&self) -> PathBuf {
+        match &self.directory {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::data_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join("yoauditor")
+                .join("runs"),
+        }
+    }
+}
+
+/// A named bundle of flag values, defined under `[profiles.<name>]` in
+/// `.yoauditor.toml` and activated with `--profile <name>` (see
+/// `Args::apply_profile`). Every field is optional: anything left unset
+/// falls through to the built-in default, and any flag the user passes
+/// explicitly on the command line always wins over the profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Equivalent to `--fail-on`.
+    #[serde(default)]
+    pub fail_on: Option<FailOnLevel>,
+    /// Equivalent to `--min-severity`.
+    #[serde(default)]
+    pub min_severity: Option<FailOnLevel>,
+    /// Equivalent to `--format`.
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    /// `true` for `--single-call`, `false` for `--no-single-call`.
+    #[serde(default)]
+    pub single_call: Option<bool>,
+    /// Equivalent to `--concurrency`.
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Equivalent to `--max-chunk-lines`.
+    #[serde(default)]
+    pub max_chunk_lines: Option<usize>,
+}
+
+/// Mirror of `Config` with every field wrapped in `Option`, used only by
+/// [`Config::discover`] to tell "this file set this field" apart from "serde
+/// filled this field in with its default", so layering files together can
+/// override field-by-field instead of replacing a whole section.
+///
+/// Each `#[serde(default)]` field is `None` when the file omits it, `Some`
+/// when the file sets it (including to a value that happens to match the
+/// built-in default). `layer_over` then folds a higher-priority partial over
+/// a lower-priority one, `Some` winning over `None`, and `into_config` fills
+/// any field still `None` after every layer with the same `default_*`
+/// function `Config`'s own `Default` impl uses.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialConfig {
+    #[serde(default)]
+    general: PartialGeneralConfig,
+    #[serde(default)]
+    model: PartialModelConfig,
+    #[serde(default)]
+    scanner: PartialScannerConfig,
+    #[serde(default)]
+    report: PartialReportConfig,
+    #[serde(default)]
+    rules: PartialRulesConfig,
+    #[serde(default)]
+    supply_chain: PartialSupplyChainConfig,
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+    #[serde(default)]
+    cache: PartialCacheConfig,
+    #[serde(default)]
+    history: PartialHistoryConfig,
+}
+
+impl PartialConfig {
+    /// Fold `self` (higher priority) over `lower`: any field `self` left
+    /// unset falls through to `lower`'s value for that field. Profiles are
+    /// combined by name, `self`'s entry winning on a name collision.
+    fn layer_over(self, lower: Self) -> Self {
+        let mut profiles = lower.profiles;
+        profiles.extend(self.profiles);
+
+        Self {
+            general: self.general.layer_over(lower.general),
+            model: self.model.layer_over(lower.model),
+            scanner: self.scanner.layer_over(lower.scanner),
+            report: self.report.layer_over(lower.report),
+            rules: self.rules.layer_over(lower.rules),
+            supply_chain: self.supply_chain.layer_over(lower.supply_chain),
+            profiles,
+            cache: self.cache.layer_over(lower.cache),
+            history: self.history.layer_over(lower.history),
+        }
+    }
+
+    /// Materialize into a concrete `Config`, applying the built-in defaults
+    /// to any field no layer set.
+    fn into_config(self) -> Config {
+        Config {
+            general: self.general.into_config(),
+            model: self.model.into_config(),
+            scanner: self.scanner.into_config(),
+            report: self.report.into_config(),
+            rules: self.rules.into_config(),
+            supply_chain: self.supply_chain.into_config(),
+            profiles: self.profiles,
+            cache: self.cache.into_config(),
+            history: self.history.into_config(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialGeneralConfig {
+    output: Option<String>,
+    verbose: Option<bool>,
+    concurrency: Option<usize>,
+    baseline: Option<String>,
+}
+
+impl PartialGeneralConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            output: self.output.or(lower.output),
+            verbose: self.verbose.or(lower.verbose),
+            concurrency: self.concurrency.or(lower.concurrency),
+            baseline: self.baseline.or(lower.baseline),
+        }
+    }
+
+    fn into_config(self) -> GeneralConfig {
+        GeneralConfig {
+            output: self.output.unwrap_or_else(default_output),
+            verbose: self.verbose.unwrap_or(false),
+            concurrency: self.concurrency.unwrap_or_else(default_concurrency),
+            baseline: self.baseline.unwrap_or_else(default_baseline),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialModelConfig {
+    name: Option<String>,
+    ollama_url: Option<String>,
+    temperature: Option<f32>,
+    #[serde(default)]
+    max_tokens: Option<usize>,
+    timeout_seconds: Option<u64>,
+    retries: Option<usize>,
+    single_call_mode: Option<bool>,
+    file_timeout_seconds: Option<u64>,
+    file_retries: Option<usize>,
+}
+
+impl PartialModelConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            name: self.name.or(lower.name),
+            ollama_url: self.ollama_url.or(lower.ollama_url),
+            temperature: self.temperature.or(lower.temperature),
+            max_tokens: self.max_tokens.or(lower.max_tokens),
+            timeout_seconds: self.timeout_seconds.or(lower.timeout_seconds),
+            retries: self.retries.or(lower.retries),
+            single_call_mode: self.single_call_mode.or(lower.single_call_mode),
+            file_timeout_seconds: self.file_timeout_seconds.or(lower.file_timeout_seconds),
+            file_retries: self.file_retries.or(lower.file_retries),
+        }
+    }
+
+    fn into_config(self) -> ModelConfig {
+        ModelConfig {
+            name: self.name.unwrap_or_else(default_model),
+            ollama_url: self.ollama_url.unwrap_or_else(default_ollama_url),
+            temperature: self.temperature.unwrap_or_else(default_temperature),
+            max_tokens: self.max_tokens,
+            timeout_seconds: self.timeout_seconds.unwrap_or_else(default_timeout),
+            retries: self.retries.unwrap_or_else(default_retries),
+            single_call_mode: self.single_call_mode.unwrap_or_else(default_single_call),
+            file_timeout_seconds: self.file_timeout_seconds.unwrap_or_else(default_file_timeout),
+            file_retries: self.file_retries.unwrap_or_else(default_file_retries),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialScannerConfig {
+    max_files: Option<usize>,
+    max_chunk_lines: Option<usize>,
+    extensions: Option<Vec<String>>,
+    excludes: Option<Vec<String>>,
+    max_file_size: Option<usize>,
+    respect_gitignore: Option<bool>,
+}
+
+impl PartialScannerConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            max_files: self.max_files.or(lower.max_files),
+            max_chunk_lines: self.max_chunk_lines.or(lower.max_chunk_lines),
+            extensions: self.extensions.or(lower.extensions),
+            excludes: self.excludes.or(lower.excludes),
+            max_file_size: self.max_file_size.or(lower.max_file_size),
+            respect_gitignore: self.respect_gitignore.or(lower.respect_gitignore),
+        }
+    }
+
+    fn into_config(self) -> ScannerConfig {
+        ScannerConfig {
+            max_files: self.max_files.unwrap_or_else(default_max_files),
+            max_chunk_lines: self.max_chunk_lines.unwrap_or_else(default_max_chunk_lines),
+            extensions: self.extensions.unwrap_or_else(default_extensions),
+            excludes: self.excludes.unwrap_or_else(default_excludes),
+            max_file_size: self.max_file_size.unwrap_or_else(default_max_file_size),
+            respect_gitignore: self.respect_gitignore.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialReportConfig {
+    include_snippets: Option<bool>,
+    include_summaries: Option<bool>,
+    max_snippet_lines: Option<usize>,
+    group_by_file: Option<bool>,
+}
+
+impl PartialReportConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            include_snippets: self.include_snippets.or(lower.include_snippets),
+            include_summaries: self.include_summaries.or(lower.include_summaries),
+            max_snippet_lines: self.max_snippet_lines.or(lower.max_snippet_lines),
+            group_by_file: self.group_by_file.or(lower.group_by_file),
+        }
+    }
+
+    fn into_config(self) -> ReportConfig {
+        ReportConfig {
+            include_snippets: self.include_snippets.unwrap_or(true),
+            include_summaries: self.include_summaries.unwrap_or(true),
+            max_snippet_lines: self.max_snippet_lines.unwrap_or_else(default_snippet_lines),
+            group_by_file: self.group_by_file.unwrap_or(true),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialRulesConfig {
+    enabled: Option<bool>,
+    extra_rules_file: Option<String>,
+    disabled: Option<Vec<String>>,
+}
+
+impl PartialRulesConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            enabled: self.enabled.or(lower.enabled),
+            extra_rules_file: self.extra_rules_file.or(lower.extra_rules_file),
+            disabled: self.disabled.or(lower.disabled),
+        }
+    }
+
+    fn into_config(self) -> RulesConfig {
+        RulesConfig {
+            enabled: self.enabled.unwrap_or(true),
+            extra_rules_file: self.extra_rules_file,
+            disabled: self.disabled.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialSupplyChainConfig {
+    enabled: Option<bool>,
+    advisory_file: Option<String>,
+}
+
+impl PartialSupplyChainConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            enabled: self.enabled.or(lower.enabled),
+            advisory_file: self.advisory_file.or(lower.advisory_file),
+        }
+    }
+
+    fn into_config(self) -> SupplyChainConfig {
+        SupplyChainConfig {
+            enabled: self.enabled.unwrap_or(false),
+            advisory_file: self.advisory_file,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialCacheConfig {
+    enabled: Option<bool>,
+    directory: Option<String>,
+    ttl_seconds: Option<u64>,
+    max_entries: Option<usize>,
+}
+
+impl PartialCacheConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            enabled: self.enabled.or(lower.enabled),
+            directory: self.directory.or(lower.directory),
+            ttl_seconds: self.ttl_seconds.or(lower.ttl_seconds),
+            max_entries: self.max_entries.or(lower.max_entries),
+        }
+    }
+
+    fn into_config(self) -> CacheConfig {
+        CacheConfig {
+            enabled: self.enabled.unwrap_or(true),
+            directory: self.directory,
+            ttl_seconds: self.ttl_seconds,
+            max_entries: self.max_entries,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PartialHistoryConfig {
+    enabled: Option<bool>,
+    directory: Option<String>,
+    format: Option<HistoryFormat>,
+}
+
+impl PartialHistoryConfig {
+    fn layer_over(self, lower: Self) -> Self {
+        Self {
+            enabled: self.enabled.or(lower.enabled),
+            directory: self.directory.or(lower.directory),
+            format: self.format.or(lower.format),
+        }
+    }
+
+    fn into_config(self) -> HistoryConfig {
+        HistoryConfig {
+            enabled: self.enabled.unwrap_or(true),
+            directory: self.directory,
+            format: self.format.unwrap_or_default(),
+        }
+    }
+}
+
+/// Overwrite `field` with `var`'s value verbatim, if set. Used by
+/// `Config::apply_env` for plain `String` fields, which can't fail to parse.
+fn apply_env_string(var: &str, field: &mut String) {
+    if let Ok(value) = std::env::var(var) {
+        *field = value;
+    }
+}
+
+/// Like `apply_env_string`, but for `Option<String>` fields.
+fn apply_env_option_string(var: &str, field: &mut Option<String>) {
+    if let Ok(value) = std::env::var(var) {
+        *field = Some(value);
+    }
+}
+
+/// Overwrite `field` with `var`'s value split on commas, if set. Used by
+/// `Config::apply_env` for `Vec<String>` fields (`extensions`, `excludes`,
+/// `disabled`), mirroring the CLI's `value_delimiter = ','` list flags.
+fn apply_env_list(var: &str, field: &mut Vec<String>) {
+    if let Ok(value) = std::env::var(var) {
+        *field = value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect();
+    }
+}
+
+/// Overwrite `field` with `var`'s value, parsed via `FromStr`, if set.
+/// Returns an error (instead of silently keeping the old value) if the
+/// variable is set to something that doesn't parse, so a typo in a CI
+/// pipeline's env fails the run instead of quietly using a wrong default.
+fn apply_env_parsed<T>(var: &str, field: &mut T) -> Result<()>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    if let Ok(value) = std::env::var(var) {
+        *field = value
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid value for {}: {}", var, e))?;
+    }
+    Ok(())
+}
+
+/// Like `apply_env_parsed`, but for `Option<T>` fields.
+fn apply_env_parsed_option<T>(var: &str, field: &mut Option<T>) -> Result<()>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    if let Ok(value) = std::env::var(var) {
+        let parsed = value
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid value for {}: {}", var, e))?;
+        *field = Some(parsed);
+    }
+    Ok(())
+}
+
 impl Config {
     /// Load configuration from a file path.
     pub fn load(path: &Path) -> Result<Self> {
@@ -261,28 +893,147 @@ impl Config {
         Ok(config)
     }
 
-    /// Try to load configuration from the default location.
+    /// Resolve a `Config` by layering every config file this tool knows
+    /// about, lowest-priority-first:
+    ///
+    /// 1. A global file shared across projects (e.g.
+    ///    `~/.config/yoauditor/config.toml`, resolved via the `dirs` crate).
+    /// 2. `.yoauditor.toml` in `repo_path` (the repository being audited).
+    /// 3. `.yoauditor.toml` in the current directory, in case the CLI is
+    ///    invoked from somewhere other than the repo root.
     ///
-    /// Returns `Ok(None)` if the file doesn't exist, `Err` if it exists but can't be parsed.
-    pub fn load_default() -> Result<Option<Self>> {
-        let default_path = Path::new(".yoauditor.toml");
+    /// Unlike [`Config::load`], which replaces the whole struct, each layer
+    /// here overrides the previous one field-by-field: a global file can set
+    /// `model.ollama_url` while a repo file sets only `scanner.extensions`
+    /// without the repo file's silence on `ollama_url` clobbering it. See
+    /// `PartialConfig`.
+    pub fn discover(repo_path: &Path) -> Result<Config> {
+        let mut partial = PartialConfig::default();
+
+        if let Some(global_path) = Self::global_config_path() {
+            if global_path.exists() {
+                partial = Self::load_partial(&global_path)?.layer_over(partial);
+            }
+        }
 
-        if default_path.exists() {
-            Ok(Some(Self::load(default_path)?))
-        } else {
-            Ok(None)
+        let repo_config_path = repo_path.join(".yoauditor.toml");
+        if repo_config_path.exists() {
+            partial = Self::load_partial(&repo_config_path)?.layer_over(partial);
         }
+
+        let cwd_config_path = Path::new(".yoauditor.toml");
+        if cwd_config_path.exists() {
+            partial = Self::load_partial(cwd_config_path)?.layer_over(partial);
+        }
+
+        Ok(partial.into_config())
     }
 
-    /// Try to load configuration from a repo directory.
-    pub fn load_from_repo(repo_path: &Path) -> Result<Option<Self>> {
-        let config_path = repo_path.join(".yoauditor.toml");
+    /// Path to the optional global config file, e.g.
+    /// `~/.config/yoauditor/config.toml` on Linux. `None` if the platform
+    /// has no resolvable config directory.
+    fn global_config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("yoauditor").join("config.toml"))
+    }
+
+    /// Read and parse one layer of `PartialConfig` from `path`.
+    fn load_partial(path: &Path) -> Result<PartialConfig> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
 
-        if config_path.exists() {
-            Ok(Some(Self::load(&config_path)?))
-        } else {
-            Ok(None)
+    /// Apply environment-variable overrides, one priority level below CLI
+    /// flags: call this after [`Config::discover`]/[`Config::load`] and
+    /// before [`Config::merge_with_args`]. Each field maps to
+    /// `YOAUDITOR_<SECTION>_<FIELD>` (e.g. `YOAUDITOR_MODEL_TEMPERATURE`,
+    /// `YOAUDITOR_SCANNER_MAX_FILES`), letting CI and container deployments
+    /// configure a run without writing a TOML file or a long argument list.
+    /// A var that's set but fails to parse into its field's type is a hard
+    /// error rather than a silent ignore, since a typo'd env var deserves to
+    /// fail the run loudly instead of quietly falling back to the default.
+    pub fn apply_env(&mut self) -> Result<()> {
+        apply_env_string("YOAUDITOR_OUTPUT", &mut self.general.output);
+        apply_env_parsed("YOAUDITOR_VERBOSE", &mut self.general.verbose)?;
+        apply_env_parsed("YOAUDITOR_CONCURRENCY", &mut self.general.concurrency)?;
+        apply_env_string("YOAUDITOR_BASELINE", &mut self.general.baseline);
+
+        apply_env_string("YOAUDITOR_MODEL_NAME", &mut self.model.name);
+        apply_env_string("YOAUDITOR_OLLAMA_URL", &mut self.model.ollama_url);
+        apply_env_parsed("YOAUDITOR_MODEL_TEMPERATURE", &mut self.model.temperature)?;
+        apply_env_parsed_option("YOAUDITOR_MODEL_MAX_TOKENS", &mut self.model.max_tokens)?;
+        apply_env_parsed("YOAUDITOR_MODEL_TIMEOUT_SECONDS", &mut self.model.timeout_seconds)?;
+        apply_env_parsed("YOAUDITOR_MODEL_RETRIES", &mut self.model.retries)?;
+        apply_env_parsed("YOAUDITOR_MODEL_SINGLE_CALL_MODE", &mut self.model.single_call_mode)?;
+        apply_env_parsed(
+            "YOAUDITOR_MODEL_FILE_TIMEOUT_SECONDS",
+            &mut self.model.file_timeout_seconds,
+        )?;
+        apply_env_parsed("YOAUDITOR_MODEL_FILE_RETRIES", &mut self.model.file_retries)?;
+
+        apply_env_parsed("YOAUDITOR_SCANNER_MAX_FILES", &mut self.scanner.max_files)?;
+        apply_env_parsed(
+            "YOAUDITOR_SCANNER_MAX_CHUNK_LINES",
+            &mut self.scanner.max_chunk_lines,
+        )?;
+        apply_env_list("YOAUDITOR_SCANNER_EXTENSIONS", &mut self.scanner.extensions);
+        apply_env_list("YOAUDITOR_SCANNER_EXCLUDES", &mut self.scanner.excludes);
+        apply_env_parsed("YOAUDITOR_SCANNER_MAX_FILE_SIZE", &mut self.scanner.max_file_size)?;
+        apply_env_parsed(
+            "YOAUDITOR_SCANNER_RESPECT_GITIGNORE",
+            &mut self.scanner.respect_gitignore,
+        )?;
+
+        apply_env_parsed(
+            "YOAUDITOR_REPORT_INCLUDE_SNIPPETS",
+            &mut self.report.include_snippets,
+        )?;
+        apply_env_parsed(
+            "YOAUDITOR_REPORT_INCLUDE_SUMMARIES",
+            &mut self.report.include_summaries,
+        )?;
+        apply_env_parsed(
+            "YOAUDITOR_REPORT_MAX_SNIPPET_LINES",
+            &mut self.report.max_snippet_lines,
+        )?;
+        apply_env_parsed("YOAUDITOR_REPORT_GROUP_BY_FILE", &mut self.report.group_by_file)?;
+
+        apply_env_parsed("YOAUDITOR_RULES_ENABLED", &mut self.rules.enabled)?;
+        apply_env_option_string(
+            "YOAUDITOR_RULES_EXTRA_RULES_FILE",
+            &mut self.rules.extra_rules_file,
+        );
+        apply_env_list("YOAUDITOR_RULES_DISABLED", &mut self.rules.disabled);
+
+        apply_env_parsed("YOAUDITOR_SUPPLY_CHAIN_ENABLED", &mut self.supply_chain.enabled)?;
+        apply_env_option_string(
+            "YOAUDITOR_SUPPLY_CHAIN_ADVISORY_FILE",
+            &mut self.supply_chain.advisory_file,
+        );
+
+        apply_env_parsed("YOAUDITOR_CACHE_ENABLED", &mut self.cache.enabled)?;
+        apply_env_option_string("YOAUDITOR_CACHE_DIRECTORY", &mut self.cache.directory);
+        apply_env_parsed_option("YOAUDITOR_CACHE_TTL_SECONDS", &mut self.cache.ttl_seconds)?;
+        apply_env_parsed_option("YOAUDITOR_CACHE_MAX_ENTRIES", &mut self.cache.max_entries)?;
+
+        apply_env_parsed("YOAUDITOR_HISTORY_ENABLED", &mut self.history.enabled)?;
+        apply_env_option_string("YOAUDITOR_HISTORY_DIRECTORY", &mut self.history.directory);
+        if let Ok(value) = std::env::var("YOAUDITOR_HISTORY_FORMAT") {
+            self.history.format = match value.to_lowercase().as_str() {
+                "json" => HistoryFormat::Json,
+                "toml" => HistoryFormat::Toml,
+                other => {
+                    return Err(anyhow::anyhow!(
+                        "Invalid value for YOAUDITOR_HISTORY_FORMAT: '{}' (expected 'json' or 'toml')",
+                        other
+                    ))
+                }
+            };
         }
+
+        Ok(())
     }
 
     /// Merge this configuration with CLI arguments.
@@ -300,6 +1051,14 @@ impl Config {
             self.model.timeout_seconds = timeout;
         }
 
+        // Per-file timeout/retries - only override if explicitly provided via CLI
+        if let Some(file_timeout) = args.file_timeout {
+            self.model.file_timeout_seconds = file_timeout;
+        }
+        if let Some(file_retries) = args.file_retries {
+            self.model.file_retries = file_retries;
+        }
+
         // Single-call mode - only override if explicitly provided via CLI flags
         if args.single_call {
             self.model.single_call_mode = true;
@@ -321,21 +1080,270 @@ impl Config {
 
         // General settings
         self.general.concurrency = args.concurrency;
+        self.general.baseline = args.baseline.to_string_lossy().to_string();
 
         // Flags always override
         if args.verbose {
             self.general.verbose = true;
         }
+
+        // --no-cache always wins; there's no CLI flag to force caching back
+        // on over a config file that disabled it.
+        if args.no_cache {
+            self.cache.enabled = false;
+        }
     }
 
-    /// Generate a default configuration file content.
-    #[allow(dead_code)] // Utility for generating example config
+    /// Generate a fully commented `.yoauditor.toml` template: every section
+    /// and field at its `Config::default()` value, each preceded by a doc
+    /// comment explaining the setting and its valid range. Used by
+    /// `yoauditor --init-config` (see `main::handle_init_config`) so new
+    /// users have a discoverable starting point instead of reading the
+    /// source to learn which keys exist. Values are pulled from
+    /// `Config::default()` rather than hardcoded, so the template can't
+    /// silently drift out of sync with the struct's actual defaults.
     pub fn default_toml() -> String {
         let config = Config::default();
-        toml::to_string_pretty(&config).unwrap_or_else(|_| String::new())
+        let m = &config.model;
+        let s = &config.scanner;
+        let r = &config.report;
+        let g = &config.general;
+
+        format!(
+            r#"# yoauditor configuration file.
+# Every key below is shown at its built-in default value; uncomment and
+# edit a line to override it. CLI flags always win over this file, and
+# `--profile <name>` can layer a named bundle of overrides on top of these
+# defaults (see the commented-out [profiles.example] section at the bottom).
+
+[general]
+# Default output file path, overridden by --output.
+output = "{output}"
+# Enable verbose logging by default, overridden by --verbose/--quiet.
+verbose = {verbose}
+# Number of files analyzed concurrently, overridden by --concurrency.
+concurrency = {concurrency}
+# Path to the baseline/exemptions file (see --update-baseline).
+baseline = "{baseline}"
+
+[model]
+# Ollama model to use, overridden by --model.
+name = "{model_name}"
+# Ollama API URL, overridden by --ollama-url.
+ollama_url = "{ollama_url}"
+# Sampling temperature. Valid range 0.0-2.0; lower is more deterministic,
+# which is usually what you want for a code audit. Overridden by --temperature.
+temperature = {temperature}
+# Maximum tokens in a single model response. Unset (the default) means no
+# cap beyond the model's own limit.
+# max_tokens = 4096
+# Request timeout in seconds, overridden by --timeout.
+timeout_seconds = {timeout_seconds}
+# Retries on a failed request, overridden by --retries.
+retries = {retries}
+# Single-call mode reads every file and sends them in one API call, which
+# is far cheaper against metered cloud models but needs a large enough
+# context window. When false, the agent instead uses tool-calling (many
+# smaller API calls). Overridden by --single-call/--no-single-call.
+single_call_mode = {single_call_mode}
+# Per-chunk timeout in single-call mode, separate from timeout_seconds:
+# bounds how long one pathological file (or small group of files) can hold
+# up the run before it's retried. Overridden by --file-timeout.
+file_timeout_seconds = {file_timeout_seconds}
+# Retries after the first attempt before a chunk that keeps timing out is
+# given up on and marked failed. Overridden by --file-retries.
+file_retries = {file_retries}
+
+[scanner]
+# Maximum number of files to analyze. Above 10000 requires --allow-large-scan.
+max_files = {max_files}
+# Maximum lines per file chunk in single-call mode, overridden by --max-chunk-lines.
+max_chunk_lines = {max_chunk_lines}
+# File extensions to include (without the leading dot).
+extensions = {extensions}
+# Path patterns to exclude, matched against any path component.
+excludes = {excludes}
+# Maximum file size in bytes to analyze. Above 10MB requires --allow-large-scan.
+max_file_size = {max_file_size}
+# Also apply any .gitignore files found while walking the tree, in addition
+# to excludes above.
+respect_gitignore = {respect_gitignore}
+
+[report]
+# Include code snippets around each reported issue.
+include_snippets = {include_snippets}
+# Include a per-file summary section.
+include_summaries = {include_summaries}
+# Maximum snippet lines shown per issue.
+max_snippet_lines = {max_snippet_lines}
+# Group issues by file (true) or by severity (false) in the report.
+group_by_file = {group_by_file}
+
+[rules]
+# Run the deterministic rule pass alongside (or, in --dry-run, instead of)
+# the LLM agent, overridden by --skip-rules.
+enabled = {rules_enabled}
+# Path to an additional TOML or JSON file of user-defined rules (.json
+# extension selects the JSON parser), merged with the shipped defaults.
+# extra_rules_file = "my-rules.toml"
+# Rule ids to skip, including shipped defaults.
+disabled = []
+
+[supply_chain]
+# Run the supply-chain manifest/lockfile audit, overridden by --supply-chain.
+# Off by default: it adds a manifest parse pass per ecosystem that most
+# runs don't need.
+enabled = {supply_chain_enabled}
+# Path to an optional local advisory list (TOML or JSON) of package names
+# to flag if found in any detected manifest.
+# advisory_file = "advisories.toml"
+
+[cache]
+# Cache single-call analysis results keyed by file content, model, and
+# temperature, and reuse them instead of re-sending unchanged files to the
+# LLM. On by default; disable with --no-cache.
+enabled = {cache_enabled}
+# Cache directory. Defaults to a yoauditor/responses subdirectory of the
+# platform user cache dir when unset.
+# directory = "/home/me/.cache/yoauditor/responses"
+# Entries older than this many seconds are treated as a miss. Unset (the
+# default) means entries never expire on their own.
+# ttl_seconds = 604800
+# Maximum number of cache entries to keep; oldest entries are evicted once
+# exceeded. Unset (the default) means no cap.
+# max_entries = 5000
+
+[history]
+# Write a small metadata record for each completed audit, so --list-runs
+# can compare issue counts and duration across runs over time.
+enabled = {history_enabled}
+# Directory run records are written to. Defaults to a yoauditor/runs
+# subdirectory of the platform user data dir when unset.
+# directory = "/home/me/.local/share/yoauditor/runs"
+# Serialization format for run record files: "json" (default) or "toml".
+# format = "json"
+
+# Named flag bundles, activated with --profile <name>. Every key is
+# optional: anything left out falls through to the defaults above, and any
+# flag passed explicitly on the command line always wins over the profile.
+# [profiles.ci]
+# fail_on = "high"
+# format = "json"
+"#,
+            output = g.output,
+            verbose = g.verbose,
+            concurrency = g.concurrency,
+            baseline = g.baseline,
+            model_name = m.name,
+            ollama_url = m.ollama_url,
+            temperature = m.temperature,
+            timeout_seconds = m.timeout_seconds,
+            retries = m.retries,
+            single_call_mode = m.single_call_mode,
+            file_timeout_seconds = m.file_timeout_seconds,
+            file_retries = m.file_retries,
+            max_files = s.max_files,
+            max_chunk_lines = s.max_chunk_lines,
+            extensions = format!("{:?}", s.extensions),
+            excludes = format!("{:?}", s.excludes),
+            max_file_size = s.max_file_size,
+            respect_gitignore = s.respect_gitignore,
+            include_snippets = r.include_snippets,
+            include_summaries = r.include_summaries,
+            max_snippet_lines = r.max_snippet_lines,
+            group_by_file = r.group_by_file,
+            rules_enabled = config.rules.enabled,
+            supply_chain_enabled = config.supply_chain.enabled,
+            cache_enabled = config.cache.enabled,
+            history_enabled = config.history.enabled,
+        )
+    }
+
+    /// Sanity-check the fully merged configuration (after
+    /// [`Config::discover`]/[`apply_env`](Config::apply_env)/
+    /// [`merge_with_args`](Config::merge_with_args)), so a bogus value from
+    /// any of those layers fails the run loudly instead of producing a
+    /// broken or silently-wrong audit.
+    ///
+    /// `allow_large_scan` is the `--allow-large-scan` escape hatch: without
+    /// it, `scanner.max_files`/`scanner.max_file_size` above conservative
+    /// ceilings are rejected, so a fat-fingered repo-wide scan against a
+    /// metered cloud model can't silently run up a huge bill.
+    pub fn validate(&self, allow_large_scan: bool) -> Result<()> {
+        if !(0.0..=2.0).contains(&self.model.temperature) {
+            return Err(anyhow::anyhow!(
+                "model.temperature must be between 0.0 and 2.0, got {}",
+                self.model.temperature
+            ));
+        }
+
+        if self.general.concurrency < 1 {
+            return Err(anyhow::anyhow!("general.concurrency must be at least 1"));
+        }
+
+        if self.model.retries == 0 {
+            return Err(anyhow::anyhow!("model.retries must be at least 1"));
+        }
+
+        if self.model.timeout_seconds == 0 {
+            return Err(anyhow::anyhow!("model.timeout_seconds must be nonzero"));
+        }
+
+        if self.model.file_timeout_seconds == 0 {
+            return Err(anyhow::anyhow!("model.file_timeout_seconds must be nonzero"));
+        }
+
+        if self.scanner.max_chunk_lines == 0 {
+            return Err(anyhow::anyhow!("scanner.max_chunk_lines must be greater than 0"));
+        }
+
+        if self.scanner.extensions.is_empty() {
+            return Err(anyhow::anyhow!(
+                "scanner.extensions must not be empty (nothing would be scanned)"
+            ));
+        }
+
+        if !self.model.ollama_url.starts_with("http://")
+            && !self.model.ollama_url.starts_with("https://")
+        {
+            return Err(anyhow::anyhow!(
+                "model.ollama_url must start with 'http://' or 'https://', got '{}'",
+                self.model.ollama_url
+            ));
+        }
+
+        if !allow_large_scan {
+            if self.scanner.max_files > MAX_FILES_CEILING {
+                return Err(anyhow::anyhow!(
+                    "scanner.max_files ({}) exceeds the {}-file ceiling; pass --allow-large-scan \
+                     to run it anyway",
+                    self.scanner.max_files,
+                    MAX_FILES_CEILING
+                ));
+            }
+
+            if self.scanner.max_file_size > MAX_FILE_SIZE_CEILING {
+                return Err(anyhow::anyhow!(
+                    "scanner.max_file_size ({} bytes) exceeds the {}-byte ceiling; pass \
+                     --allow-large-scan to run it anyway",
+                    self.scanner.max_file_size,
+                    MAX_FILE_SIZE_CEILING
+                ));
+            }
+        }
+
+        Ok(())
     }
 }
 
+/// Conservative `scanner.max_files` ceiling enforced by [`Config::validate`]
+/// unless `--allow-large-scan` is passed: 10k files.
+const MAX_FILES_CEILING: usize = 10_000;
+
+/// Conservative `scanner.max_file_size` ceiling enforced by
+/// [`Config::validate`] unless `--allow-large-scan` is passed: 10MB.
+const MAX_FILE_SIZE_CEILING: usize = 10 * 1024 * 1024;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,6 +1354,113 @@ mod tests {
         assert_eq!(config.model.name, "llama3.2:latest");
         assert_eq!(config.scanner.max_files, 100);
         assert!(config.scanner.extensions.contains(&"rs".to_string()));
+        assert!(config.rules.enabled);
+        assert!(config.rules.disabled.is_empty());
+        assert!(!config.supply_chain.enabled);
+        assert!(config.supply_chain.advisory_file.is_none());
+        assert!(config.cache.enabled);
+        assert!(config.cache.directory.is_none());
+        assert!(config.history.enabled);
+        assert!(config.history.directory.is_none());
+        assert_eq!(config.history.format, HistoryFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_cache_config() {
+        let toml_content = r#"
+[cache]
+enabled = false
+directory = "/tmp/yoauditor-cache"
+ttl_seconds = 3600
+max_entries = 500
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(!config.cache.enabled);
+        assert_eq!(config.cache.directory.as_deref(), Some("/tmp/yoauditor-cache"));
+        assert_eq!(config.cache.ttl_seconds, Some(3600));
+        assert_eq!(config.cache.max_entries, Some(500));
+    }
+
+    #[test]
+    fn test_cache_resolved_directory_defaults_under_user_cache_dir() {
+        let config = CacheConfig::default();
+        let resolved = config.resolved_directory();
+        assert!(resolved.ends_with("yoauditor/responses"));
+    }
+
+    #[test]
+    fn test_cache_resolved_directory_honors_explicit_path() {
+        let config = CacheConfig {
+            directory: Some("/tmp/custom-cache".to_string()),
+            ..CacheConfig::default()
+        };
+        assert_eq!(config.resolved_directory(), PathBuf::from("/tmp/custom-cache"));
+    }
+
+    #[test]
+    fn test_parse_history_config() {
+        let toml_content = r#"
+[history]
+enabled = false
+directory = "/tmp/yoauditor-runs"
+format = "toml"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(!config.history.enabled);
+        assert_eq!(config.history.directory.as_deref(), Some("/tmp/yoauditor-runs"));
+        assert_eq!(config.history.format, HistoryFormat::Toml);
+    }
+
+    #[test]
+    fn test_history_resolved_directory_defaults_under_user_data_dir() {
+        let config = HistoryConfig::default();
+        let resolved = config.resolved_directory();
+        assert!(resolved.ends_with("yoauditor/runs"));
+    }
+
+    #[test]
+    fn test_history_resolved_directory_honors_explicit_path() {
+        let config = HistoryConfig {
+            directory: Some("/tmp/custom-runs".to_string()),
+            ..HistoryConfig::default()
+        };
+        assert_eq!(config.resolved_directory(), PathBuf::from("/tmp/custom-runs"));
+    }
+
+    #[test]
+    fn test_history_format_extension() {
+        assert_eq!(HistoryFormat::Json.extension(), "json");
+        assert_eq!(HistoryFormat::Toml.extension(), "toml");
+    }
+
+    #[test]
+    fn test_parse_supply_chain_config() {
+        let toml_content = r#"
+[supply_chain]
+enabled = true
+advisory_file = "advisories.toml"
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(config.supply_chain.enabled);
+        assert_eq!(config.supply_chain.advisory_file.as_deref(), Some("advisories.toml"));
+    }
+
+    #[test]
+    fn test_parse_rules_config() {
+        let toml_content = r#"
+[rules]
+enabled = false
+extra_rules_file = "custom-rules.toml"
+disabled = ["hardcoded-secret"]
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(!config.rules.enabled);
+        assert_eq!(config.rules.extra_rules_file.as_deref(), Some("custom-rules.toml"));
+        assert_eq!(config.rules.disabled, vec!["hardcoded-secret".to_string()]);
     }
 
     #[test]
@@ -373,6 +1488,39 @@ extensions = ["rs", "py"]
         assert_eq!(config.scanner.extensions, vec!["rs", "py"]);
     }
 
+    #[test]
+    fn test_parse_profiles() {
+        let toml_content = r#"
+[profiles.ci]
+fail_on = "high"
+min_severity = "medium"
+format = "json"
+single_call = true
+
+[profiles.deep]
+concurrency = 8
+max_chunk_lines = 8000
+"#;
+
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.profiles.len(), 2);
+
+        let ci = &config.profiles["ci"];
+        assert_eq!(ci.fail_on, Some(FailOnLevel::High));
+        assert_eq!(ci.min_severity, Some(FailOnLevel::Medium));
+        assert_eq!(ci.format, Some(OutputFormat::Json));
+        assert_eq!(ci.single_call, Some(true));
+
+        let deep = &config.profiles["deep"];
+        assert_eq!(deep.concurrency, Some(8));
+        assert_eq!(deep.max_chunk_lines, Some(8000));
+    }
+
+    #[test]
+    fn test_default_config_has_no_profiles() {
+        assert!(Config::default().profiles.is_empty());
+    }
+
     #[test]
     fn test_default_toml_generation() {
         let toml_str = Config::default_toml();
@@ -380,5 +1528,165 @@ extensions = ["rs", "py"]
         assert!(toml_str.contains("[general]"));
         assert!(toml_str.contains("[model]"));
         assert!(toml_str.contains("[scanner]"));
+        assert!(toml_str.contains("[cache]"));
+        assert!(toml_str.contains("[history]"));
+        // Every uncommented key should be a doc-commented explanation of
+        // a real field, not just a bare value -- spot check a few.
+        assert!(toml_str.contains("# Sampling temperature"));
+        assert!(toml_str.contains("# Single-call mode reads every file"));
+    }
+
+    #[test]
+    fn test_default_toml_round_trips_to_defaults() {
+        let toml_str = Config::default_toml();
+        let parsed: Config = toml::from_str(&toml_str).unwrap();
+        let default = Config::default();
+
+        assert_eq!(parsed.general.output, default.general.output);
+        assert_eq!(parsed.model.name, default.model.name);
+        assert_eq!(parsed.model.temperature, default.model.temperature);
+        assert_eq!(parsed.scanner.max_files, default.scanner.max_files);
+        assert_eq!(parsed.scanner.extensions, default.scanner.extensions);
+        assert_eq!(parsed.cache.enabled, default.cache.enabled);
+        assert_eq!(parsed.history.enabled, default.history.enabled);
+    }
+
+    #[test]
+    fn test_discover_with_no_files_returns_defaults() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = Config::discover(temp_dir.path()).unwrap();
+        assert_eq!(config.model.name, Config::default().model.name);
+        assert_eq!(config.scanner.max_files, Config::default().scanner.max_files);
+    }
+
+    #[test]
+    fn test_discover_layers_repo_config_field_by_field() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(".yoauditor.toml"),
+            r#"
+[model]
+ollama_url = "http://repo-ollama:11434"
+
+[scanner]
+extensions = ["rs"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::discover(temp_dir.path()).unwrap();
+
+        // Set explicitly by the repo file.
+        assert_eq!(config.model.ollama_url, "http://repo-ollama:11434");
+        assert_eq!(config.scanner.extensions, vec!["rs".to_string()]);
+        // Left untouched by the repo file, so it falls through to the
+        // built-in default rather than some wholesale-replaced value.
+        assert_eq!(config.model.name, Config::default().model.name);
+        assert_eq!(config.scanner.max_files, Config::default().scanner.max_files);
+    }
+
+    #[test]
+    fn test_partial_config_layer_over_prefers_higher_priority_field() {
+        let lower = PartialGeneralConfig {
+            output: Some("lower.md".to_string()),
+            verbose: Some(true),
+            concurrency: None,
+            baseline: None,
+        };
+        let higher = PartialGeneralConfig {
+            output: Some("higher.md".to_string()),
+            verbose: None,
+            concurrency: Some(8),
+            baseline: None,
+        };
+
+        let merged = higher.layer_over(lower);
+        assert_eq!(merged.output, Some("higher.md".to_string()));
+        assert_eq!(merged.verbose, Some(true)); // fell through from `lower`
+        assert_eq!(merged.concurrency, Some(8));
+        assert_eq!(merged.baseline, None);
+    }
+
+    #[test]
+    fn test_apply_env_overrides_fields_from_different_sections() {
+        std::env::set_var("YOAUDITOR_MODEL_NAME", "test-apply-env-model");
+        std::env::set_var("YOAUDITOR_MODEL_TEMPERATURE", "0.7");
+        std::env::set_var("YOAUDITOR_SCANNER_MAX_FILES", "250");
+        std::env::set_var("YOAUDITOR_SCANNER_EXTENSIONS", "rs, py ,go");
+
+        let mut config = Config::default();
+        let result = config.apply_env();
+
+        std::env::remove_var("YOAUDITOR_MODEL_NAME");
+        std::env::remove_var("YOAUDITOR_MODEL_TEMPERATURE");
+        std::env::remove_var("YOAUDITOR_SCANNER_MAX_FILES");
+        std::env::remove_var("YOAUDITOR_SCANNER_EXTENSIONS");
+
+        result.unwrap();
+        assert_eq!(config.model.name, "test-apply-env-model");
+        assert_eq!(config.model.temperature, 0.7);
+        assert_eq!(config.scanner.max_files, 250);
+        assert_eq!(
+            config.scanner.extensions,
+            vec!["rs".to_string(), "py".to_string(), "go".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_apply_env_leaves_unset_fields_untouched() {
+        let mut config = Config::default();
+        config.apply_env().unwrap();
+        assert_eq!(config.model.name, Config::default().model.name);
+    }
+
+    #[test]
+    fn test_apply_env_rejects_unparseable_value() {
+        std::env::set_var("YOAUDITOR_MODEL_TEMPERATURE", "not-a-number");
+        let mut config = Config::default();
+        let result = config.apply_env();
+        std::env::remove_var("YOAUDITOR_MODEL_TEMPERATURE");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(Config::default().validate(false).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_temperature_out_of_range() {
+        let mut config = Config::default();
+        config.model.temperature = 50.0;
+        assert!(config.validate(false).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_concurrency() {
+        let mut config = Config::default();
+        config.general.concurrency = 0;
+        assert!(config.validate(false).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_extensions() {
+        let mut config = Config::default();
+        config.scanner.extensions = Vec::new();
+        assert!(config.validate(false).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_ollama_url() {
+        let mut config = Config::default();
+        config.model.ollama_url = "localhost:11434".to_string();
+        assert!(config.validate(false).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_oversized_scan_without_opt_out() {
+        let mut config = Config::default();
+        config.scanner.max_files = 50_000;
+        assert!(config.validate(false).is_err());
+        assert!(config.validate(true).is_ok());
     }
 }
@@ -0,0 +1,124 @@
+//! RustSec advisory-database dependency audit.
+//!
+//! Loads `Cargo.lock` with `rustsec::Lockfile`, checks it against the
+//! RustSec advisory database, and converts each `Vulnerability` into an
+//! `Issue` so known-vulnerable dependencies flow through the same
+//! `Report`/`Issue` pipeline as LLM-derived findings: they show up in the
+//! severity breakdown, the category table, and both the Markdown and JSON
+//! renderers without any changes to those functions.
+
+use crate::models::{Issue, Severity};
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// File path stamped on each dependency `Issue`, matching where `Cargo.lock`
+/// lives in a checked-out repo. `analysis::group_by_file` groups issues by
+/// this path into a synthetic `AnalyzedFile`, which is how these issues pick
+/// up the existing severity breakdown, category table, and "Most
+/// Problematic Files" logic for free.
+const LOCKFILE_PATH: &str = "Cargo.lock";
+
+/// Scans `<repo_path>/Cargo.lock` against the RustSec advisory database and
+/// returns one `Issue` per known vulnerability, ready to merge into the same
+/// `Vec<Issue>` the LLM agent produces.
+///
+/// Returns an empty `Vec` if the repository has no `Cargo.lock` (nothing to
+/// audit, not an error). Database fetch/parse failures are returned as
+/// `Err` so the caller can decide whether to fail the run or downgrade to
+/// a warning and continue without the dependency audit.
+pub fn audit_dependencies(repo_path: &Path) -> Result<Vec<Issue>> {
+    let lockfile_path = repo_path.join(LOCKFILE_PATH);
+    if !lockfile_path.exists() {
+        info!("No Cargo.lock found, skipping dependency audit");
+        return Ok(Vec::new());
+    }
+
+    let lockfile = rustsec::Lockfile::load(&lockfile_path)
+        .with_context(|| format!("Failed to load lockfile at {}", lockfile_path.display()))?;
+
+    let database = rustsec::Database::fetch()
+        .context("Failed to fetch the RustSec advisory database")?;
+
+    let report =
+        rustsec::Report::generate(&database, &lockfile, &rustsec::report::Settings::default());
+
+    let issues: Vec<Issue> = report
+        .vulnerabilities
+        .list
+        .iter()
+        .map(vulnerability_to_issue)
+        .collect();
+
+    if issues.is_empty() {
+        info!("Dependency audit found no known vulnerabilities");
+    } else {
+        warn!(
+            "Dependency audit found {} known-vulnerable dependenc{}",
+            issues.len(),
+            if issues.len() == 1 { "y" } else { "ies" }
+        );
+    }
+
+    Ok(issues)
+}
+
+/// Converts a single RustSec `Vulnerability` into our `Issue` shape.
+fn vulnerability_to_issue(vuln: &rustsec::Vulnerability) -> Issue {
+    let advisory = &vuln.advisory;
+
+    let title = format!("{}: {}", advisory.id, vuln.package.name);
+    let description = if advisory.description.is_empty() {
+        advisory.title.clone()
+    } else {
+        format!("{} {}", advisory.title, advisory.description)
+    };
+
+    let suggestion = if vuln.versions.patched.is_empty() {
+        "No patched version is available yet; track the advisory for updates.".to_string()
+    } else {
+        format!(
+            "Upgrade {} to a patched version: {}",
+            vuln.package.name,
+            vuln.versions
+                .patched
+                .iter()
+                .map(|req| req.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    let severity = severity_from_cvss(advisory.cvss.as_ref().map(|cvss| cvss.score().value()));
+    let rule_id = Issue::derive_rule_id("dependency", &advisory.id.to_string());
+
+    Issue {
+        file_path: LOCKFILE_PATH.to_string(),
+        start_line: 1,
+        end_line: None,
+        severity,
+        category: "Dependency".to_string(),
+        title,
+        description,
+        suggestion,
+        code_snippet: None,
+        fix: None,
+        start_column: None,
+        end_column: None,
+        rule_id,
+        known: false,
+    }
+}
+
+/// Maps a CVSS base score to our `Severity` scale: >=9.0 critical, >=7.0
+/// high, >=4.0 medium, else low. Advisories without a CVSS score (some
+/// RustSec entries predate scoring) are treated as medium.
+fn severity_from_cvss(score: Option<f64>) -> Severity {
+    match score {
+        Some(score) if score >= 9.0 => Severity::Critical,
+        Some(score) if score >= 7.0 => Severity::High,
+        Some(score) if score >= 4.0 => Severity::Medium,
+        Some(_) => Severity::Low,
+        None => Severity::Medium,
+    }
+}
@@ -0,0 +1,403 @@
+//! Benchmark/evaluation harness for measuring agent accuracy.
+//!
+//! Reads JSON workload files describing a target repository, the agent
+//! configuration to run it with, and a set of expected issues. Runs
+//! `run_analysis` against the repo, fuzzy-matches produced `ReportedIssue`s
+//! against expectations, and scores precision/recall/F1 per category plus
+//! wall-clock time and iteration count. Intended for regression-testing
+//! prompt/loop changes and comparing models, not for CI correctness checks.
+
+use crate::agent::tools::ReportedIssue;
+use crate::agent::{AgentConfig, CodeAnalysisAgent};
+use crate::scanner::ScanConfig;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// An issue the workload author expects the agent to find. Matched against
+/// produced issues by file path, a line-number tolerance, category, and a
+/// fuzzy (word-overlap) title comparison rather than an exact string match,
+/// since models rarely phrase titles identically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedIssue {
+    pub file_path: String,
+    pub line_number: usize,
+    pub category: String,
+    pub title: String,
+    /// Allowed distance, in lines, between the expected and reported line.
+    #[serde(default = "default_line_tolerance")]
+    pub line_tolerance: usize,
+}
+
+fn default_line_tolerance() -> usize {
+    2
+}
+
+/// The subset of `AgentConfig` a workload file can specify. Anything not
+/// set here falls back to `AgentConfig::default()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadAgentConfig {
+    #[serde(default = "default_model_name")]
+    pub model_name: String,
+    #[serde(default = "default_ollama_url")]
+    pub ollama_url: String,
+    #[serde(default)]
+    pub single_call_mode: bool,
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: usize,
+}
+
+impl Default for WorkloadAgentConfig {
+    fn default() -> Self {
+        Self {
+            model_name: default_model_name(),
+            ollama_url: default_ollama_url(),
+            single_call_mode: false,
+            temperature: default_temperature(),
+            max_iterations: default_max_iterations(),
+        }
+    }
+}
+
+fn default_model_name() -> String {
+    AgentConfig::default().model_name
+}
+
+fn default_ollama_url() -> String {
+    AgentConfig::default().ollama_url
+}
+
+fn default_temperature() -> f32 {
+    AgentConfig::default().temperature
+}
+
+fn default_max_iterations() -> usize {
+    AgentConfig::default().max_iterations
+}
+
+impl WorkloadAgentConfig {
+    /// Build a full `AgentConfig`, taking the fields a workload can
+    /// override and leaving everything else (provider, concurrency,
+    /// chunking, ...) at its default.
+    fn to_agent_config(&self) -> AgentConfig {
+        AgentConfig {
+            ollama_url: self.ollama_url.clone(),
+            model_name: self.model_name.clone(),
+            temperature: self.temperature,
+            max_iterations: self.max_iterations,
+            single_call_mode: self.single_call_mode,
+            ..AgentConfig::default()
+        }
+    }
+}
+
+/// A benchmark workload: a target repo, the agent configuration to run it
+/// with, and the issues expected to be found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workload {
+    /// Name of this workload, carried through into the results file.
+    pub name: String,
+    /// Local directory to analyze (bench runs never clone).
+    pub repo_path: PathBuf,
+    #[serde(default)]
+    pub agent: WorkloadAgentConfig,
+    pub expected_issues: Vec<ExpectedIssue>,
+}
+
+impl Workload {
+    /// Load a workload from a JSON file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workload file: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse workload file: {}", path.display()))
+    }
+}
+
+/// Precision/recall/F1 for one category (or the aggregated `"overall"` row).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryScore {
+    pub category: String,
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+}
+
+impl CategoryScore {
+    fn new(category: String, true_positives: usize, false_positives: usize, false_negatives: usize) -> Self {
+        let precision = if true_positives + false_positives == 0 {
+            0.0
+        } else {
+            true_positives as f64 / (true_positives + false_positives) as f64
+        };
+        let recall = if true_positives + false_negatives == 0 {
+            0.0
+        } else {
+            true_positives as f64 / (true_positives + false_negatives) as f64
+        };
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+
+        Self {
+            category,
+            true_positives,
+            false_positives,
+            false_negatives,
+            precision,
+            recall,
+            f1,
+        }
+    }
+}
+
+/// Result of scoring one workload run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadResult {
+    pub workload_name: String,
+    pub model_name: String,
+    pub single_call_mode: bool,
+    pub duration_seconds: f64,
+    pub iterations: usize,
+    pub issues_found: usize,
+    pub issues_expected: usize,
+    pub category_scores: Vec<CategoryScore>,
+    pub overall: CategoryScore,
+}
+
+/// Run a single workload end to end: build the agent, run analysis, score
+/// the produced issues against expectations.
+pub async fn run_workload(workload: &Workload) -> Result<WorkloadResult> {
+    let agent_config = workload.agent.to_agent_config();
+    let model_name = agent_config.model_name.clone();
+    let single_call_mode = agent_config.single_call_mode;
+
+    let mut agent = CodeAnalysisAgent::new(
+        agent_config,
+        workload.repo_path.clone(),
+        ScanConfig::default(),
+    );
+
+    agent
+        .preflight()
+        .await
+        .context("Preflight check against the chat provider failed")?;
+
+    let start = Instant::now();
+    let issues = agent.run_analysis().await?;
+    let duration_seconds = start.elapsed().as_secs_f64();
+    let iterations = agent.last_run_iterations();
+
+    let (category_scores, overall) = score_issues(&workload.expected_issues, &issues);
+
+    Ok(WorkloadResult {
+        workload_name: workload.name.clone(),
+        model_name,
+        single_call_mode,
+        duration_seconds,
+        iterations,
+        issues_found: issues.len(),
+        issues_expected: workload.expected_issues.len(),
+        category_scores,
+        overall,
+    })
+}
+
+/// Run a workload loaded from `path` and write the scored result as
+/// pretty-printed JSON to `output_path`.
+pub async fn run_bench_file(path: &Path, output_path: &Path) -> Result<WorkloadResult> {
+    let workload = Workload::load(path)?;
+    let result = run_workload(&workload).await?;
+
+    let json = serde_json::to_string_pretty(&result).context("Failed to serialize bench result")?;
+    std::fs::write(output_path, json)
+        .with_context(|| format!("Failed to write bench results to {}", output_path.display()))?;
+
+    Ok(result)
+}
+
+/// Score produced issues against expectations, matching greedily within
+/// each category so no actual issue is counted as a match more than once.
+/// Returns per-category scores plus an aggregated "overall" row.
+fn score_issues(
+    expected: &[ExpectedIssue],
+    actual: &[ReportedIssue],
+) -> (Vec<CategoryScore>, CategoryScore) {
+    let mut categories: Vec<String> = expected
+        .iter()
+        .map(|e| e.category.to_lowercase())
+        .chain(actual.iter().map(|a| a.category.to_lowercase()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    categories.sort();
+
+    let mut category_scores = Vec::new();
+    let mut total_tp = 0;
+    let mut total_fp = 0;
+    let mut total_fn = 0;
+
+    for category in categories {
+        let expected_in_category: Vec<&ExpectedIssue> = expected
+            .iter()
+            .filter(|e| e.category.to_lowercase() == category)
+            .collect();
+        let actual_in_category: Vec<&ReportedIssue> = actual
+            .iter()
+            .filter(|a| a.category.to_lowercase() == category)
+            .collect();
+
+        let mut matched_actual = vec![false; actual_in_category.len()];
+        let mut true_positives = 0;
+
+        for exp in &expected_in_category {
+            let found = actual_in_category.iter().enumerate().find(|(i, act)| {
+                !matched_actual[*i]
+                    && act.file_path == exp.file_path
+                    && act.line_number.abs_diff(exp.line_number) <= exp.line_tolerance
+                    && titles_match(&exp.title, &act.title)
+            });
+
+            if let Some((i, _)) = found {
+                matched_actual[i] = true;
+                true_positives += 1;
+            }
+        }
+
+        let false_negatives = expected_in_category.len() - true_positives;
+        let false_positives = actual_in_category.len() - true_positives;
+
+        total_tp += true_positives;
+        total_fp += false_positives;
+        total_fn += false_negatives;
+
+        category_scores.push(CategoryScore::new(
+            category,
+            true_positives,
+            false_positives,
+            false_negatives,
+        ));
+    }
+
+    let overall = CategoryScore::new("overall".to_string(), total_tp, total_fp, total_fn);
+
+    (category_scores, overall)
+}
+
+/// Fuzzy title comparison: normalize to lowercase alphanumeric words and
+/// check for meaningful overlap, since models rarely phrase titles exactly
+/// like the workload author did.
+fn titles_match(expected: &str, actual: &str) -> bool {
+    let words = |s: &str| -> HashSet<String> {
+        s.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() > 2)
+            .map(String::from)
+            .collect()
+    };
+
+    let expected_words = words(expected);
+    let actual_words = words(actual);
+
+    if expected_words.is_empty() || actual_words.is_empty() {
+        return expected.eq_ignore_ascii_case(actual);
+    }
+
+    let overlap = expected_words.intersection(&actual_words).count();
+    let union = expected_words.union(&actual_words).count();
+
+    (overlap as f64 / union as f64) >= 0.3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(file: &str, line: usize, category: &str, title: &str) -> ReportedIssue {
+        ReportedIssue {
+            file_path: file.to_string(),
+            line_number: line,
+            severity: "medium".to_string(),
+            category: category.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            suggestion: String::new(),
+            code_snippet: None,
+        }
+    }
+
+    fn expected(file: &str, line: usize, category: &str, title: &str) -> ExpectedIssue {
+        ExpectedIssue {
+            file_path: file.to_string(),
+            line_number: line,
+            category: category.to_string(),
+            title: title.to_string(),
+            line_tolerance: 2,
+        }
+    }
+
+    #[test]
+    fn test_titles_match_fuzzy() {
+        assert!(titles_match(
+            "SQL injection via string concatenation",
+            "Possible SQL injection in query concatenation"
+        ));
+        assert!(!titles_match("Unused import", "Race condition in cache"));
+    }
+
+    #[test]
+    fn test_score_issues_perfect_match() {
+        let expected = vec![expected("src/a.rs", 10, "security", "SQL injection risk")];
+        let actual = vec![issue("src/a.rs", 11, "security", "Possible SQL injection")];
+
+        let (scores, overall) = score_issues(&expected, &actual);
+        assert_eq!(scores.len(), 1);
+        assert_eq!(overall.true_positives, 1);
+        assert_eq!(overall.false_positives, 0);
+        assert_eq!(overall.false_negatives, 0);
+        assert_eq!(overall.precision, 1.0);
+        assert_eq!(overall.recall, 1.0);
+    }
+
+    #[test]
+    fn test_score_issues_miss_and_extra() {
+        let expected = vec![
+            expected("src/a.rs", 10, "security", "SQL injection risk"),
+            expected("src/b.rs", 5, "bug", "Off by one error"),
+        ];
+        let actual = vec![
+            issue("src/a.rs", 10, "security", "SQL injection risk"),
+            issue("src/c.rs", 1, "style", "Unused variable"),
+        ];
+
+        let (_, overall) = score_issues(&expected, &actual);
+        assert_eq!(overall.true_positives, 1);
+        assert_eq!(overall.false_positives, 1);
+        assert_eq!(overall.false_negatives, 1);
+    }
+
+    #[test]
+    fn test_category_score_handles_zero_denominators() {
+        let score = CategoryScore::new("bug".to_string(), 0, 0, 0);
+        assert_eq!(score.precision, 0.0);
+        assert_eq!(score.recall, 0.0);
+        assert_eq!(score.f1, 0.0);
+    }
+
+    #[test]
+    fn test_workload_agent_config_defaults_match_agent_config() {
+        let workload_config = WorkloadAgentConfig::default();
+        let agent_config = workload_config.to_agent_config();
+        assert_eq!(agent_config.model_name, AgentConfig::default().model_name);
+        assert!(!agent_config.single_call_mode);
+    }
+}
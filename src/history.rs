@@ -0,0 +1,241 @@
+//! Persisted audit-run metadata, so `--list-runs` can compare issue counts
+//! and duration across runs over time. Each completed run is written as one
+//! small record file under `[history].directory` (see `config::HistoryConfig`);
+//! `main::run_audit` writes a record right after the report itself is
+//! written to disk, and `main::handle_list_runs` reads them all back for
+//! the `--list-runs` table.
+
+use crate::config::{HistoryConfig, HistoryFormat};
+use crate::models::Report;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One row of `--list-runs` output: a snapshot of a single completed audit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// When the analysis finished.
+    pub timestamp: DateTime<Utc>,
+    /// URL of the analyzed repository.
+    pub repo: String,
+    /// Name of the LLM model used.
+    pub model: String,
+    /// Number of files analyzed.
+    pub file_count: usize,
+    /// Number of critical issues found.
+    pub critical: usize,
+    /// Number of high severity issues found.
+    pub high: usize,
+    /// Number of medium severity issues found.
+    pub medium: usize,
+    /// Number of low severity issues found.
+    pub low: usize,
+    /// Duration of the analysis in seconds.
+    pub duration_seconds: f64,
+    /// Path the full report was written to.
+    pub output_path: String,
+}
+
+impl RunRecord {
+    /// Build a `RunRecord` from a completed `Report`, recording where its
+    /// full output was written.
+    pub fn from_report(report: &Report, output_path: &str) -> Self {
+        Self {
+            timestamp: report.metadata.analysis_date,
+            repo: report.metadata.repo_url.clone(),
+            model: report.metadata.model_used.clone(),
+            file_count: report.metadata.files_analyzed,
+            critical: report.summary.critical,
+            high: report.summary.high,
+            medium: report.summary.medium,
+            low: report.summary.low,
+            duration_seconds: report.metadata.duration_seconds,
+            output_path: output_path.to_string(),
+        }
+    }
+}
+
+/// A directory of `RunRecord` files, one per completed audit. See
+/// [`HistoryConfig`] for how the directory and serialization format are
+/// configured.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    dir: std::path::PathBuf,
+    format: HistoryFormat,
+}
+
+impl HistoryStore {
+    /// Build a `HistoryStore` from `config`, creating its directory if
+    /// needed. Returns `None` if history recording is disabled, so call
+    /// sites can thread an `Option<HistoryStore>` through without an extra
+    /// `enabled` check at every use.
+    pub fn new(config: &HistoryConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let dir = config.resolved_directory();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create history directory: {}", dir.display()))?;
+
+        Ok(Some(Self {
+            dir,
+            format: config.format,
+        }))
+    }
+
+    /// Append `record` as a new file in the history directory. Writes to a
+    /// temp file first, then renames it into place, so a reader never
+    /// observes a partially written record.
+    pub fn record(&self, record: &RunRecord) -> Result<()> {
+        let serialized = match self.format {
+            HistoryFormat::Json => {
+                serde_json::to_string_pretty(record).context("Failed to serialize run record")?
+            }
+            HistoryFormat::Toml => {
+                toml::to_string_pretty(record).context("Failed to serialize run record")?
+            }
+        };
+
+        let file_name = format!(
+            "{}-{}.{}",
+            record.timestamp.format("%Y%m%dT%H%M%S%.3f"),
+            std::process::id(),
+            self.format.extension()
+        );
+        let final_path = self.dir.join(&file_name);
+        let tmp_path = self.dir.join(format!("{file_name}.tmp"));
+
+        std::fs::write(&tmp_path, &serialized)
+            .with_context(|| format!("Failed to write run record: {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, &final_path).with_context(|| {
+            format!("Failed to finalize run record: {}", final_path.display())
+        })?;
+
+        Ok(())
+    }
+
+    /// Read back every run record in the history directory, oldest first.
+    /// A record file that fails to parse (e.g. written by a future format
+    /// version) is skipped rather than failing the whole listing.
+    pub fn list(&self) -> Result<Vec<RunRecord>> {
+        let read_dir = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read history directory: {}", self.dir.display()))?;
+
+        let mut records: Vec<RunRecord> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let raw = std::fs::read_to_string(&path).ok()?;
+                match path.extension().and_then(|ext| ext.to_str()) {
+                    Some("json") => serde_json::from_str(&raw).ok(),
+                    Some("toml") => toml::from_str(&raw).ok(),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        records.sort_by_key(|record| record.timestamp);
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AnalyzedFile, IssueSummary, ReportMetadata};
+
+    fn test_config(dir: &std::path::Path, format: HistoryFormat) -> HistoryConfig {
+        HistoryConfig {
+            enabled: true,
+            directory: Some(dir.to_string_lossy().to_string()),
+            format,
+        }
+    }
+
+    fn test_report(repo_url: &str) -> Report {
+        Report {
+            metadata: ReportMetadata {
+                repo_url: repo_url.to_string(),
+                analysis_date: Utc::now(),
+                model_used: "llama3.2:latest".to_string(),
+                files_analyzed: 3,
+                files_failed: 0,
+                total_issues: 2,
+                duration_seconds: 12.5,
+                scoped_to_diff: None,
+            },
+            project_overview: "overview".to_string(),
+            files: Vec::<AnalyzedFile>::new(),
+            summary: IssueSummary {
+                total: 2,
+                critical: 0,
+                high: 1,
+                medium: 1,
+                low: 0,
+                by_category: Default::default(),
+            },
+            recommendations: Vec::new(),
+            code_stats: Default::default(),
+            logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_history_returns_none() {
+        let config = HistoryConfig {
+            enabled: false,
+            ..HistoryConfig::default()
+        };
+        assert!(HistoryStore::new(&config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_record_then_list_round_trips_json() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = HistoryStore::new(&test_config(temp_dir.path(), HistoryFormat::Json))
+            .unwrap()
+            .unwrap();
+
+        let report = test_report("https://example.com/repo.git");
+        let record = RunRecord::from_report(&report, "report.json");
+        store.record(&record).unwrap();
+
+        let records = store.list().unwrap();
+        assert_eq!(records, vec![record]);
+    }
+
+    #[test]
+    fn test_record_then_list_round_trips_toml() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = HistoryStore::new(&test_config(temp_dir.path(), HistoryFormat::Toml))
+            .unwrap()
+            .unwrap();
+
+        let report = test_report("https://example.com/repo.git");
+        let record = RunRecord::from_report(&report, "report.toml");
+        store.record(&record).unwrap();
+
+        let records = store.list().unwrap();
+        assert_eq!(records, vec![record]);
+    }
+
+    #[test]
+    fn test_list_is_sorted_oldest_first() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = HistoryStore::new(&test_config(temp_dir.path(), HistoryFormat::Json))
+            .unwrap()
+            .unwrap();
+
+        let mut older = RunRecord::from_report(&test_report("repo-a"), "a.json");
+        older.timestamp = Utc::now() - chrono::Duration::seconds(60);
+        let newer = RunRecord::from_report(&test_report("repo-b"), "b.json");
+
+        store.record(&newer).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        store.record(&older).unwrap();
+
+        let records = store.list().unwrap();
+        assert_eq!(records, vec![older, newer]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Structured, in-memory log capture attached to the report.
+//!
+//! `init` wires up three `tracing` layers: the human-friendly console
+//! layer (compact, colorized), an in-memory capture layer that always
+//! runs regardless of `--log-file`, and -- if `--log-file` is set -- a
+//! newline-delimited JSON file layer with ANSI stripped. The in-memory
+//! layer's buffer is handed back as a `LogHandle`, and also stashed
+//! globally so `main::audit_once` can attach a snapshot of it to
+//! `Report::logs` without threading the handle through every call in
+//! between. This keeps audit runs debuggable after the fact even when
+//! invoked by a server or CI where stderr is discarded.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+/// One captured log line, detached from the `tracing` machinery that
+/// produced it so it serializes directly into `Report::logs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// RFC 3339 timestamp of the event.
+    pub timestamp: String,
+    /// `tracing::Level` rendered as a string, e.g. `"INFO"`.
+    pub level: String,
+    /// The module/target the event was emitted from.
+    pub target: String,
+    /// The formatted log message.
+    pub message: String,
+}
+
+/// Shared handle to the in-memory log buffer.
+#[derive(Clone, Default)]
+pub struct LogHandle(Arc<Mutex<Vec<LogRecord>>>);
+
+impl LogHandle {
+    /// Returns a copy of every record captured so far, in emission order.
+    pub fn snapshot(&self) -> Vec<LogRecord> {
+        self.0.lock().expect("log buffer mutex poisoned").clone()
+    }
+}
+
+static GLOBAL_HANDLE: OnceLock<LogHandle> = OnceLock::new();
+
+/// Returns the process-wide log handle set by `init`, or an empty one if
+/// `init` was never called (e.g. in unit tests that don't set up logging).
+pub fn current() -> LogHandle {
+    GLOBAL_HANDLE.get().cloned().unwrap_or_default()
+}
+
+/// A `tracing_subscriber::Layer` that appends every event it sees to a
+/// `LogHandle`, independent of the console layer's own filtering/format.
+struct LogCapture(LogHandle);
+
+impl<S> Layer<S> for LogCapture
+where
+    S: tracing::Subscriber,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let record = LogRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: event.metadata().level().to_string(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        };
+
+        self.0 .0.lock().expect("log buffer mutex poisoned").push(record);
+    }
+}
+
+/// Extracts the `message` field that `tracing`'s `info!("...")`-style
+/// macros record, ignoring any other structured fields -- this module only
+/// needs the rendered text, not the raw key/value pairs.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        }
+    }
+}
+
+/// Initializes the global `tracing` subscriber: a compact console layer at
+/// `level`, an in-memory capture layer (always on), and -- if `log_file` is
+/// set -- a newline-delimited JSON file layer with no ANSI escapes. Returns
+/// the `LogHandle` backing the in-memory buffer (also stashed globally, see
+/// `current`).
+pub fn init(level: tracing::Level, log_file: Option<&std::path::Path>) -> LogHandle {
+    let handle = LogHandle::default();
+
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .with_thread_ids(false)
+        .with_file(false)
+        .with_line_number(false)
+        .compact();
+
+    let capture_layer = LogCapture(handle.clone());
+
+    let file_layer = log_file.and_then(|path| {
+        match std::fs::OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_ansi(false)
+                    .with_writer(move || file.try_clone().expect("failed to clone log file handle")),
+            ),
+            Err(e) => {
+                eprintln!("Warning: failed to open --log-file {}: {}", path.display(), e);
+                None
+            }
+        }
+    });
+
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::from_level(level))
+        .with(console_layer)
+        .with(capture_layer)
+        .with(file_layer);
+
+    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
+
+    let _ = GLOBAL_HANDLE.set(handle.clone());
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_handle_starts_empty() {
+        let handle = LogHandle::default();
+        assert!(handle.snapshot().is_empty());
+    }
+}
@@ -10,27 +10,77 @@
 
 mod agent;
 mod analysis;
+mod apply;
+mod baseline;
+mod batch;
+mod cache;
 mod cli;
 mod config;
+mod dependency_audit;
+mod eval;
+mod history;
+mod logging;
 mod models;
+mod pathutil;
 mod repo;
 mod report;
+mod rules;
 mod scanner;
+mod server;
+mod stats;
+mod supply_chain;
+mod watch;
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use cli::{Args, FailOnLevel, OutputFormat};
 use config::Config;
 use models::{AnalyzedFile, Issue, IssueSummary, Report, ReportMetadata, Severity};
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
-use tracing_subscriber::FmtSubscriber;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Parse command-line arguments
-    let args = Args::parse_args();
+    // Parse command-line arguments, keeping the ArgMatches around so
+    // --profile resolution can tell an explicit flag from a default value.
+    let (mut args, matches) = Args::parse_args_with_matches();
+
+    // Resolve --profile/--list-profiles before validating: built-in
+    // defaults, then the profile's values, then explicit CLI flags (which
+    // always win), mirroring how config/alias layering works in other CLI
+    // tools. This needs a config file, so load one now rather than waiting
+    // for run_audit's own (repo-aware) load.
+    let profile_config = load_config(&args).unwrap_or_else(|_| Config::default());
+
+    if args.list_profiles {
+        print_profiles(&profile_config);
+        return Ok(());
+    }
+
+    if args.list_runs {
+        return handle_list_runs(&args, &profile_config);
+    }
+
+    if let Some(ref profile_name) = args.profile.clone() {
+        match profile_config.profiles.get(profile_name) {
+            Some(profile) => args.apply_profile(profile, &matches),
+            None => {
+                let available: Vec<&str> =
+                    profile_config.profiles.keys().map(String::as_str).collect();
+                eprintln!(
+                    "Error: unknown profile '{}' (available: {})",
+                    profile_name,
+                    if available.is_empty() {
+                        "none defined in .yoauditor.toml".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                );
+                std::process::exit(1);
+            }
+        }
+    }
 
     // Validate arguments
     if let Err(e) = args.validate() {
@@ -40,7 +90,7 @@ async fn main() -> Result<()> {
 
     // Handle --init-config early (no logging needed)
     if args.init_config {
-        return handle_init_config();
+        return handle_init_config(&args);
     }
 
     // Initialize logging
@@ -49,6 +99,57 @@ async fn main() -> Result<()> {
     info!("YoAuditor v{}", env!("CARGO_PKG_VERSION"));
     debug!("Arguments: {:?}", args);
 
+    // Handle --bench: run a scored workload instead of a normal audit
+    if let Some(ref bench_path) = args.bench {
+        return match run_bench(bench_path, &args.bench_output).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                error!("Bench run failed: {}", e);
+                eprintln!("\n❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    // Handle --serve: run as a long-lived HTTP service instead of a single audit.
+    if args.serve {
+        let port = args.port;
+        return match server::run_server(args, port).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                error!("Server exited: {}", e);
+                eprintln!("\n❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    // Handle --workload: run a batch of full audits instead of a single one.
+    if let Some(ref workload_path) = args.workload {
+        return match batch::run_workload(workload_path, &args).await {
+            Ok(exit_code) => std::process::exit(exit_code),
+            Err(e) => {
+                error!("Workload run failed: {}", e);
+                eprintln!("\n❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    // Handle --apply: rewrite files from a previously generated report instead
+    // of running a new audit.
+    if let Some(ref report_path) = args.apply {
+        let local_dir = args.local.clone().expect("clap requires --local with --apply");
+        return match run_apply(report_path, &local_dir, args.dry_run) {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                error!("Apply failed: {}", e);
+                eprintln!("\n❌ Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+
     // Run the audit
     match run_audit(args).await {
         Ok(exit_code) => {
@@ -62,37 +163,125 @@ async fn main() -> Result<()> {
     }
 }
 
-/// Handle --init-config: generate a default .yoauditor.toml.
-fn handle_init_config() -> Result<()> {
-    let path = std::path::Path::new(".yoauditor.toml");
+/// Handle --init-config: generate a fully commented .yoauditor.toml at
+/// --init-config-path (default ./.yoauditor.toml), refusing to overwrite an
+/// existing file unless --force is given.
+fn handle_init_config(args: &Args) -> Result<()> {
+    let path = args
+        .init_config_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(".yoauditor.toml"));
 
-    if path.exists() {
-        eprintln!("⚠️  .yoauditor.toml already exists. Remove it first or edit it manually.");
+    if path.exists() && !args.force {
+        eprintln!(
+            "⚠️  {} already exists. Pass --force to overwrite it, or edit it manually.",
+            path.display()
+        );
         std::process::exit(1);
     }
 
     let content = Config::default_toml();
-    std::fs::write(path, &content).context("Failed to write .yoauditor.toml")?;
+    std::fs::write(&path, &content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
 
-    println!("✅ Created .yoauditor.toml with default settings.");
+    println!("✅ Created {} with default settings.", path.display());
     println!("   Edit it to customize model, extensions, excludes, and more.");
     Ok(())
 }
 
-/// Initialize logging based on verbosity settings.
+/// Handle --list-profiles: print each profile defined in `.yoauditor.toml`
+/// and the flag values it resolves to.
+fn print_profiles(config: &Config) {
+    if config.profiles.is_empty() {
+        println!("No profiles defined. Add a [profiles.<name>] table to .yoauditor.toml.");
+        return;
+    }
+
+    println!("📋 Available profiles:\n");
+    let mut names: Vec<&String> = config.profiles.keys().collect();
+    names.sort();
+
+    for name in names {
+        let profile = &config.profiles[name];
+        println!("  {}", name);
+        if let Some(level) = profile.fail_on {
+            println!("    --fail-on {:?}", level);
+        }
+        if let Some(level) = profile.min_severity {
+            println!("    --min-severity {:?}", level);
+        }
+        if let Some(format) = profile.format {
+            println!("    --format {:?}", format);
+        }
+        if let Some(single_call) = profile.single_call {
+            println!(
+                "    {}",
+                if single_call { "--single-call" } else { "--no-single-call" }
+            );
+        }
+        if let Some(concurrency) = profile.concurrency {
+            println!("    --concurrency {}", concurrency);
+        }
+        if let Some(max_chunk_lines) = profile.max_chunk_lines {
+            println!("    --max-chunk-lines {}", max_chunk_lines);
+        }
+        println!();
+    }
+}
+
+/// Handle --list-runs: load recorded run history and print it as a table,
+/// optionally narrowed by --list-runs-repo/--list-runs-model.
+fn handle_list_runs(args: &Args, config: &Config) -> Result<()> {
+    let Some(store) = history::HistoryStore::new(&config.history)? else {
+        println!("Run history is disabled ([history].enabled = false in config).");
+        return Ok(());
+    };
+
+    let mut records = store.list()?;
+    if let Some(repo) = &args.list_runs_repo {
+        records.retain(|record| &record.repo == repo);
+    }
+    if let Some(model) = &args.list_runs_model {
+        records.retain(|record| &record.model == model);
+    }
+
+    print_runs(&records);
+    Ok(())
+}
+
+/// Print recorded runs as a table, most recent last (matching the oldest-
+/// first order `HistoryStore::list` returns).
+fn print_runs(records: &[history::RunRecord]) {
+    if records.is_empty() {
+        println!("No recorded runs. Runs are recorded automatically unless [history].enabled = false.");
+        return;
+    }
+
+    println!("📜 Recorded runs:\n");
+    println!(
+        "{:<26} {:<22} {:>9} {:>9} {:>9} {:>8} {:>10} {}",
+        "Timestamp", "Model", "Critical", "High", "Medium", "Low", "Duration", "Repo"
+    );
+    for record in records {
+        println!(
+            "{:<26} {:<22} {:>9} {:>9} {:>9} {:>8} {:>10} {}",
+            record.timestamp.to_rfc3339(),
+            record.model,
+            record.critical,
+            record.high,
+            record.medium,
+            record.low,
+            format!("{:.1}s", record.duration_seconds),
+            record.repo,
+        );
+    }
+}
+
+/// Initialize logging based on verbosity settings. Also starts the
+/// in-memory log capture (and the `--log-file` NDJSON sink, if set) that
+/// eventually becomes `Report::logs` (see `crate::logging`).
 fn init_logging(args: &Args) {
-    let level = args.log_level();
-
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(level)
-        .with_target(false)
-        .with_thread_ids(false)
-        .with_file(false)
-        .with_line_number(false)
-        .compact()
-        .finish();
-
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
+    logging::init(args.log_level(), args.log_file.as_deref());
 }
 
 /// Run the complete audit workflow. Returns exit code (0 or 2).
@@ -101,20 +290,38 @@ async fn run_audit(args: Args) -> Result<i32> {
 
     // Load configuration
     let mut config = load_config(&args)?;
+    config.apply_env()?;
     config.merge_with_args(&args);
+    config.validate(args.allow_large_scan)?;
 
     let repo_url = args.repo_url().to_string();
 
-    // Step 1: Get the repository
+    // Step 1: Get the repository. --watch-remote needs the live
+    // `CloneResult` (not just its path) so it can keep fetching the same
+    // `origin` afterwards, so it clones directly here instead of going
+    // through `get_repository`, which only returns a path.
     println!("📥 Cloning repository: {}", repo_url);
-    let repo_path = get_repository(&args).await?;
+    let (repo_path, watch_remote_clone) = if args.watch_remote {
+        let clone_result = repo::clone_repository(&repo_url, build_clone_options(&args))?;
+        (clone_result.path.clone(), Some(clone_result))
+    } else {
+        (get_repository(&args).await?, None)
+    };
     info!("Repository at: {}", repo_path.display());
 
-    // Try to load config from repository
-    if let Ok(Some(repo_config)) = Config::load_from_repo(&repo_path) {
-        info!("Found .yoauditor.toml in repository");
-        config = repo_config;
-        config.merge_with_args(&args);
+    // Re-resolve config now the repo path is known, so its own
+    // .yoauditor.toml layers in field-by-field alongside the global and CWD
+    // files already folded in by `load_config` (see `Config::discover`).
+    if args.config.is_none() {
+        match Config::discover(&repo_path) {
+            Ok(repo_config) => {
+                config = repo_config;
+                config.apply_env()?;
+                config.merge_with_args(&args);
+                config.validate(args.allow_large_scan)?;
+            }
+            Err(e) => warn!("Failed to load config from repository: {}", e),
+        }
     }
 
     // Create scan config from scanner settings
@@ -122,9 +329,248 @@ async fn run_audit(args: Args) -> Result<i32> {
 
     // Handle --dry-run: scan files and exit
     if args.dry_run {
-        return handle_dry_run(&repo_path, &scan_config);
+        return handle_dry_run(
+            &repo_path,
+            &scan_config,
+            &config.rules,
+            args.skip_rules,
+            &config.supply_chain,
+            args.supply_chain || config.supply_chain.enabled,
+        );
+    }
+
+    // --since/--changed-only: resolve the restricted file set up front so it
+    // can flow through the normal single-call pipeline unchanged.
+    let diff_scope = if args.since.is_some() || args.changed_only {
+        Some(resolve_diff_scope(&repo_path, args.since.as_deref(), args.changed_only)?)
+    } else {
+        None
+    };
+
+    // --watch takes over from here: it keeps re-analyzing changed files and
+    // never produces a single final report, so it's handled here rather than
+    // in `audit_once` (which always builds exactly one `Report`).
+    if args.watch {
+        let agent_config = build_agent_config(&config);
+        let mut agent =
+            agent::CodeAnalysisAgent::new(agent_config, repo_path.clone(), scan_config.clone())
+                .with_cache(build_response_cache(&config));
+        agent
+            .preflight()
+            .await
+            .context("Preflight check against the chat provider failed")?;
+        let scanner = scanner::FileScanner::new(repo_path.clone(), scan_config.clone());
+        watch::run_watch(repo_path.clone(), scanner, agent).await?;
+        return Ok(0);
+    }
+
+    // --watch-remote is --watch's counterpart for a remote repo: it keeps
+    // polling `origin` instead of watching a local directory's filesystem.
+    if args.watch_remote {
+        let clone_result = watch_remote_clone
+            .expect("cloned directly above whenever --watch-remote is set");
+        let agent_config = build_agent_config(&config);
+        let mut agent =
+            agent::CodeAnalysisAgent::new(agent_config, repo_path.clone(), scan_config.clone())
+                .with_cache(build_response_cache(&config));
+        agent
+            .preflight()
+            .await
+            .context("Preflight check against the chat provider failed")?;
+        let scanner = scanner::FileScanner::new(repo_path.clone(), scan_config.clone());
+        let poll_interval = Duration::from_secs(args.poll_interval);
+        watch::run_watch_remote(
+            clone_result,
+            build_clone_options(&args),
+            poll_interval,
+            scanner,
+            agent,
+        )
+        .await?;
+        return Ok(0);
+    }
+
+    let report = audit_once(
+        &args,
+        &config,
+        &repo_path,
+        &scan_config,
+        diff_scope.as_ref(),
+        repo_url,
+        start_time,
+    )
+    .await?;
+    let summary = report.summary.clone();
+    let failed_files: Vec<_> =
+        report.files.iter().filter(|f| !f.analysis_successful).cloned().collect();
+    let duration = report.metadata.duration_seconds;
+
+    // Step 6: Diff against a previous report if requested, then generate
+    // and save the report.
+    let previous_report = match &args.diff_against {
+        Some(diff_against_path) => Some(load_previous_report(diff_against_path)?),
+        None => None,
+    };
+
+    let output = match (&previous_report, args.format) {
+        (Some(previous), OutputFormat::Json) => {
+            report::generate_diff_json_report(previous, &report)?
+        }
+        (Some(previous), OutputFormat::Markdown) => report::generate_diff_report(previous, &report),
+        (Some(_), OutputFormat::Sarif | OutputFormat::Html) => {
+            return Err(anyhow::anyhow!(
+                "--diff-against only supports --format markdown or --format json"
+            ));
+        }
+        (None, OutputFormat::Json) => report::generate_json_report(&report)?,
+        (None, OutputFormat::Markdown) => report::generate_markdown_report(&report),
+        (None, OutputFormat::Sarif) => report::generate_sarif_report(&report)?,
+        (None, OutputFormat::Html) => report::generate_html_report(&report),
+    };
+
+    std::fs::write(&args.output, &output)
+        .with_context(|| format!("Failed to write report to {}", args.output.display()))?;
+
+    if let Some(store) = build_history_store(&config) {
+        let record = history::RunRecord::from_report(&report, &args.output.display().to_string());
+        if let Err(e) = store.record(&record) {
+            warn!("Failed to record run history: {}", e);
+        }
+    }
+
+    // Print summary
+    println!("\n📊 Analysis Summary:");
+    println!(
+        "   Files with issues: {}",
+        report.files.iter().filter(|f| f.analysis_successful).count()
+    );
+    println!("   Total issues: {}", summary.total);
+    println!(
+        "   - 🔴 Critical: {} | 🟠 High: {} | 🟡 Medium: {} | 🟢 Low: {}",
+        summary.critical, summary.high, summary.medium, summary.low
+    );
+    if let Some(previous) = &previous_report {
+        let diff = report::diff_reports(previous, &report);
+        println!(
+            "   Δ vs baseline: +{} new, −{} fixed, {} persisting",
+            diff.new_issues.len(),
+            diff.fixed_issues.len(),
+            diff.persisting_issues.len()
+        );
     }
+    if !failed_files.is_empty() {
+        println!(
+            "   ⏱️  Skipped (timed out): {} file(s)",
+            failed_files.len()
+        );
+        for failed in &failed_files {
+            println!(
+                "      - {}: {}",
+                failed.path,
+                failed.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+    }
+    println!("   Duration: {:.1}s", duration);
+    println!(
+        "\n✅ Audit complete! Report saved to: {}",
+        args.output.display()
+    );
+
+    // Check --fail-on threshold. With --diff-against, only newly-introduced
+    // issues count, so fixing everything old doesn't keep failing CI.
+    if let Some(fail_level) = args.fail_on {
+        let threshold_severity = fail_on_to_severity(fail_level);
+        let has_issues_above = match &previous_report {
+            Some(previous) => report::diff_reports(previous, &report)
+                .new_issues
+                .iter()
+                .any(|i| !i.known && i.severity >= threshold_severity),
+            None => report
+                .files
+                .iter()
+                .flat_map(|f| f.issues.iter())
+                .any(|i| !i.known && i.severity >= threshold_severity),
+        };
 
+        if has_issues_above {
+            eprintln!(
+                "\n⛔ Issues found at or above {:?} severity. Failing (exit code 2).",
+                fail_level
+            );
+            return Ok(2);
+        }
+    }
+
+    Ok(0)
+}
+
+/// Builds the `agent::AgentConfig` shared by every agent instance this
+/// binary creates (the single-audit path, `--watch`, and the `--serve` HTTP
+/// server), so tuning knobs like `max_concurrency` stay in one place.
+fn build_agent_config(config: &Config) -> agent::AgentConfig {
+    agent::AgentConfig {
+        ollama_url: config.model.ollama_url.clone(),
+        model_name: config.model.name.clone(),
+        temperature: config.model.temperature,
+        max_iterations: 50,
+        timeout_seconds: config.model.timeout_seconds,
+        single_call_mode: config.model.single_call_mode,
+        max_context_messages: 10,
+        provider: agent::Provider::Ollama,
+        api_key: None,
+        chunk_byte_budget: 24_000,
+        max_concurrency: num_cpus::get(),
+        context_length: None,
+        file_timeout_seconds: config.model.file_timeout_seconds,
+        file_retries: config.model.file_retries,
+    }
+}
+
+/// Builds the `ResponseCache` shared by every agent instance this binary
+/// creates, from `config.cache`. Returns `None` (no caching) if the config
+/// disables it or if the cache directory can't be created; the latter is
+/// logged as a warning rather than failing the run, since the cache is an
+/// optimization, not something an audit should fail over.
+fn build_response_cache(config: &Config) -> Option<cache::ResponseCache> {
+    match cache::ResponseCache::new(&config.cache) {
+        Ok(cache) => cache,
+        Err(e) => {
+            warn!("Failed to initialize response cache, continuing without it: {}", e);
+            None
+        }
+    }
+}
+
+/// Builds the `HistoryStore` a completed `run_audit` records its run into,
+/// from `config.history`. Returns `None` (no recording) if the config
+/// disables it or if the history directory can't be created; the latter is
+/// logged as a warning rather than failing the run, since history is a
+/// convenience, not something an audit should fail over.
+fn build_history_store(config: &Config) -> Option<history::HistoryStore> {
+    match history::HistoryStore::new(&config.history) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to initialize run history, continuing without it: {}", e);
+            None
+        }
+    }
+}
+
+/// Initializes the agent, runs the analysis, audits dependencies, marks
+/// baseline-known issues, and builds the final `Report`. Shared by the CLI's
+/// `run_audit` and the `--serve` HTTP server so both drive one code path.
+/// Does not cover `--dry-run`/`--watch` (which return before or bypass this
+/// entirely) or writing/diffing the report, which stay with each caller.
+async fn audit_once(
+    args: &Args,
+    config: &Config,
+    repo_path: &PathBuf,
+    scan_config: &scanner::ScanConfig,
+    diff_scope: Option<&(Vec<String>, String)>,
+    repo_url: String,
+    start_time: Instant,
+) -> Result<Report> {
     // Step 2: Initialize the agent
     let mode_str = if config.model.single_call_mode {
         "Single-call (efficient)"
@@ -138,17 +584,36 @@ async fn run_audit(args: Args) -> Result<i32> {
     println!("   Mode: {}", mode_str);
     println!("   Timeout: {}s", config.model.timeout_seconds);
 
-    let agent_config = agent::AgentConfig {
-        ollama_url: config.model.ollama_url.clone(),
-        model_name: config.model.name.clone(),
-        temperature: config.model.temperature,
-        max_iterations: 50,
-        timeout_seconds: config.model.timeout_seconds,
-        single_call_mode: config.model.single_call_mode,
-        max_context_messages: 10,
+    let agent_config = build_agent_config(config);
+    let mut agent =
+        agent::CodeAnalysisAgent::new(agent_config, repo_path.clone(), scan_config.clone())
+            .with_cache(build_response_cache(config));
+
+    // Stream the assistant's text as it's generated instead of going quiet
+    // until the whole completion lands. `--quiet` skips it, matching how it
+    // already suppresses the rest of this function's progress output.
+    let stream_task = if !args.quiet {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        agent.set_stream_channel(tx);
+        Some(tokio::spawn(async move {
+            use std::io::Write;
+            while let Some(event) = rx.recv().await {
+                if let agent::StreamEvent::ContentDelta(delta) = event {
+                    print!("{}", delta);
+                    let _ = std::io::stdout().flush();
+                }
+            }
+        }))
+    } else {
+        None
     };
 
-    let mut agent = agent::CodeAnalysisAgent::new(agent_config, repo_path.clone(), scan_config);
+    // Confirm the server is reachable and the model is actually installed
+    // before committing to a (potentially long) analysis run.
+    agent
+        .preflight()
+        .await
+        .context("Preflight check against the chat provider failed")?;
 
     // Step 3: Run the agentic analysis
     println!("\n🔬 Running code analysis...");
@@ -159,28 +624,79 @@ async fn run_audit(args: Args) -> Result<i32> {
         println!("   The AI agent will explore the repository using tools...\n");
     }
 
-    let reported_issues = agent.run_analysis().await?;
+    let reported_issues = match &diff_scope {
+        Some((changed_paths, label)) if config.model.single_call_mode => {
+            println!(
+                "   🔎 Scoped to {} changed file(s) since {}\n",
+                changed_paths.len(),
+                label
+            );
+            let files = collect_scoped_files(repo_path, scan_config, changed_paths)?;
+            agent.analyze_files(files).await?
+        }
+        Some(_) => {
+            warn!(
+                "--since/--changed-only currently only scope single-call mode; \
+                 running a full tool-calling analysis instead"
+            );
+            agent.run_analysis().await?
+        }
+        None => agent.run_analysis().await?,
+    };
+    let failed_files = agent.last_failed_files().to_vec();
+
+    // Drop the agent (and with it, the stream sender) so the printer task
+    // sees the channel close and finishes before we move on to output that
+    // shouldn't be interleaved with streamed text.
+    drop(agent);
+    if let Some(task) = stream_task {
+        let _ = task.await;
+        println!();
+    }
 
     // Step 4: Convert reported issues to our Issue format
-    let mut issues: Vec<Issue> = reported_issues
-        .into_iter()
-        .map(|ri| Issue {
-            file_path: ri.file_path,
-            start_line: ri.line_number,
-            end_line: None,
-            severity: match ri.severity.to_lowercase().as_str() {
-                "critical" => Severity::Critical,
-                "high" => Severity::High,
-                "medium" => Severity::Medium,
-                _ => Severity::Low,
-            },
-            category: ri.category,
-            title: ri.title,
-            description: ri.description,
-            suggestion: ri.suggestion,
-            code_snippet: None,
-        })
-        .collect();
+    let mut issues: Vec<Issue> = reported_issues.into_iter().map(Issue::from).collect();
+
+    // Re-scan file contents once, for the rule engine, the lines-of-code
+    // breakdown, and the baseline fingerprint's code-context window.
+    // Separate from the agent's own file reads since not every mode
+    // (tool-calling) keeps contents around after analysis.
+    let file_contents = scanner::FileScanner::new(repo_path.clone(), scan_config.clone())
+        .collect_files()
+        .unwrap_or_else(|e| {
+            warn!("Failed to re-scan file contents: {}", e);
+            std::collections::HashMap::new()
+        });
+
+    // Step 4a: Run the deterministic rule engine and merge its findings with
+    // the agent's before de-duplicating, so the same issue surfaced by both
+    // only appears once.
+    if !args.skip_rules && config.rules.enabled {
+        match rules::RuleSet::load(&config.rules) {
+            Ok(rule_set) => issues.extend(rule_set.run(&file_contents)),
+            Err(e) => warn!("Rule engine failed to load, continuing without it: {}", e),
+        }
+    }
+    issues = analysis::dedupe_by_fingerprint(issues);
+
+    // Step 4b: Audit Cargo.lock against the RustSec advisory database. A
+    // fetch/parse failure only downgrades to a warning so a network hiccup
+    // doesn't fail an otherwise-successful LLM-based audit.
+    if !args.skip_dependency_audit {
+        match dependency_audit::audit_dependencies(repo_path) {
+            Ok(dependency_issues) => issues.extend(dependency_issues),
+            Err(e) => warn!("Dependency audit failed, continuing without it: {}", e),
+        }
+    }
+
+    // Step 4c: Run the supply-chain manifest/lockfile audit. Off by default
+    // (see --supply-chain), so most runs skip the extra manifest parsing.
+    if args.supply_chain || config.supply_chain.enabled {
+        match supply_chain::audit_supply_chain(repo_path, &config.supply_chain) {
+            Ok(supply_chain_issues) => issues.extend(supply_chain_issues),
+            Err(e) => warn!("Supply-chain audit failed, continuing without it: {}", e),
+        }
+    }
 
     // Apply --min-severity filter
     if let Some(min_level) = args.min_severity {
@@ -188,16 +704,34 @@ async fn run_audit(args: Args) -> Result<i32> {
         issues.retain(|issue| issue.severity >= min_severity);
     }
 
+    // Step 4d: Mark issues already accepted into the baseline as "known" so
+    // re-audits of an evolving repo only fail CI on genuinely new findings.
+    let baseline_path = std::path::PathBuf::from(&config.general.baseline);
+    let baseline = baseline::Baseline::load(&baseline_path)?;
+    for issue in &mut issues {
+        issue.known = baseline.contains(issue, &file_contents);
+    }
+    if args.update_baseline {
+        baseline::Baseline::from_issues(&issues, &file_contents).save(&baseline_path)?;
+        println!("📌 Updated baseline at: {}", baseline_path.display());
+    }
+
     // Step 5: Build the report
     println!("\n📝 Generating report...");
 
     let duration = start_time.elapsed().as_secs_f64();
     let summary = IssueSummary::from_issues(&issues);
 
+    // Surface recurring issue titles (near-duplicates clustered via
+    // Levenshtein distance) as an extra recommendation, since a pattern
+    // repeated across the codebase usually calls for a single structural
+    // fix rather than N one-off edits.
+    let recurring_patterns = analysis::identify_patterns(&issues);
+
     // Group issues by file using the aggregator
     let files_map = analysis::group_by_file(&issues);
 
-    let analyzed_files: Vec<AnalyzedFile> = files_map
+    let mut analyzed_files: Vec<AnalyzedFile> = files_map
         .into_iter()
         .map(|(path, file_issues)| AnalyzedFile {
             path,
@@ -209,70 +743,153 @@ async fn run_audit(args: Args) -> Result<i32> {
         })
         .collect();
 
+    for failed in &failed_files {
+        analyzed_files.push(AnalyzedFile {
+            path: failed.path.clone(),
+            language: "Unknown".to_string(),
+            line_count: 0,
+            issues: Vec::new(),
+            analysis_successful: false,
+            error: Some(failed.error.clone()),
+        });
+    }
+
     let metadata = ReportMetadata {
         repo_url: repo_url.clone(),
         analysis_date: Utc::now(),
         model_used: config.model.name.clone(),
         files_analyzed: analyzed_files.len(),
-        files_failed: 0,
+        files_failed: failed_files.len(),
         total_issues: summary.total,
         duration_seconds: duration,
+        scoped_to_diff: diff_scope.map(|(_, label)| label.clone()),
     };
 
+    let code_stats = stats::compute_stats(&file_contents);
+
+    let mut recommendations = vec![
+        "Review all reported issues and prioritize by severity.".to_string(),
+        "Address critical and high severity issues first.".to_string(),
+    ];
+    for (title, count) in &recurring_patterns {
+        recommendations.push(format!(
+            "Recurring issue: \"{}\" appears {} times — consider a single structural fix instead of {} one-off edits.",
+            title, count, count
+        ));
+    }
+
     let report = Report {
         metadata,
         project_overview: "Analysis performed by AI agent with tool-calling capabilities."
             .to_string(),
         files: analyzed_files,
+        code_stats,
         summary: summary.clone(),
-        recommendations: vec![
-            "Review all reported issues and prioritize by severity.".to_string(),
-            "Address critical and high severity issues first.".to_string(),
-        ],
+        recommendations,
+        logs: logging::current().snapshot(),
     };
 
-    // Step 6: Generate and save the report
-    let output = match args.format {
-        OutputFormat::Json => report::generate_json_report(&report)?,
-        OutputFormat::Markdown => report::generate_markdown_report(&report),
-    };
+    Ok(report)
+}
 
-    std::fs::write(&args.output, &output)
-        .with_context(|| format!("Failed to write report to {}", args.output.display()))?;
+/// Load a previously generated JSON report for `--diff-against`.
+fn load_previous_report(path: &PathBuf) -> Result<Report> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline report {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {} as a JSON report", path.display()))
+}
 
-    // Print summary
-    println!("\n📊 Analysis Summary:");
-    println!("   Files with issues: {}", report.files.len());
-    println!("   Total issues: {}", summary.total);
+/// Handle --bench: run a workload file, score the results, and write them out.
+async fn run_bench(workload_path: &PathBuf, output_path: &PathBuf) -> Result<()> {
+    println!("📊 Running benchmark workload: {}", workload_path.display());
+
+    let result = eval::run_bench_file(workload_path, output_path).await?;
+
+    println!("\n📊 Bench Results: {}", result.workload_name);
+    println!("   Model: {} (single_call={})", result.model_name, result.single_call_mode);
+    println!("   Duration: {:.1}s, iterations: {}", result.duration_seconds, result.iterations);
     println!(
-        "   - 🔴 Critical: {} | 🟠 High: {} | 🟡 Medium: {} | 🟢 Low: {}",
-        summary.critical, summary.high, summary.medium, summary.low
+        "   Issues found: {} / expected: {}",
+        result.issues_found, result.issues_expected
     );
-    println!("   Duration: {:.1}s", duration);
     println!(
-        "\n✅ Audit complete! Report saved to: {}",
-        args.output.display()
+        "   Overall: precision={:.2} recall={:.2} f1={:.2}",
+        result.overall.precision, result.overall.recall, result.overall.f1
     );
+    for score in &result.category_scores {
+        println!(
+            "     - {}: precision={:.2} recall={:.2} f1={:.2} (tp={} fp={} fn={})",
+            score.category,
+            score.precision,
+            score.recall,
+            score.f1,
+            score.true_positives,
+            score.false_positives,
+            score.false_negatives
+        );
+    }
+    println!("\n✅ Bench results written to: {}", output_path.display());
 
-    // Check --fail-on threshold
-    if let Some(fail_level) = args.fail_on {
-        let threshold_severity = fail_on_to_severity(fail_level);
-        let has_issues_above = issues.iter().any(|i| i.severity >= threshold_severity);
+    Ok(())
+}
 
-        if has_issues_above {
-            eprintln!(
-                "\n⛔ Issues found at or above {:?} severity. Failing (exit code 2).",
-                fail_level
-            );
-            return Ok(2);
+/// Handle --apply: read a JSON report's `Issue.fix` edits and rewrite the
+/// files under `local_dir`. With `dry_run`, prints the diff instead of
+/// writing anything.
+fn run_apply(report_path: &PathBuf, local_dir: &PathBuf, dry_run: bool) -> Result<()> {
+    let content = std::fs::read_to_string(report_path)
+        .with_context(|| format!("Failed to read report file {}", report_path.display()))?;
+    let report: Report = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {} as a JSON report", report_path.display()))?;
+
+    println!(
+        "🛠️  Applying fixes from {} to {}{}",
+        report_path.display(),
+        local_dir.display(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    let summary = apply::apply_fixes(&report, local_dir, dry_run)?;
+
+    for outcome in &summary.applied {
+        if let Some(preview) = &outcome.preview {
+            println!("\n--- {} ({} edit(s)) ---", outcome.file_path, outcome.edits_applied);
+            print!("{}", preview);
+        } else {
+            println!("✅ {} ({} edit(s) applied)", outcome.file_path, outcome.edits_applied);
         }
     }
 
-    Ok(0)
+    for skipped in &summary.skipped {
+        eprintln!("⚠️  Skipped {}: {}", skipped.file_path, skipped.reason);
+    }
+
+    println!(
+        "\n{} file(s) {}, {} skipped.",
+        summary.applied.len(),
+        if dry_run { "previewed" } else { "updated" },
+        summary.skipped.len()
+    );
+
+    Ok(())
 }
 
 /// Handle --dry-run: scan files, print what would be analyzed, exit.
-fn handle_dry_run(repo_path: &PathBuf, scan_config: &scanner::ScanConfig) -> Result<i32> {
+///
+/// Also runs the deterministic rule engine (unless `--skip-rules`/
+/// `[rules].enabled = false`) and the supply-chain audit (if requested via
+/// `--supply-chain`/`[supply_chain].enabled = true`), previewing what each
+/// would find, since neither needs an LLM and both are cheap enough to run
+/// even here.
+fn handle_dry_run(
+    repo_path: &PathBuf,
+    scan_config: &scanner::ScanConfig,
+    rules_config: &config::RulesConfig,
+    skip_rules: bool,
+    supply_chain_config: &config::SupplyChainConfig,
+    run_supply_chain: bool,
+) -> Result<i32> {
     println!("\n🔍 Dry run: scanning files (no LLM call)...\n");
 
     let file_scanner = scanner::FileScanner::new(repo_path.clone(), scan_config.clone());
@@ -288,10 +905,113 @@ fn handle_dry_run(repo_path: &PathBuf, scan_config: &scanner::ScanConfig) -> Res
         println!("\n   Total: {} files", files.len());
     }
 
+    if !skip_rules && rules_config.enabled {
+        match rules::RuleSet::load(rules_config) {
+            Ok(rule_set) => {
+                let file_contents = file_scanner.collect_files().unwrap_or_else(|e| {
+                    warn!("Failed to read file contents for rule preview: {}", e);
+                    std::collections::HashMap::new()
+                });
+                let rule_issues = rule_set.run(&file_contents);
+                if rule_issues.is_empty() {
+                    println!("\n🔎 Rule engine: no deterministic findings.");
+                } else {
+                    println!(
+                        "\n🔎 Rule engine: {} deterministic finding(s) (offline, no LLM call):\n",
+                        rule_issues.len()
+                    );
+                    for issue in &rule_issues {
+                        println!(
+                            "     {} {}:{} — {}",
+                            issue.severity.emoji(),
+                            issue.file_path,
+                            issue.start_line,
+                            issue.title
+                        );
+                    }
+                }
+            }
+            Err(e) => warn!("Rule engine failed to load: {}", e),
+        }
+    }
+
+    if run_supply_chain {
+        match supply_chain::audit_supply_chain(repo_path, supply_chain_config) {
+            Ok(supply_chain_issues) => {
+                if supply_chain_issues.is_empty() {
+                    println!("\n🔎 Supply-chain audit: no findings.");
+                } else {
+                    println!(
+                        "\n🔎 Supply-chain audit: {} finding(s) (offline, no LLM call):\n",
+                        supply_chain_issues.len()
+                    );
+                    for issue in &supply_chain_issues {
+                        println!("     {} {} — {}", issue.severity.emoji(), issue.file_path, issue.title);
+                    }
+                }
+            }
+            Err(e) => warn!("Supply-chain audit failed: {}", e),
+        }
+    }
+
     println!("\n✅ Dry run complete. No LLM calls were made.");
     Ok(0)
 }
 
+/// Resolve `--since`/`--changed-only` into the concrete changed file list
+/// plus a short label describing the base ref, for both scoping the analysis
+/// and annotating the report so readers don't mistake a reduced issue count
+/// for an improvement.
+fn resolve_diff_scope(
+    repo_path: &PathBuf,
+    since: Option<&str>,
+    changed_only: bool,
+) -> Result<(Vec<String>, String)> {
+    let git_repo = git2::Repository::open(repo_path).with_context(|| {
+        format!(
+            "--since/--changed-only require a git repository at {}, but none was found",
+            repo_path.display()
+        )
+    })?;
+
+    let (base_ref, label) = match since {
+        Some(since_ref) => (since_ref.to_string(), since_ref.to_string()),
+        None => {
+            debug_assert!(changed_only);
+            let merge_base = repo::default_branch_merge_base(&git_repo)?;
+            let label = format!("merge-base {}", &merge_base[..merge_base.len().min(8)]);
+            (merge_base, label)
+        }
+    };
+
+    let changed = repo::changed_files_since(&git_repo, &base_ref)
+        .with_context(|| format!("Failed to diff against {}", base_ref))?;
+
+    Ok((changed, label))
+}
+
+/// Scan the repository, then narrow the result to `changed_paths` expanded
+/// with same-language files that look like they depend on them.
+fn collect_scoped_files(
+    repo_path: &PathBuf,
+    scan_config: &scanner::ScanConfig,
+    changed_paths: &[String],
+) -> Result<Vec<(String, String)>> {
+    let file_scanner = scanner::FileScanner::new(repo_path.clone(), scan_config.clone());
+    let all_files = file_scanner.collect_files()?;
+
+    let changed_set: std::collections::HashSet<String> = changed_paths.iter().cloned().collect();
+    let scoped_set = scanner::expand_with_dependents(&changed_set, &all_files);
+
+    let mut files: Vec<(String, String)> = all_files
+        .into_iter()
+        .filter(|(path, _)| scoped_set.contains(path))
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(files)
+}
+
 /// Convert FailOnLevel to Severity for comparison.
 fn fail_on_to_severity(level: FailOnLevel) -> Severity {
     match level {
@@ -310,16 +1030,11 @@ fn load_config(args: &Args) -> Result<Config> {
         return Config::load(config_path);
     }
 
-    // Try default location
-    match Config::load_default() {
-        Ok(Some(config)) => {
-            info!("Loaded default config from .yoauditor.toml");
-            Ok(config)
-        }
-        Ok(None) => {
-            debug!("No config file found, using defaults");
-            Ok(Config::default())
-        }
+    // Layer the global and CWD config files (see `Config::discover`). The
+    // repo being audited isn't cloned/located yet at this point, so its own
+    // .yoauditor.toml is folded in later, once `run_audit` knows the path.
+    match Config::discover(Path::new(".")) {
+        Ok(config) => Ok(config),
         Err(e) => {
             warn!("Failed to load config: {}", e);
             Ok(Config::default())
@@ -327,6 +1042,23 @@ fn load_config(args: &Args) -> Result<Config> {
     }
 }
 
+/// Build `CloneOptions` from the flags shared by every cloning entry point
+/// (the single-repo clone below, and `--watch-remote`'s own clone).
+fn build_clone_options(args: &Args) -> repo::CloneOptions {
+    repo::CloneOptions {
+        branch: args.branch.clone(),
+        depth: Some(1), // Shallow clone
+        show_progress: !args.quiet,
+        recurse_submodules: args.recurse_submodules,
+        ssh_private_key: args.ssh_key.clone(),
+        ssh_passphrase: args.ssh_key_passphrase.clone(),
+        https_token: args.https_token.clone(),
+        username: args.username.clone(),
+        reference: args.git_ref.clone(),
+        ..Default::default()
+    }
+}
+
 /// Get the repository path (clone if needed).
 async fn get_repository(args: &Args) -> Result<PathBuf> {
     // Use local directory if specified
@@ -339,13 +1071,9 @@ async fn get_repository(args: &Args) -> Result<PathBuf> {
     let repo_url = args.repo_url();
     info!("Cloning repository: {}", repo_url);
 
-    let clone_options = repo::CloneOptions {
-        branch: args.branch.clone(),
-        depth: Some(1), // Shallow clone
-        show_progress: !args.quiet,
-        target_dir: None,
-    };
-
-    let clone_result = repo::clone_repository(repo_url, clone_options)?;
+    let clone_result = repo::clone_repository(repo_url, build_clone_options(args))?;
+    if let Some(ref commit) = clone_result.resolved_commit {
+        info!("Pinned to commit: {}", commit);
+    }
     Ok(clone_result.into_path())
 }
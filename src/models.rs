@@ -42,6 +42,27 @@ impl Severity {
             Severity::Critical => "🔴",
         }
     }
+
+    /// Returns the ANSI color escape code for this severity, for
+    /// terminal-rendered diagnostics (see `report::renderer::render_issue`).
+    pub fn ansi_color(&self) -> &'static str {
+        match self {
+            Severity::Low => "\x1b[32m",      // green
+            Severity::Medium => "\x1b[33m",   // yellow
+            Severity::High => "\x1b[38;5;208m", // orange
+            Severity::Critical => "\x1b[31m", // red
+        }
+    }
+
+    /// Maps to a SARIF `level` (`error`/`warning`/`note`/`none`), per the
+    /// standard severity scale editors and CI consume.
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            Severity::Critical | Severity::High => "error",
+            Severity::Medium => "warning",
+            Severity::Low => "note",
+        }
+    }
 }
 
 /// Category of an issue (for future structured categorization).
@@ -107,6 +128,45 @@ pub struct Issue {
     /// Optional code snippet showing the issue.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code_snippet: Option<String>,
+    /// Optional machine-applicable fix, for `--apply` mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+    /// Starting column of the issue (1-indexed), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    /// Ending column of the issue (1-indexed), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    /// Stable rule identifier for SARIF/editor integrations, derived from
+    /// category and title (see `Issue::derive_rule_id`).
+    #[serde(default)]
+    pub rule_id: String,
+    /// Set when this issue matches a fingerprint in the `--baseline` file.
+    /// Still rendered in reports, but excluded from the `--fail-on` check
+    /// (see `baseline::Baseline`). Recomputed against the baseline on every
+    /// run rather than being meaningful input.
+    #[serde(default)]
+    pub known: bool,
+}
+
+/// A structured, machine-applicable fix for an `Issue`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fix {
+    /// One or more text edits that together resolve the issue.
+    pub edits: Vec<TextEdit>,
+}
+
+/// A single text replacement within a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextEdit {
+    /// Path to the file to edit (relative to repo root).
+    pub file_path: String,
+    /// First line to replace (1-indexed, inclusive).
+    pub start_line: usize,
+    /// Last line to replace (1-indexed, inclusive).
+    pub end_line: usize,
+    /// Text to replace the line range with.
+    pub replacement: String,
 }
 
 impl Issue {
@@ -117,6 +177,29 @@ impl Issue {
             _ => self.start_line.to_string(),
         }
     }
+
+    /// Derives a stable SARIF `ruleId` slug from a category and title, e.g.
+    /// `("Security", "SQL Injection")` -> `"security/sql-injection"`.
+    pub fn derive_rule_id(category: &str, title: &str) -> String {
+        format!("{}/{}", slugify(category), slugify(title))
+    }
+}
+
+/// Lowercases and replaces runs of non-alphanumeric characters with a
+/// single `-`, trimming leading/trailing dashes.
+fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    let mut last_was_dash = true; // avoid a leading dash
+    for c in s.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+            last_was_dash = false;
+        } else if !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
+    slug.trim_end_matches('-').to_string()
 }
 
 /// Represents an analyzed source code file.
@@ -232,6 +315,12 @@ pub struct ReportMetadata {
     pub total_issues: usize,
     /// Duration of the analysis in seconds.
     pub duration_seconds: f64,
+    /// Set when `--since`/`--changed-only` scoped this run to files that
+    /// differ from a base git ref, so readers don't mistake the reduced
+    /// issue count for an improvement. Holds the base ref that was diffed
+    /// against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoped_to_diff: Option<String>,
 }
 
 /// The complete code audit report.
@@ -247,6 +336,14 @@ pub struct Report {
     pub summary: IssueSummary,
     /// High-level improvement recommendations.
     pub recommendations: Vec<String>,
+    /// Per-language lines-of-code/comment/blank breakdown (see `crate::stats`).
+    #[serde(default)]
+    pub code_stats: crate::stats::CodeStats,
+    /// Structured log records captured during the run (see
+    /// `crate::logging`), so the JSON report is self-describing even when
+    /// the console/`--log-file` output isn't available to the reader.
+    #[serde(default)]
+    pub logs: Vec<crate::logging::LogRecord>,
 }
 
 impl Report {
@@ -259,6 +356,8 @@ impl Report {
             files: Vec::new(),
             summary: IssueSummary::default(),
             recommendations: Vec::new(),
+            code_stats: crate::stats::CodeStats::default(),
+            logs: Vec::new(),
         }
     }
 
@@ -270,6 +369,109 @@ impl Report {
             IssueSummary::from_issues(&all_issues.into_iter().cloned().collect::<Vec<_>>());
         self.metadata.total_issues = self.summary.total;
     }
+
+    /// Renders the report as a SARIF 2.1.0 `runs`/`results` document, for
+    /// editors and CI systems (including GitHub code scanning) that consume
+    /// the standard static-analysis interchange format.
+    ///
+    /// The run's `tool.driver.rules` array is built by de-duplicating all
+    /// issues on the `rule_id` each `Issue` carries into `reportingDescriptor`
+    /// entries, each named after the first issue with that id.
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let all_issues: Vec<(&AnalyzedFile, &Issue)> = self
+            .files
+            .iter()
+            .flat_map(|file| file.issues.iter().map(move |issue| (file, issue)))
+            .collect();
+
+        let mut seen_rules = std::collections::HashSet::new();
+        let rules: Vec<serde_json::Value> = all_issues
+            .iter()
+            .filter(|(_, issue)| seen_rules.insert(issue.rule_id.clone()))
+            .map(|(_, issue)| {
+                serde_json::json!({
+                    "id": issue.rule_id,
+                    "name": issue.title,
+                    "shortDescription": {
+                        "text": issue.title,
+                    },
+                })
+            })
+            .collect();
+
+        let results: Vec<serde_json::Value> = all_issues
+            .iter()
+            .map(|(file, issue)| {
+                serde_json::json!({
+                    "ruleId": issue.rule_id,
+                    "level": issue.severity.sarif_level(),
+                    "message": {
+                        "text": issue.description,
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": file.path,
+                            },
+                            "region": {
+                                "startLine": issue.start_line,
+                                "endLine": issue.end_line.unwrap_or(issue.start_line),
+                                "startColumn": issue.start_column,
+                                "endColumn": issue.end_column,
+                            },
+                        },
+                    }],
+                    "fixes": [{
+                        "description": {
+                            "text": issue.suggestion,
+                        },
+                    }],
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "YoAuditor",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+}
+
+impl From<crate::agent::tools::ReportedIssue> for Issue {
+    fn from(ri: crate::agent::tools::ReportedIssue) -> Self {
+        let rule_id = Issue::derive_rule_id(&ri.category, &ri.title);
+        Self {
+            file_path: ri.file_path,
+            start_line: ri.line_number,
+            end_line: None,
+            severity: match ri.severity.to_lowercase().as_str() {
+                "critical" => Severity::Critical,
+                "high" => Severity::High,
+                "medium" => Severity::Medium,
+                _ => Severity::Low,
+            },
+            category: ri.category,
+            title: ri.title,
+            description: ri.description,
+            suggestion: ri.suggestion,
+            code_snippet: ri.code_snippet,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id,
+            known: false,
+        }
+    }
 }
 
 /// Represents a file to be analyzed (for future batch processing).
@@ -318,6 +520,23 @@ mod tests {
         assert_eq!(Severity::Low.emoji(), "🟢");
     }
 
+    #[test]
+    fn test_severity_ansi_color_distinct() {
+        let colors = [
+            Severity::Low.ansi_color(),
+            Severity::Medium.ansi_color(),
+            Severity::High.ansi_color(),
+            Severity::Critical.ansi_color(),
+        ];
+        for (i, a) in colors.iter().enumerate() {
+            for (j, b) in colors.iter().enumerate() {
+                if i != j {
+                    assert_ne!(a, b);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_category_from_str() {
         assert_eq!(Category::from("bug"), Category::Bug);
@@ -341,6 +560,11 @@ mod tests {
             description: "Test description".to_string(),
             suggestion: "Test suggestion".to_string(),
             code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
         };
         assert_eq!(issue.line_range(), "10-15");
 
@@ -365,6 +589,11 @@ mod tests {
                 description: "".to_string(),
                 suggestion: "".to_string(),
                 code_snippet: None,
+                fix: None,
+                start_column: None,
+                end_column: None,
+                rule_id: String::new(),
+            known: false,
             },
             Issue {
                 file_path: "test.rs".to_string(),
@@ -376,6 +605,11 @@ mod tests {
                 description: "".to_string(),
                 suggestion: "".to_string(),
                 code_snippet: None,
+                fix: None,
+                start_column: None,
+                end_column: None,
+                rule_id: String::new(),
+            known: false,
             },
             Issue {
                 file_path: "test.rs".to_string(),
@@ -387,6 +621,11 @@ mod tests {
                 description: "".to_string(),
                 suggestion: "".to_string(),
                 code_snippet: None,
+                fix: None,
+                start_column: None,
+                end_column: None,
+                rule_id: String::new(),
+            known: false,
             },
         ];
 
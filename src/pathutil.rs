@@ -0,0 +1,59 @@
+//! Shared path-safety helpers used anywhere a tool-supplied or
+//! report-supplied relative path gets joined onto a repo root and must be
+//! checked against escaping it (`agent/tools.rs::resolve_repo_path`,
+//! `apply.rs::resolve_within_repo`, `scanner/mod.rs::is_within_repo`).
+
+use std::path::{Component, Path, PathBuf};
+
+/// Resolves `.`/`..` components against `path` purely lexically, without
+/// touching the filesystem. `Path::starts_with` is component-wise and
+/// doesn't collapse `..`, so a non-canonicalized `repo_root.join("../../etc/x")`
+/// textually "starts with" `repo_root` -- this normalizes first so that
+/// check is actually meaningful for paths that don't exist yet (and so
+/// can't go through `fs::canonicalize`).
+pub fn lexically_normalize(path: &Path) -> PathBuf {
+    let mut stack: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(stack.last(), Some(Component::Normal(_))) {
+                    stack.pop();
+                } else {
+                    stack.push(component);
+                }
+            }
+            other => stack.push(other),
+        }
+    }
+    stack.iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lexically_normalize_collapses_dotdot() {
+        assert_eq!(
+            lexically_normalize(Path::new("/repo/a/../b")),
+            PathBuf::from("/repo/b")
+        );
+    }
+
+    #[test]
+    fn test_lexically_normalize_escapes_root() {
+        assert_eq!(
+            lexically_normalize(Path::new("/repo/../../etc/passwd")),
+            PathBuf::from("/../etc/passwd")
+        );
+    }
+
+    #[test]
+    fn test_lexically_normalize_no_dotdot_is_unchanged() {
+        assert_eq!(
+            lexically_normalize(Path::new("/repo/src/main.rs")),
+            PathBuf::from("/repo/src/main.rs")
+        );
+    }
+}
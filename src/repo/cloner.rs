@@ -4,10 +4,12 @@
 //! using the git2 library.
 
 use anyhow::{Context, Result};
-use git2::{FetchOptions, Progress, RemoteCallbacks, Repository};
-use indicatif::{ProgressBar, ProgressStyle};
+use git2::{Cred, CredentialType, FetchOptions, Oid, Progress, RemoteCallbacks, Repository};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tempfile::TempDir;
 use tracing::{debug, info, warn};
 
@@ -21,6 +23,9 @@ pub struct CloneResult {
     /// Temporary directory handle (keeps the directory alive).
     /// If None, the repository was cloned to a persistent location.
     pub temp_dir: Option<TempDir>,
+    /// Full SHA of the commit actually checked out, if `CloneOptions::reference`
+    /// was resolved (so reports can record exactly what was audited).
+    pub resolved_commit: Option<String>,
 }
 
 impl CloneResult {
@@ -54,6 +59,25 @@ pub struct CloneOptions {
     pub show_progress: bool,
     /// Target directory (None for temp directory).
     pub target_dir: Option<PathBuf>,
+    /// Path to an SSH private key, for `git@host:owner/repo` style URLs.
+    pub ssh_private_key: Option<PathBuf>,
+    /// Passphrase for the SSH private key, if it is encrypted.
+    pub ssh_passphrase: Option<String>,
+    /// Personal access token for HTTPS authentication.
+    pub https_token: Option<String>,
+    /// Username to pair with `https_token` (defaults to "x-access-token").
+    pub username: Option<String>,
+    /// Number of concurrent clones for `clone_repositories` (ignored by `clone_repository`).
+    pub concurrency: usize,
+    /// Pin the audit to a specific commit SHA, tag, or `refs/...` (overrides `branch`).
+    ///
+    /// Accepts anything `git2::Repository::revparse_single` understands. Because
+    /// the default shallow clone may not contain the history this resolves to,
+    /// a full clone is used when a reference is requested.
+    pub reference: Option<String>,
+    /// Recursively initialize and update submodules after cloning, so audits
+    /// cover vendored dependencies instead of leaving empty directories.
+    pub recurse_submodules: bool,
 }
 
 impl Default for CloneOptions {
@@ -63,25 +87,81 @@ impl Default for CloneOptions {
             depth: Some(1), // Shallow clone by default for speed
             show_progress: true,
             target_dir: None,
+            ssh_private_key: None,
+            ssh_passphrase: None,
+            https_token: None,
+            username: None,
+            concurrency: 4,
+            reference: None,
+            recurse_submodules: false,
         }
     }
 }
 
+/// Build a git2 credentials callback from the authentication fields on `CloneOptions`.
+///
+/// git2 retries the callback once per credential type it's willing to accept
+/// (communicated via `allowed_types`), so we must only offer a credential that
+/// matches what was requested and fall back to `Cred::default()` otherwise.
+fn build_credentials_callback(
+    options: &CloneOptions,
+) -> impl Fn(&str, Option<&str>, CredentialType) -> std::result::Result<Cred, git2::Error> + 'static
+{
+    let ssh_private_key = options.ssh_private_key.clone();
+    let ssh_passphrase = options.ssh_passphrase.clone();
+    let https_token = options.https_token.clone();
+    let username = options.username.clone();
+
+    move |_url, username_from_url, allowed_types| {
+        let user = username
+            .as_deref()
+            .or(username_from_url)
+            .unwrap_or("git");
+
+        if allowed_types.contains(CredentialType::SSH_KEY) {
+            if let Some(ref key_path) = ssh_private_key {
+                return Cred::ssh_key(user, None, key_path, ssh_passphrase.as_deref());
+            }
+            return Cred::ssh_key_from_agent(user);
+        }
+
+        if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(ref token) = https_token {
+                return Cred::userpass_plaintext(username.as_deref().unwrap_or("x-access-token"), token);
+            }
+        }
+
+        if allowed_types.contains(CredentialType::USERNAME) {
+            return Cred::username(user);
+        }
+
+        Cred::default()
+    }
+}
+
 /// Clone a repository from a URL.
 pub fn clone_repository(url: &str, options: CloneOptions) -> Result<CloneResult> {
     info!("Cloning repository: {}", url);
 
     // Determine the target path
-    let (path, temp_dir) = if let Some(target) = options.target_dir {
+    let (path, temp_dir) = if let Some(target) = options.target_dir.clone() {
         if target.exists() {
             debug!("Target directory already exists: {}", target.display());
             // Try to open existing repository
             if let Ok(repo) = Repository::open(&target) {
                 info!("Using existing repository at: {}", target.display());
+                let resolved_commit = match options.reference {
+                    Some(ref reference) => Some(checkout_reference(&repo, reference)?),
+                    None => None,
+                };
+                if options.recurse_submodules {
+                    checkout_submodules_recursive(&repo, &options, &mut std::collections::HashSet::new())?;
+                }
                 return Ok(CloneResult {
                     repo,
                     path: target,
                     temp_dir: None,
+                    resolved_commit,
                 });
             }
         }
@@ -94,23 +174,58 @@ pub fn clone_repository(url: &str, options: CloneOptions) -> Result<CloneResult>
 
     debug!("Clone target: {}", path.display());
 
-    // Set up progress callback
+    // Set up a standalone progress bar (not part of a MultiProgress).
     let progress_bar = if options.show_progress {
-        let pb = ProgressBar::new(0);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template(
-                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
-                )
-                .unwrap()
-                .progress_chars("#>-"),
-        );
-        Some(Arc::new(pb))
+        Some(new_progress_bar())
     } else {
         None
     };
 
-    let pb_clone = progress_bar.clone();
+    let repo = clone_with_bar(url, &options, &path, progress_bar.as_ref())?;
+
+    if let Some(pb) = progress_bar {
+        pb.finish_with_message("Clone complete");
+    }
+
+    info!("Successfully cloned repository to: {}", path.display());
+
+    let resolved_commit = match options.reference {
+        Some(ref reference) => Some(checkout_reference(&repo, reference)?),
+        None => None,
+    };
+
+    if options.recurse_submodules {
+        checkout_submodules_recursive(&repo, &options, &mut std::collections::HashSet::new())?;
+    }
+
+    Ok(CloneResult {
+        repo,
+        path,
+        temp_dir,
+        resolved_commit,
+    })
+}
+
+/// Build the standard clone progress bar style.
+fn new_progress_bar() -> ProgressBar {
+    let pb = ProgressBar::new(0);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb
+}
+
+/// Perform the actual `git2` clone, reporting transfer progress on `progress_bar` if given.
+fn clone_with_bar(
+    url: &str,
+    options: &CloneOptions,
+    path: &Path,
+    progress_bar: Option<&ProgressBar>,
+) -> Result<Repository> {
+    let pb_clone = progress_bar.cloned();
     let mut callbacks = RemoteCallbacks::new();
 
     callbacks.transfer_progress(move |progress: Progress<'_>| {
@@ -121,12 +236,18 @@ pub fn clone_repository(url: &str, options: CloneOptions) -> Result<CloneResult>
         true
     });
 
+    callbacks.credentials(build_credentials_callback(options));
+
     // Set up fetch options
     let mut fetch_opts = FetchOptions::new();
     fetch_opts.remote_callbacks(callbacks);
 
-    if let Some(depth) = options.depth {
-        fetch_opts.depth(depth);
+    // A shallow clone usually lacks the historical objects an arbitrary
+    // commit/tag reference needs, so fetch full history whenever one is pinned.
+    if options.reference.is_none() {
+        if let Some(depth) = options.depth {
+            fetch_opts.depth(depth);
+        }
     }
 
     // Build the repository
@@ -138,20 +259,192 @@ pub fn clone_repository(url: &str, options: CloneOptions) -> Result<CloneResult>
     }
 
     // Perform the clone
-    let repo = builder
-        .clone(url, &path)
-        .with_context(|| format!("Failed to clone repository: {}", url))?;
+    builder
+        .clone(url, path)
+        .with_context(|| format!("Failed to clone repository: {}", url))
+}
+
+/// Resolve `reference` (a branch, tag, short/full SHA, or `refs/...`) against
+/// `repo`, detach HEAD onto it, and force-checkout the working tree.
+///
+/// Returns the full SHA of the resolved commit so callers can record exactly
+/// what was audited.
+fn checkout_reference(repo: &Repository, reference: &str) -> Result<String> {
+    let object = repo
+        .revparse_single(reference)
+        .with_context(|| format!("Failed to resolve reference: {}", reference))?;
+    let commit = object
+        .peel_to_commit()
+        .with_context(|| format!("Reference does not point to a commit: {}", reference))?;
+
+    repo.set_head_detached(commit.id())
+        .with_context(|| format!("Failed to detach HEAD at: {}", reference))?;
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_head(Some(&mut checkout))
+        .with_context(|| format!("Failed to checkout: {}", reference))?;
+
+    Ok(commit.id().to_string())
+}
+
+/// Recursively initialize and update every submodule in `repo`, so vendored
+/// dependencies are present for the audit instead of left as empty directories.
+///
+/// `visited` tracks submodule URLs already updated in this clone to guard
+/// against cycles (a submodule that, directly or transitively, points back
+/// at an ancestor repository).
+fn checkout_submodules_recursive(
+    repo: &Repository,
+    options: &CloneOptions,
+    visited: &mut std::collections::HashSet<String>,
+) -> Result<()> {
+    let submodule_names: Vec<String> = repo
+        .submodules()
+        .context("Failed to list submodules")?
+        .iter()
+        .filter_map(|s| s.name().map(String::from))
+        .collect();
+
+    for name in submodule_names {
+        let mut submodule = match repo.find_submodule(&name) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("Failed to find submodule {}: {}", name, e);
+                continue;
+            }
+        };
+
+        let url = match submodule.url() {
+            Some(u) => u.to_string(),
+            None => {
+                warn!("Submodule {} has no URL, skipping", name);
+                continue;
+            }
+        };
+
+        if !visited.insert(url.clone()) {
+            debug!("Skipping already-visited submodule URL: {}", url);
+            continue;
+        }
+
+        info!("Updating submodule {} ({})", name, url);
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(build_credentials_callback(options));
+
+        let mut fetch_opts = FetchOptions::new();
+        fetch_opts.remote_callbacks(callbacks);
+        if let Some(depth) = options.depth {
+            fetch_opts.depth(depth);
+        }
+
+        let mut update_opts = git2::SubmoduleUpdateOptions::new();
+        update_opts.fetch(fetch_opts);
+
+        if let Err(e) = submodule.update(true, Some(&mut update_opts)) {
+            warn!("Failed to update submodule {}: {}", name, e);
+            continue;
+        }
+
+        if let Ok(sub_repo) = submodule.open() {
+            checkout_submodules_recursive(&sub_repo, options, visited)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Clone multiple repositories concurrently, rendering one shared `MultiProgress`
+/// with a child bar per in-flight clone.
+///
+/// Work is distributed across `options.concurrency` worker threads (each doing a
+/// full clone, since `git2::Repository` is not safely shared across threads), and
+/// results are returned in the same order as `urls` so callers can correlate
+/// failures with their source URL.
+pub fn clone_repositories(urls: &[String], options: CloneOptions) -> Vec<Result<CloneResult>> {
+    let concurrency = options.concurrency.max(1);
+    let multi_progress = Arc::new(MultiProgress::new());
+
+    let queue: Mutex<VecDeque<(usize, String)>> = Mutex::new(
+        urls.iter().cloned().enumerate().collect(),
+    );
+    let results: Mutex<Vec<Option<Result<CloneResult>>>> =
+        Mutex::new((0..urls.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.min(urls.len().max(1)) {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().pop_front();
+                let Some((index, url)) = next else {
+                    break;
+                };
+
+                let progress_bar = if options.show_progress {
+                    Some(multi_progress.add(new_progress_bar()))
+                } else {
+                    None
+                };
+
+                let result = clone_one(&url, options.clone(), index, progress_bar);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every queued URL produces a result"))
+        .collect()
+}
+
+/// Clone a single repository for use by `clone_repositories`' worker threads,
+/// driving an externally-supplied progress bar instead of creating its own.
+///
+/// `index` (the repo's position in the original `urls` slice) is folded into
+/// the target subdirectory name alongside the parsed repo name, so that
+/// concurrent workers sharing one `options.target_dir` each get a distinct
+/// directory instead of racing to clone into the same path.
+fn clone_one(
+    url: &str,
+    options: CloneOptions,
+    index: usize,
+    progress_bar: Option<ProgressBar>,
+) -> Result<CloneResult> {
+    let (path, temp_dir) = if let Some(ref target) = options.target_dir {
+        let dir_name = match parse_repo_url(url) {
+            Some(location) => format!("{}-{}-{}", index, location.owner.replace('/', "_"), location.repo),
+            None => format!("{}-repo", index),
+        };
+        (target.join(dir_name), None)
+    } else {
+        let temp = TempDir::new().context("Failed to create temporary directory")?;
+        let path = temp.path().to_path_buf();
+        (path, Some(temp))
+    };
+
+    let repo = clone_with_bar(url, &options, &path, progress_bar.as_ref())?;
 
     if let Some(pb) = progress_bar {
         pb.finish_with_message("Clone complete");
     }
 
-    info!("Successfully cloned repository to: {}", path.display());
+    let resolved_commit = match options.reference {
+        Some(ref reference) => Some(checkout_reference(&repo, reference)?),
+        None => None,
+    };
+
+    if options.recurse_submodules {
+        checkout_submodules_recursive(&repo, &options, &mut std::collections::HashSet::new())?;
+    }
 
     Ok(CloneResult {
         repo,
         path,
         temp_dir,
+        resolved_commit,
     })
 }
 
@@ -171,32 +464,70 @@ pub fn open_local_repository(path: &Path) -> Result<CloneResult> {
         repo,
         path: path.to_path_buf(),
         temp_dir: None,
+        resolved_commit: None,
     })
 }
 
-/// Parse a GitHub URL to extract owner and repo name.
-#[allow(dead_code)] // Utility for URL parsing
-pub fn parse_github_url(url: &str) -> Option<(String, String)> {
-    // Handle various GitHub URL formats
+/// Location of a repository on a Git hosting service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoLocation {
+    /// Hostname of the Git service (e.g. "github.com", "gitlab.com").
+    pub host: String,
+    /// Owner or namespace path (e.g. "owner" or "owner/subgroup" on GitLab).
+    pub owner: String,
+    /// Repository name.
+    pub repo: String,
+}
+
+/// Parse a repository URL from any Git host into its component parts.
+///
+/// Supports `https://host/owner/repo(.git)`, `git@host:owner/repo(.git)`,
+/// and `ssh://git@host:port/owner/repo`. GitLab-style nested subgroups
+/// (`owner/subgroup/repo`) are preserved in `owner`, with only the final
+/// path segment treated as the repository name.
+pub fn parse_repo_url(url: &str) -> Option<RepoLocation> {
     let url = url.trim_end_matches(".git");
 
-    // https://github.com/owner/repo
-    if let Some(rest) = url.strip_prefix("https://github.com/") {
-        let parts: Vec<&str> = rest.split('/').collect();
-        if parts.len() >= 2 {
-            return Some((parts[0].to_string(), parts[1].to_string()));
-        }
-    }
+    let (host, path) = if let Some(rest) = url.strip_prefix("https://") {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("ssh://git@") {
+        // ssh://git@host:port/owner/repo
+        let (host_and_port, path) = rest.split_once('/')?;
+        let host = host_and_port.split(':').next().unwrap_or(host_and_port);
+        (host, path)
+    } else if let Some(rest) = url.strip_prefix("git@") {
+        // git@host:owner/repo
+        rest.split_once(':')?
+    } else {
+        return None;
+    };
 
-    // git@github.com:owner/repo
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let parts: Vec<&str> = rest.split('/').collect();
-        if parts.len() >= 2 {
-            return Some((parts[0].to_string(), parts[1].to_string()));
-        }
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.len() < 2 {
+        return None;
     }
 
-    None
+    let (repo, owner_segments) = segments.split_last()?;
+    Some(RepoLocation {
+        host: host.to_string(),
+        owner: owner_segments.join("/"),
+        repo: repo.to_string(),
+    })
+}
+
+/// Parse a GitHub URL to extract owner and repo name.
+///
+/// Thin wrapper around [`parse_repo_url`] that only succeeds for `github.com`.
+#[allow(dead_code)] // Utility for URL parsing
+pub fn parse_github_url(url: &str) -> Option<(String, String)> {
+    let location = parse_repo_url(url)?;
+    if location.host == "github.com" {
+        Some((location.owner, location.repo))
+    } else {
+        None
+    }
 }
 
 /// Get the current branch name of a repository.
@@ -234,6 +565,199 @@ pub fn cleanup_repository(clone_result: CloneResult) -> Result<()> {
     Ok(())
 }
 
+/// Outcome of a single `sync_repository` check against "origin".
+#[derive(Debug, Clone)]
+pub struct SyncEvent {
+    /// Whether new commits were fetched and fast-forwarded onto.
+    pub changed: bool,
+    /// HEAD commit before this sync.
+    pub old_commit: String,
+    /// HEAD commit after this sync (equals `old_commit` if nothing changed).
+    pub new_commit: String,
+}
+
+/// Fetch "origin" for an already-cloned repository and fast-forward onto any
+/// new commits on the tracked remote branch.
+///
+/// Compares the remote-tracking ref's OID against the local HEAD OID; when
+/// they differ, checks out the new commit and returns the old/new pair so
+/// callers can trigger an incremental re-audit via [`changed_files`].
+pub fn sync_repository(clone: &CloneResult, options: &CloneOptions) -> Result<SyncEvent> {
+    let repo = &clone.repo;
+
+    let old_commit = repo
+        .head()
+        .context("Repository has no HEAD")?
+        .peel_to_commit()
+        .context("HEAD does not point to a commit")?
+        .id();
+
+    let mut remote = repo
+        .find_remote("origin")
+        .context("No 'origin' remote configured")?;
+
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(build_credentials_callback(options));
+
+    let mut fetch_opts = FetchOptions::new();
+    fetch_opts.remote_callbacks(callbacks);
+
+    let branch_name = get_current_branch(repo).unwrap_or_else(|| "HEAD".to_string());
+    remote
+        .fetch(&[branch_name.as_str()], Some(&mut fetch_opts), None)
+        .context("Failed to fetch from origin")?;
+
+    let remote_ref_name = format!("refs/remotes/origin/{}", branch_name);
+    let new_commit = match repo.refname_to_id(&remote_ref_name) {
+        Ok(oid) => oid,
+        Err(_) => old_commit, // Nothing to compare against; treat as unchanged.
+    };
+
+    if new_commit == old_commit {
+        return Ok(SyncEvent {
+            changed: false,
+            old_commit: old_commit.to_string(),
+            new_commit: new_commit.to_string(),
+        });
+    }
+
+    info!(
+        "New commits detected: {} -> {}",
+        old_commit, new_commit
+    );
+
+    repo.set_head_detached(new_commit)
+        .context("Failed to move HEAD to new commit")?;
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_head(Some(&mut checkout))
+        .context("Failed to fast-forward working tree")?;
+
+    Ok(SyncEvent {
+        changed: true,
+        old_commit: old_commit.to_string(),
+        new_commit: new_commit.to_string(),
+    })
+}
+
+/// Repeatedly call [`sync_repository`] at `interval`, invoking `on_change` with
+/// each [`SyncEvent`] where new commits were found so the caller can trigger an
+/// incremental re-audit. Runs until `on_change` returns `false` or an error occurs.
+pub fn watch_repository(
+    clone: &CloneResult,
+    options: &CloneOptions,
+    interval: Duration,
+    mut on_change: impl FnMut(&SyncEvent) -> bool,
+) -> Result<()> {
+    loop {
+        let event = sync_repository(clone, options)?;
+        if event.changed && !on_change(&event) {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// List paths that changed between two commits, so re-analysis can be scoped
+/// to only the modified files instead of re-scanning the whole tree.
+pub fn changed_files(repo: &Repository, old: &str, new: &str) -> Result<Vec<String>> {
+    let old_oid = Oid::from_str(old).with_context(|| format!("Invalid commit SHA: {}", old))?;
+    let new_oid = Oid::from_str(new).with_context(|| format!("Invalid commit SHA: {}", new))?;
+
+    let old_tree = repo.find_commit(old_oid)?.tree()?;
+    let new_tree = repo.find_commit(new_oid)?.tree()?;
+
+    let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+
+    let mut paths = Vec::new();
+    diff.foreach(
+        &mut |delta, _| {
+            if let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) {
+                paths.push(path.to_string_lossy().to_string());
+            }
+            true
+        },
+        None,
+        None,
+        None,
+    )?;
+
+    Ok(paths)
+}
+
+/// List paths that differ between `since_ref` (a branch, tag, or commit SHA)
+/// and the current HEAD, for `--since`/`--changed-only` scoped audits. Uses
+/// the same committed tree-to-tree diff as [`changed_files`], so uncommitted
+/// working-directory edits aren't picked up.
+pub fn changed_files_since(repo: &Repository, since_ref: &str) -> Result<Vec<String>> {
+    let since_commit = repo
+        .revparse_single(since_ref)
+        .with_context(|| format!("Failed to resolve git ref: {}", since_ref))?
+        .peel_to_commit()
+        .with_context(|| format!("Git ref does not point to a commit: {}", since_ref))?;
+
+    let head_commit = repo
+        .head()
+        .context("Repository has no HEAD (empty or detached?)")?
+        .peel_to_commit()
+        .context("Failed to resolve HEAD to a commit")?;
+
+    changed_files(
+        repo,
+        &since_commit.id().to_string(),
+        &head_commit.id().to_string(),
+    )
+}
+
+/// Resolve the merge-base between HEAD and the repository's default branch,
+/// for `--changed-only`'s "diff against where this branch forked" shorthand.
+/// Tries, in order: `origin/HEAD`'s symbolic target, then `origin/main`,
+/// `origin/master`, `main`, `master`.
+pub fn default_branch_merge_base(repo: &Repository) -> Result<String> {
+    let head_oid = repo
+        .head()
+        .context("Repository has no HEAD (empty or detached?)")?
+        .peel_to_commit()
+        .context("Failed to resolve HEAD to a commit")?
+        .id();
+
+    let default_branch_oid = resolve_default_branch_oid(repo)
+        .context("Could not determine the default branch to diff --changed-only against")?;
+
+    let merge_base = repo
+        .merge_base(head_oid, default_branch_oid)
+        .context("Failed to compute merge base with the default branch")?;
+
+    Ok(merge_base.to_string())
+}
+
+/// Find the default branch's commit, trying the most reliable source first.
+fn resolve_default_branch_oid(repo: &Repository) -> Result<Oid> {
+    if let Ok(reference) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if let Ok(resolved) = reference.resolve() {
+            if let Some(oid) = resolved.target() {
+                return Ok(oid);
+            }
+        }
+    }
+
+    for candidate in [
+        "refs/remotes/origin/main",
+        "refs/remotes/origin/master",
+        "refs/heads/main",
+        "refs/heads/master",
+    ] {
+        if let Ok(reference) = repo.find_reference(candidate) {
+            if let Some(oid) = reference.target() {
+                return Ok(oid);
+            }
+        }
+    }
+
+    anyhow::bail!("No default branch ref found (tried origin/HEAD, origin/main, origin/master, main, master)")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,11 +786,128 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_parse_repo_url_gitlab_https() {
+        let location = parse_repo_url("https://gitlab.com/owner/repo.git").unwrap();
+        assert_eq!(location.host, "gitlab.com");
+        assert_eq!(location.owner, "owner");
+        assert_eq!(location.repo, "repo");
+    }
+
+    #[test]
+    fn test_parse_repo_url_gitlab_nested_subgroup() {
+        let location = parse_repo_url("https://gitlab.com/owner/subgroup/repo").unwrap();
+        assert_eq!(location.host, "gitlab.com");
+        assert_eq!(location.owner, "owner/subgroup");
+        assert_eq!(location.repo, "repo");
+    }
+
+    #[test]
+    fn test_parse_repo_url_ssh_shorthand() {
+        let location = parse_repo_url("git@bitbucket.org:owner/repo.git").unwrap();
+        assert_eq!(location.host, "bitbucket.org");
+        assert_eq!(location.owner, "owner");
+        assert_eq!(location.repo, "repo");
+    }
+
+    #[test]
+    fn test_parse_repo_url_ssh_with_port() {
+        let location = parse_repo_url("ssh://git@git.example.com:2222/owner/repo").unwrap();
+        assert_eq!(location.host, "git.example.com");
+        assert_eq!(location.owner, "owner");
+        assert_eq!(location.repo, "repo");
+    }
+
     #[test]
     fn test_clone_options_default() {
         let opts = CloneOptions::default();
         assert!(opts.branch.is_none());
         assert_eq!(opts.depth, Some(1));
         assert!(opts.show_progress);
+        assert!(opts.ssh_private_key.is_none());
+        assert!(opts.https_token.is_none());
+        assert_eq!(opts.concurrency, 4);
+        assert!(opts.reference.is_none());
+        assert!(!opts.recurse_submodules);
+    }
+
+    #[test]
+    fn test_sync_event_unchanged() {
+        let event = SyncEvent {
+            changed: false,
+            old_commit: "abc123".to_string(),
+            new_commit: "abc123".to_string(),
+        };
+        assert!(!event.changed);
+        assert_eq!(event.old_commit, event.new_commit);
+    }
+
+    /// Build a throwaway repo with a `base.txt` commit on `main`, then a
+    /// second commit adding `changed.rs` on top, for diff-related tests.
+    fn repo_with_two_commits() -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo =
+            Repository::init_opts(dir.path(), git2::RepositoryInitOptions::new().initial_head("main"))
+                .unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+
+        std::fs::write(dir.path().join("base.txt"), "base\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("base.txt")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "base", &tree, &[])
+            .unwrap();
+
+        std::fs::write(dir.path().join("changed.rs"), "fn main() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("changed.rs")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "add changed.rs",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_changed_files_since_resolves_branch_name() {
+        let (_dir, repo) = repo_with_two_commits();
+
+        // Point a `base` branch ref at the first commit so a branch name
+        // given to `--since` resolves the same way a real base branch would.
+        let base_commit = repo
+            .find_commit(repo.head().unwrap().peel_to_commit().unwrap().parent_id(0).unwrap())
+            .unwrap();
+        repo.branch("base", &base_commit, false).unwrap();
+
+        let changed = changed_files_since(&repo, "base").unwrap();
+        assert_eq!(changed, vec!["changed.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_changed_files_since_unknown_ref_errors() {
+        let (_dir, repo) = repo_with_two_commits();
+        assert!(changed_files_since(&repo, "does-not-exist").is_err());
+    }
+
+    #[test]
+    fn test_resolve_default_branch_oid_falls_back_to_local_main() {
+        let (_dir, repo) = repo_with_two_commits();
+        // No `origin` remote exists in this throwaway repo, so resolution
+        // should fall back to the local `refs/heads/main` branch.
+        let oid = resolve_default_branch_oid(&repo).unwrap();
+        let head_oid = repo.head().unwrap().peel_to_commit().unwrap().id();
+        assert_eq!(oid, head_oid);
     }
 }
@@ -0,0 +1,9 @@
+//! Git repository cloning and syncing.
+//!
+//! This module handles cloning repositories (single and batch), pinning to
+//! a ref, submodule checkout, and polling an already-cloned repo's origin
+//! for incremental re-audits.
+
+pub mod cloner;
+
+pub use cloner::*;
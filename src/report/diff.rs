@@ -0,0 +1,206 @@
+//! Baseline-diff mode: classifies issues between two audits as New, Fixed,
+//! or Persisting, so a re-audit of an evolving repo can report only what
+//! changed instead of a full, noisy snapshot.
+//!
+//! Issues are matched on a stable key of `(file_path, category, title)`
+//! plus `start_line`, tolerating small line drift (refactors shifting line
+//! numbers around): two `start_line`s within `LINE_TOLERANCE` lines of each
+//! other count as a match rather than requiring an exact one.
+
+use crate::models::{Issue, Report};
+use anyhow::Result;
+
+/// Matching two issues' `start_line`s within this many lines of each other
+/// still counts as the same issue.
+const LINE_TOLERANCE: usize = 3;
+
+/// The result of classifying `current`'s issues against `previous`'s.
+#[derive(Debug, Clone, Default)]
+pub struct IssueDiff {
+    /// Present in `current` but not `previous`.
+    pub new_issues: Vec<Issue>,
+    /// Present in `previous` but not `current`.
+    pub fixed_issues: Vec<Issue>,
+    /// Present in both.
+    pub persisting_issues: Vec<Issue>,
+}
+
+/// A stable match key: everything but the line number, which is matched
+/// with tolerance separately.
+fn match_key(issue: &Issue) -> (&str, &str, &str) {
+    (&issue.file_path, &issue.category, &issue.title)
+}
+
+/// Whether two issues are the same finding: same `match_key`, and
+/// `start_line`s within `LINE_TOLERANCE` of each other.
+fn same_issue(a: &Issue, b: &Issue) -> bool {
+    match_key(a) == match_key(b) && a.start_line.abs_diff(b.start_line) <= LINE_TOLERANCE
+}
+
+/// Classifies every issue in `previous` and `current` as New, Fixed, or
+/// Persisting.
+pub fn diff_reports(previous: &Report, current: &Report) -> IssueDiff {
+    let previous_issues: Vec<&Issue> = previous.files.iter().flat_map(|f| &f.issues).collect();
+    let current_issues: Vec<&Issue> = current.files.iter().flat_map(|f| &f.issues).collect();
+
+    let mut diff = IssueDiff::default();
+
+    for issue in &current_issues {
+        if previous_issues.iter().any(|prev| same_issue(prev, issue)) {
+            diff.persisting_issues.push((*issue).clone());
+        } else {
+            diff.new_issues.push((*issue).clone());
+        }
+    }
+
+    for issue in &previous_issues {
+        if !current_issues.iter().any(|cur| same_issue(issue, cur)) {
+            diff.fixed_issues.push((*issue).clone());
+        }
+    }
+
+    diff
+}
+
+/// Renders a Markdown baseline-diff report: a delta summary line, then a
+/// New/Fixed/Persisting section each listing the matching issues.
+pub fn generate_diff_report(previous: &Report, current: &Report) -> String {
+    let diff = diff_reports(previous, current);
+    let mut output = String::new();
+
+    output.push_str("# YoAuditor Diff Report\n\n");
+    output.push_str(&format!(
+        "**Delta:** +{} new, \u{2212}{} fixed, {} persisting\n\n",
+        diff.new_issues.len(),
+        diff.fixed_issues.len(),
+        diff.persisting_issues.len()
+    ));
+
+    output.push_str(&render_issue_list("New Issues", &diff.new_issues));
+    output.push_str(&render_issue_list("Fixed Issues", &diff.fixed_issues));
+    output.push_str(&render_issue_list("Persisting Issues", &diff.persisting_issues));
+
+    output
+}
+
+/// Renders one of the diff's three sections as a Markdown list.
+fn render_issue_list(heading: &str, issues: &[Issue]) -> String {
+    let mut section = format!("## {} ({})\n\n", heading, issues.len());
+
+    if issues.is_empty() {
+        section.push_str("_None._\n\n");
+        return section;
+    }
+
+    for issue in issues {
+        section.push_str(&format!(
+            "- **{}** `{}` {} - {} ({})\n",
+            issue.severity, issue.file_path, issue.line_range(), issue.title, issue.category
+        ));
+    }
+    section.push('\n');
+
+    section
+}
+
+/// Renders the same classification as a JSON object with `new`, `fixed`,
+/// and `persisting` arrays of `Issue`.
+pub fn generate_diff_json_report(previous: &Report, current: &Report) -> Result<String> {
+    let diff = diff_reports(previous, current);
+
+    let value = serde_json::json!({
+        "new": diff.new_issues,
+        "fixed": diff.fixed_issues,
+        "persisting": diff.persisting_issues,
+    });
+
+    serde_json::to_string_pretty(&value).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AnalyzedFile, IssueSummary, ReportMetadata, Severity};
+    use chrono::Utc;
+
+    fn make_issue(file_path: &str, title: &str, start_line: usize) -> Issue {
+        Issue {
+            file_path: file_path.to_string(),
+            start_line,
+            end_line: None,
+            severity: Severity::High,
+            category: "Bug".to_string(),
+            title: title.to_string(),
+            description: "desc".to_string(),
+            suggestion: "fix it".to_string(),
+            code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        }
+    }
+
+    fn make_report(issues: Vec<Issue>) -> Report {
+        Report {
+            metadata: ReportMetadata {
+                repo_url: "local".to_string(),
+                analysis_date: Utc::now(),
+                model_used: "test-model".to_string(),
+                files_analyzed: 1,
+                files_failed: 0,
+                total_issues: issues.len(),
+                duration_seconds: 1.0,
+                scoped_to_diff: None,
+            },
+            project_overview: String::new(),
+            files: vec![AnalyzedFile {
+                path: "src/lib.rs".to_string(),
+                language: "Rust".to_string(),
+                line_count: 10,
+                issues,
+                analysis_successful: true,
+                error: None,
+            }],
+            summary: IssueSummary::default(),
+            recommendations: vec![],
+            code_stats: crate::stats::CodeStats::default(),
+            logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_classifies_new_fixed_persisting() {
+        let previous = make_report(vec![
+            make_issue("src/lib.rs", "Unchecked unwrap", 10),
+            make_issue("src/lib.rs", "Dead code", 50),
+        ]);
+        let current = make_report(vec![
+            make_issue("src/lib.rs", "Unchecked unwrap", 11), // shifted by 1, still a match
+            make_issue("src/lib.rs", "SQL Injection", 20),
+        ]);
+
+        let diff = diff_reports(&previous, &current);
+
+        assert_eq!(diff.persisting_issues.len(), 1);
+        assert_eq!(diff.persisting_issues[0].title, "Unchecked unwrap");
+        assert_eq!(diff.new_issues.len(), 1);
+        assert_eq!(diff.new_issues[0].title, "SQL Injection");
+        assert_eq!(diff.fixed_issues.len(), 1);
+        assert_eq!(diff.fixed_issues[0].title, "Dead code");
+    }
+
+    #[test]
+    fn test_generate_diff_report_includes_delta_summary() {
+        let previous = make_report(vec![make_issue("src/lib.rs", "Dead code", 50)]);
+        let current = make_report(vec![make_issue("src/lib.rs", "SQL Injection", 20)]);
+
+        let report = generate_diff_report(&previous, &current);
+
+        assert!(report.contains("+1 new"));
+        assert!(report.contains("1 fixed"));
+        assert!(report.contains("## New Issues (1)"));
+        assert!(report.contains("## Fixed Issues (1)"));
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::analysis::{language_distribution, most_problematic_files};
 use crate::models::{AnalyzedFile, Issue, IssueSummary, Report, ReportMetadata, Severity};
+use crate::stats::CodeStats;
 use anyhow::Result;
 use std::io::Write;
 use std::path::Path;
@@ -26,7 +27,11 @@ pub fn generate_markdown_report(report: &Report) -> String {
     output.push_str(&generate_overview_section(&report.project_overview));
 
     // Summary section
-    output.push_str(&generate_summary_section(&report.summary, &report.files));
+    output.push_str(&generate_summary_section(
+        &report.summary,
+        &report.files,
+        &report.code_stats,
+    ));
 
     // Issues by file
     output.push_str(&generate_issues_section(&report.files));
@@ -34,6 +39,9 @@ pub fn generate_markdown_report(report: &Report) -> String {
     // Recommendations
     output.push_str(&generate_recommendations_section(&report.recommendations));
 
+    // Run log (collapsed, since most readers only care when debugging)
+    output.push_str(&generate_run_log_section(&report.logs));
+
     // Footer
     output.push_str(&generate_footer());
 
@@ -63,6 +71,13 @@ fn generate_metadata_section(metadata: &ReportMetadata) -> String {
         "- **Analysis Duration:** {:.1}s\n",
         metadata.duration_seconds
     ));
+    if let Some(ref base_ref) = metadata.scoped_to_diff {
+        section.push_str(&format!(
+            "- **Scope:** ⚠️ Limited to files changed since `{}` — the issue count \
+             below does not reflect the whole repository.\n",
+            base_ref
+        ));
+    }
     section.push_str("\n");
 
     section
@@ -94,6 +109,10 @@ fn generate_table_of_contents(report: &Report) -> String {
         toc.push_str("- [Recommendations](#recommendations)\n");
     }
 
+    if !report.logs.is_empty() {
+        toc.push_str("- [Run Log](#run-log)\n");
+    }
+
     toc.push_str("\n");
 
     toc
@@ -115,7 +134,11 @@ fn generate_overview_section(overview: &str) -> String {
 }
 
 /// Generate the summary section.
-fn generate_summary_section(summary: &IssueSummary, files: &[AnalyzedFile]) -> String {
+fn generate_summary_section(
+    summary: &IssueSummary,
+    files: &[AnalyzedFile],
+    code_stats: &CodeStats,
+) -> String {
     let mut section = String::new();
 
     section.push_str("## Summary\n\n");
@@ -179,6 +202,34 @@ fn generate_summary_section(summary: &IssueSummary, files: &[AnalyzedFile]) -> S
         section.push_str("\n");
     }
 
+    // Code statistics: a lightweight tokei-style breakdown of where the
+    // auditable surface area actually is.
+    if !code_stats.by_language.is_empty() {
+        section.push_str("### Code Statistics\n\n");
+        section.push_str("| Language | Code | Comments | Blank | % of Code |\n");
+        section.push_str("|:---|:---:|:---:|:---:|:---:|\n");
+
+        let total_code = code_stats.total_code_lines();
+        let mut langs: Vec<_> = code_stats.by_language.iter().collect();
+        langs.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.code_lines));
+
+        for (lang, stats) in langs {
+            let pct = if total_code == 0 {
+                0.0
+            } else {
+                100.0 * stats.code_lines as f64 / total_code as f64
+            };
+            section.push_str(&format!(
+                "| {} | {} | {} | {} | {:.1}% |\n",
+                lang, stats.code_lines, stats.comment_lines, stats.blank_lines, pct
+            ));
+        }
+        section.push_str(&format!(
+            "\n*Comment-to-code ratio: {:.2}*\n\n",
+            code_stats.comment_to_code_ratio()
+        ));
+    }
+
     section
 }
 
@@ -227,14 +278,20 @@ fn generate_file_issues_section(file: &AnalyzedFile) -> String {
     });
 
     for issue in &issues {
-        section.push_str(&generate_issue_block(issue));
+        section.push_str(&generate_issue_block(issue, &file.language));
     }
 
     section
 }
 
 /// Generate a single issue block.
-fn generate_issue_block(issue: &Issue) -> String {
+///
+/// `language` (the owning file's detected language, e.g. `"Rust"`) is used
+/// to tag the code snippet's fence so Markdown renderers that support
+/// syntax highlighting (e.g. GitHub) can colorize it. `generate_html_report`
+/// carries the tag through as a `language-*` class but does not highlight
+/// the snippet.
+fn generate_issue_block(issue: &Issue, language: &str) -> String {
     let mut block = String::new();
 
     // Issue header with severity badge
@@ -245,9 +302,10 @@ fn generate_issue_block(issue: &Issue) -> String {
         Severity::Low => "ðŸŸ¢ **LOW**",
     };
 
+    let known_badge = if issue.known { " _(known)_" } else { "" };
     block.push_str(&format!(
-        "#### {} {} - {}\n\n",
-        severity_badge, issue.category, issue.title
+        "#### {} {} - {}{}\n\n",
+        severity_badge, issue.category, issue.title, known_badge
     ));
 
     // Line reference
@@ -258,9 +316,18 @@ fn generate_issue_block(issue: &Issue) -> String {
         block.push_str(&format!("**Description:** {}\n\n", issue.description));
     }
 
-    // Code snippet
+    // Code snippet, fenced with the file's language so Markdown renderers
+    // that support syntax highlighting can colorize it.
     if let Some(ref snippet) = issue.code_snippet {
-        block.push_str("<details>\n<summary>View Code</summary>\n\n```\n");
+        let fence_lang = if language.eq_ignore_ascii_case("unknown") {
+            ""
+        } else {
+            language
+        };
+        block.push_str(&format!(
+            "<details>\n<summary>View Code</summary>\n\n```{}\n",
+            fence_lang.to_lowercase()
+        ));
         block.push_str(snippet);
         block.push_str("\n```\n</details>\n\n");
     }
@@ -296,6 +363,30 @@ fn generate_recommendations_section(recommendations: &[String]) -> String {
     section
 }
 
+/// Generate the collapsed "Run Log" section from captured `tracing` output
+/// (see `crate::logging`). Collapsed by default since most readers only
+/// need it when debugging a run invoked by a server or CI, where stderr
+/// itself isn't available afterward.
+fn generate_run_log_section(logs: &[crate::logging::LogRecord]) -> String {
+    if logs.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::new();
+
+    section.push_str("## Run Log\n\n");
+    section.push_str("<details>\n<summary>View run log</summary>\n\n```\n");
+    for record in logs {
+        section.push_str(&format!(
+            "{} {:>5} {} {}\n",
+            record.timestamp, record.level, record.target, record.message
+        ));
+    }
+    section.push_str("```\n</details>\n\n");
+
+    section
+}
+
 /// Generate the report footer.
 fn generate_footer() -> String {
     let mut footer = String::new();
@@ -333,6 +424,96 @@ pub fn write_json_report(report: &Report, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Generate a SARIF 2.1.0 report, for upload to GitHub code scanning and
+/// other dashboards that consume the standard interchange format.
+pub fn generate_sarif_report(report: &Report) -> Result<String> {
+    serde_json::to_string_pretty(&report.to_sarif()).map_err(Into::into)
+}
+
+/// Write a SARIF report to a file.
+#[allow(dead_code)] // Convenience wrapper
+pub fn write_sarif_report(report: &Report, path: &Path) -> Result<()> {
+    let content = generate_sarif_report(report)?;
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(())
+}
+
+/// Generate a self-contained, browser-viewable HTML report.
+///
+/// Feeds `generate_markdown_report`'s output through pulldown-cmark's
+/// `Parser`/`push_html` pipeline, then wraps the resulting HTML fragment in
+/// a minimal document with embedded CSS for the page, code blocks and the
+/// collapsible `<details>` blocks `generate_issue_block` emits. Severities
+/// appear as the Markdown's emoji; the CSS has no badge styling.
+pub fn generate_html_report(report: &Report) -> String {
+    let markdown = generate_markdown_report(report);
+
+    let parser = pulldown_cmark::Parser::new_ext(&markdown, pulldown_cmark::Options::all());
+    let mut body_html = String::new();
+    pulldown_cmark::html::push_html(&mut body_html, parser);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>YoAuditor Report</title>
+<style>
+{css}
+</style>
+</head>
+<body>
+<main>
+{body}
+</main>
+</body>
+</html>
+"#,
+        css = HTML_REPORT_CSS,
+        body = body_html
+    )
+}
+
+/// Write an HTML report to a file.
+#[allow(dead_code)] // Convenience wrapper
+pub fn write_html_report(report: &Report, path: &Path) -> Result<()> {
+    let content = generate_html_report(report);
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(())
+}
+
+/// Embedded CSS for `generate_html_report`'s self-contained document: a
+/// readable body font, shaded code blocks, and a subtle border around
+/// collapsible `<details>` blocks. Severity emoji are left unstyled.
+const HTML_REPORT_CSS: &str = r#"
+body {
+    font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+    line-height: 1.5;
+    max-width: 960px;
+    margin: 2rem auto;
+    padding: 0 1rem;
+    color: #1b1f23;
+}
+h1, h2, h3, h4 { border-bottom: 1px solid #e1e4e8; padding-bottom: 0.3rem; }
+code, pre { font-family: "SFMono-Regular", Consolas, "Liberation Mono", Menlo, monospace; }
+pre { background: #f6f8fa; padding: 0.75rem; border-radius: 6px; overflow-x: auto; }
+blockquote { border-left: 4px solid #dfe2e5; margin-left: 0; padding-left: 1rem; color: #555; }
+details {
+    border: 1px solid #e1e4e8;
+    border-radius: 6px;
+    padding: 0.5rem 1rem;
+    margin: 0.5rem 0;
+}
+summary { cursor: pointer; font-weight: 600; }
+hr { border: none; border-top: 1px solid #e1e4e8; margin: 1.5rem 0; }
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +528,7 @@ mod tests {
             files_failed: 0,
             total_issues: 5,
             duration_seconds: 30.0,
+            scoped_to_diff: None,
         };
 
         Report {
@@ -366,6 +548,11 @@ mod tests {
                     description: "Error is not handled".to_string(),
                     suggestion: "Use ? operator".to_string(),
                     code_snippet: Some("let x = foo.unwrap();".to_string()),
+                    fix: None,
+                    start_column: None,
+                    end_column: None,
+                    rule_id: String::new(),
+                    known: false,
                 }],
                 analysis_successful: true,
                 error: None,
@@ -384,6 +571,8 @@ mod tests {
                 "Add proper error handling".to_string(),
                 "Improve test coverage".to_string(),
             ],
+            code_stats: CodeStats::default(),
+            logs: Vec::new(),
         }
     }
 
@@ -400,6 +589,27 @@ mod tests {
         assert!(markdown.contains("Unchecked error"));
     }
 
+    #[test]
+    fn test_generate_summary_section_includes_code_statistics() {
+        let mut by_language = std::collections::HashMap::new();
+        by_language.insert(
+            "Rust".to_string(),
+            crate::stats::LanguageStats {
+                files: 2,
+                blank_lines: 10,
+                comment_lines: 5,
+                code_lines: 85,
+            },
+        );
+        let code_stats = CodeStats { by_language };
+
+        let section = generate_summary_section(&IssueSummary::default(), &[], &code_stats);
+
+        assert!(section.contains("### Code Statistics"));
+        assert!(section.contains("| Rust | 85 | 5 | 10 | 100.0% |"));
+        assert!(section.contains("Comment-to-code ratio"));
+    }
+
     #[test]
     fn test_generate_metadata_section() {
         let metadata = ReportMetadata {
@@ -410,6 +620,7 @@ mod tests {
             files_failed: 2,
             total_issues: 5,
             duration_seconds: 30.0,
+            scoped_to_diff: None,
         };
 
         let section = generate_metadata_section(&metadata);
@@ -420,6 +631,26 @@ mod tests {
         assert!(section.contains("Files Failed:"));
     }
 
+    #[test]
+    fn test_generate_metadata_section_notes_diff_scope() {
+        let mut metadata = ReportMetadata {
+            repo_url: "https://github.com/test/repo".to_string(),
+            analysis_date: Utc::now(),
+            model_used: "test-model".to_string(),
+            files_analyzed: 3,
+            files_failed: 0,
+            total_issues: 1,
+            duration_seconds: 5.0,
+            scoped_to_diff: None,
+        };
+        assert!(!generate_metadata_section(&metadata).contains("Scope:"));
+
+        metadata.scoped_to_diff = Some("origin/main".to_string());
+        let section = generate_metadata_section(&metadata);
+        assert!(section.contains("Scope:"));
+        assert!(section.contains("origin/main"));
+    }
+
     #[test]
     fn test_generate_issue_block() {
         let issue = Issue {
@@ -432,15 +663,47 @@ mod tests {
             description: "User input not sanitized".to_string(),
             suggestion: "Use parameterized queries".to_string(),
             code_snippet: Some("query(user_input)".to_string()),
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
         };
 
-        let block = generate_issue_block(&issue);
+        let block = generate_issue_block(&issue, "Rust");
 
         assert!(block.contains("CRITICAL"));
         assert!(block.contains("Security"));
         assert!(block.contains("SQL Injection"));
         assert!(block.contains("10-15"));
         assert!(block.contains("Use parameterized queries"));
+        assert!(block.contains("```rust\n"));
+        assert!(!block.contains("(known)"));
+    }
+
+    #[test]
+    fn test_generate_issue_block_tags_known_issues() {
+        let mut issue = Issue {
+            file_path: "test.rs".to_string(),
+            start_line: 10,
+            end_line: Some(15),
+            severity: Severity::Low,
+            category: "Style".to_string(),
+            title: "Long line".to_string(),
+            description: String::new(),
+            suggestion: String::new(),
+            code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        };
+        issue.known = true;
+
+        let block = generate_issue_block(&issue, "Rust");
+
+        assert!(block.contains("(known)"));
     }
 
     #[test]
@@ -452,4 +715,25 @@ mod tests {
         assert!(json.contains("\"files\""));
         assert!(json.contains("\"issues\""));
     }
+
+    #[test]
+    fn test_generate_sarif_report() {
+        let report = create_test_report();
+        let sarif = generate_sarif_report(&report).unwrap();
+
+        assert!(sarif.contains("\"version\": \"2.1.0\""));
+        assert!(sarif.contains("\"ruleId\""));
+        assert!(sarif.contains("\"YoAuditor\""));
+    }
+
+    #[test]
+    fn test_generate_html_report() {
+        let report = create_test_report();
+        let html = generate_html_report(&report);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<h1>YoAuditor Report</h1>"));
+        assert!(html.contains("<details>"));
+    }
 }
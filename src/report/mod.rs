@@ -0,0 +1,12 @@
+//! Report generation and rendering.
+
+mod diff;
+mod generator;
+mod renderer;
+
+pub use diff::{diff_reports, generate_diff_json_report, generate_diff_report, IssueDiff};
+pub use generator::{
+    generate_html_report, generate_json_report, generate_markdown_report, generate_sarif_report,
+    write_html_report, write_json_report, write_report, write_sarif_report,
+};
+pub use renderer::render_issue;
@@ -0,0 +1,141 @@
+//! Rich terminal rendering of `Issue`s with source spans and underlines.
+//!
+//! Mirrors how `codespan-reporting` renders diagnostics: a header with the
+//! file path and severity, a small window of the offending source with
+//! right-aligned line numbers, an underline under the affected range, and
+//! the `suggestion` printed as a labeled note below. Intended as a more
+//! reviewable alternative to the flat issue list in the Markdown report.
+
+use crate::models::{Issue, Severity};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+
+/// Number of context lines shown above and below the affected range.
+const CONTEXT_LINES: usize = 2;
+
+/// Render a single `Issue` as a codespan-style diagnostic.
+///
+/// `source` is the full content of `issue.file_path`, typically pulled
+/// from the map returned by `FileScanner::collect_files`. Line numbers are
+/// clamped to the bounds of `source` so a stale or truncated `end_line`
+/// never panics on an out-of-range index.
+pub fn render_issue(issue: &Issue, source: &str) -> String {
+    let color = issue.severity.ansi_color();
+    let lines: Vec<&str> = source.lines().collect();
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{color}{}{RESET} {BOLD}{}{RESET}: {}\n",
+        issue.severity.emoji(),
+        issue.severity,
+        issue.title
+    ));
+    out.push_str(&format!(
+        "  --> {}:{}\n",
+        issue.file_path,
+        issue.line_range()
+    ));
+
+    if lines.is_empty() {
+        out.push_str("  (source unavailable)\n");
+    } else {
+        let total = lines.len();
+        let start_line = issue.start_line.clamp(1, total);
+        let end_line = issue.end_line.unwrap_or(start_line).clamp(start_line, total);
+        let window_start = start_line.saturating_sub(CONTEXT_LINES).max(1);
+        let window_end = (end_line + CONTEXT_LINES).min(total);
+        let gutter_width = window_end.to_string().len();
+
+        for line_no in window_start..=window_end {
+            let text = lines[line_no - 1];
+            let in_span = line_no >= start_line && line_no <= end_line;
+            let marker = if in_span { ">" } else { " " };
+            out.push_str(&format!(
+                "{:>width$} {marker} | {text}\n",
+                line_no,
+                width = gutter_width
+            ));
+            if in_span {
+                let underline = "^".repeat(text.chars().count().max(1));
+                out.push_str(&format!(
+                    "{:width$}   | {color}{underline}{RESET}\n",
+                    "",
+                    width = gutter_width
+                ));
+            }
+        }
+    }
+
+    if !issue.suggestion.is_empty() {
+        out.push_str(&format!("  {color}{BOLD}note{RESET}: {}\n", issue.suggestion));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_issue(start_line: usize, end_line: Option<usize>) -> Issue {
+        Issue {
+            file_path: "src/lib.rs".to_string(),
+            start_line,
+            end_line,
+            severity: Severity::High,
+            category: "Bug".to_string(),
+            title: "Unchecked unwrap".to_string(),
+            description: "This call can panic".to_string(),
+            suggestion: "Use `?` or handle the `None` case".to_string(),
+            code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        }
+    }
+
+    #[test]
+    fn test_render_issue_includes_header_and_note() {
+        let issue = sample_issue(3, None);
+        let source = "fn main() {\n    let x = 1;\n    foo.unwrap();\n    println!(\"{}\", x);\n}\n";
+        let rendered = render_issue(&issue, source);
+
+        assert!(rendered.contains("src/lib.rs:3"));
+        assert!(rendered.contains("Unchecked unwrap"));
+        assert!(rendered.contains("foo.unwrap();"));
+        assert!(rendered.contains("note"));
+        assert!(rendered.contains("Use `?` or handle the `None` case"));
+    }
+
+    #[test]
+    fn test_render_issue_underlines_affected_range() {
+        let issue = sample_issue(2, Some(3));
+        let source = "line1\nline2\nline3\nline4\nline5\n";
+        let rendered = render_issue(&issue, source);
+
+        let lines: Vec<&str> = rendered.lines().collect();
+        let underline_lines: Vec<&&str> = lines.iter().filter(|l| l.contains('^')).collect();
+        assert_eq!(underline_lines.len(), 2);
+    }
+
+    #[test]
+    fn test_render_issue_clamps_out_of_bounds_lines() {
+        let issue = sample_issue(500, Some(600));
+        let source = "line1\nline2\nline3\n";
+        let rendered = render_issue(&issue, source);
+
+        assert!(rendered.contains("line3"));
+        assert!(!rendered.contains("line4"));
+    }
+
+    #[test]
+    fn test_render_issue_handles_empty_source() {
+        let issue = sample_issue(1, None);
+        let rendered = render_issue(&issue, "");
+
+        assert!(rendered.contains("source unavailable"));
+    }
+}
@@ -0,0 +1,337 @@
+//! Deterministic rule-based issue detection.
+//!
+//! Complements the LLM agent with fast, offline checks that produce
+//! reproducible `Issue`s without a model call. Each `Rule` scopes itself to
+//! matching files with a glob, then tests a regex against the file's
+//! content line by line, optionally gated by a companion "must also match"
+//! / "must not match" regex (the `regex` crate has no lookahead support, so
+//! these stand in for it). Because this pass is deterministic and cheap, it
+//! also runs during `--dry-run`, giving users a fast, zero-LLM preview of
+//! what would fire.
+//!
+//! Rules are defined in TOML (the shipped defaults in `defaults.toml`) or
+//! JSON, both following the same `RuleFile` shape, and loaded via
+//! `crate::config::RulesConfig`.
+
+use crate::models::Issue;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+
+/// Default rules shipped with YoAuditor, defined the same way a user's
+/// `rules.extra_rules_file` would be (see `RuleSet::load`).
+const DEFAULT_RULES_TOML: &str = include_str!("defaults.toml");
+
+/// A single deterministic detection rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    /// Stable identifier. Becomes `Issue::rule_id` and is what
+    /// `RulesConfig::disabled` matches against.
+    pub id: String,
+    /// Severity assigned to every `Issue` this rule produces.
+    pub severity: crate::models::Severity,
+    /// Category assigned to every `Issue` this rule produces.
+    pub category: String,
+    /// Glob restricting which scanned files this rule runs against (e.g.
+    /// `**/*.rs`), matched with `scanner::glob_matches`.
+    pub file_glob: String,
+    /// Regex tested against each line of a matching file.
+    pub pattern: String,
+    /// If set, the rule only fires when this regex also matches somewhere
+    /// in the same file (e.g. confirming a companion import is present).
+    #[serde(default)]
+    pub must_also_match: Option<String>,
+    /// If set, the rule is suppressed entirely when this regex matches
+    /// anywhere in the file (e.g. an existing guard, or a
+    /// `// yoauditor-ignore` comment).
+    #[serde(default)]
+    pub must_not_match: Option<String>,
+    /// Issue title/description template. `${1}`, `${2}`, ... interpolate
+    /// `pattern`'s capture groups (`regex::Captures::expand` syntax).
+    pub message: String,
+    /// Suggested-fix template, same interpolation as `message`.
+    pub suggestion: String,
+}
+
+/// The on-disk shape of a rules file: a `[[rules]]` array in TOML, or the
+/// equivalent `{"rules": [...]}` object in JSON.
+#[derive(Debug, Default, Deserialize)]
+struct RuleFile {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+/// A `Rule` with its regexes compiled once, ready to run against many files.
+struct CompiledRule {
+    rule: Rule,
+    pattern: regex::Regex,
+    must_also_match: Option<regex::Regex>,
+    must_not_match: Option<regex::Regex>,
+}
+
+/// A loaded, compiled set of rules ready to run over scanned files.
+pub struct RuleSet {
+    compiled: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Loads the shipped default rules plus `config.extra_rules_file` (if
+    /// set), then drops anything named in `config.disabled`. A rule whose
+    /// regex fails to compile is skipped (with a warning) rather than
+    /// failing the whole load, so one bad user-authored rule doesn't take
+    /// out the defaults too.
+    pub fn load(config: &crate::config::RulesConfig) -> Result<Self> {
+        let mut rules = parse_toml_rules(DEFAULT_RULES_TOML)
+            .context("Failed to parse the built-in default rules")?;
+
+        if let Some(ref path) = config.extra_rules_file {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read rules file: {}", path))?;
+            let extra = if path.ends_with(".json") {
+                serde_json::from_str::<RuleFile>(&content)
+                    .with_context(|| format!("Failed to parse {} as JSON rules", path))?
+                    .rules
+            } else {
+                parse_toml_rules(&content)
+                    .with_context(|| format!("Failed to parse {} as TOML rules", path))?
+            };
+            rules.extend(extra);
+        }
+
+        let disabled: HashSet<&str> = config.disabled.iter().map(String::as_str).collect();
+        rules.retain(|rule| !disabled.contains(rule.id.as_str()));
+
+        let compiled = rules
+            .into_iter()
+            .filter_map(|rule| match compile(rule) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    warn!("Skipping invalid rule: {:#}", e);
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Self { compiled })
+    }
+
+    /// Runs every compiled rule against `files` (path -> contents, as
+    /// returned by `scanner::FileScanner::collect_files`), returning one
+    /// `Issue` per match. Files are visited in sorted order so results are
+    /// stable across runs.
+    pub fn run(&self, files: &HashMap<String, String>) -> Vec<Issue> {
+        let mut paths: Vec<&String> = files.keys().collect();
+        paths.sort();
+
+        let mut issues = Vec::new();
+        for path in paths {
+            let content = &files[path];
+            for compiled in &self.compiled {
+                if !crate::scanner::glob_matches(&compiled.rule.file_glob, path) {
+                    continue;
+                }
+                if let Some(guard) = &compiled.must_also_match {
+                    if !guard.is_match(content) {
+                        continue;
+                    }
+                }
+                if let Some(guard) = &compiled.must_not_match {
+                    if guard.is_match(content) {
+                        continue;
+                    }
+                }
+                issues.extend(compiled.run_against(path, content));
+            }
+        }
+        issues
+    }
+}
+
+fn parse_toml_rules(content: &str) -> Result<Vec<Rule>> {
+    Ok(toml::from_str::<RuleFile>(content)?.rules)
+}
+
+fn compile(rule: Rule) -> Result<CompiledRule> {
+    let pattern = regex::Regex::new(&rule.pattern)
+        .with_context(|| format!("rule '{}': invalid pattern regex", rule.id))?;
+    let must_also_match = rule
+        .must_also_match
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+        .with_context(|| format!("rule '{}': invalid must_also_match regex", rule.id))?;
+    let must_not_match = rule
+        .must_not_match
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+        .with_context(|| format!("rule '{}': invalid must_not_match regex", rule.id))?;
+
+    Ok(CompiledRule {
+        rule,
+        pattern,
+        must_also_match,
+        must_not_match,
+    })
+}
+
+impl CompiledRule {
+    /// Applies `pattern` line by line against `content` (matches don't span
+    /// lines), producing one `Issue` per match with a real `start_line`/
+    /// `end_line` and the matched line as `code_snippet`.
+    fn run_against(&self, path: &str, content: &str) -> Vec<Issue> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| {
+                let caps = self.pattern.captures(line)?;
+                let line_number = idx + 1;
+
+                let mut message = String::new();
+                caps.expand(&self.rule.message, &mut message);
+                let mut suggestion = String::new();
+                caps.expand(&self.rule.suggestion, &mut suggestion);
+
+                Some(Issue {
+                    file_path: path.to_string(),
+                    start_line: line_number,
+                    end_line: Some(line_number),
+                    severity: self.rule.severity,
+                    category: self.rule.category.clone(),
+                    title: message.clone(),
+                    description: message,
+                    suggestion,
+                    code_snippet: Some(line.to_string()),
+                    fix: None,
+                    start_column: None,
+                    end_column: None,
+                    rule_id: self.rule.id.clone(),
+                    known: false,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RulesConfig;
+    use crate::models::Severity;
+
+    fn rule_config() -> RulesConfig {
+        RulesConfig {
+            enabled: true,
+            extra_rules_file: None,
+            disabled: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_rules_load_and_compile() {
+        let rule_set = RuleSet::load(&rule_config()).unwrap();
+        assert!(!rule_set.compiled.is_empty());
+    }
+
+    #[test]
+    fn test_hardcoded_secret_rule_fires() {
+        let rule_set = RuleSet::load(&rule_config()).unwrap();
+        let mut files = HashMap::new();
+        files.insert(
+            "config.rs".to_string(),
+            "let api_key = \"sk_live_abcdef1234567890\";\n".to_string(),
+        );
+
+        let issues = rule_set.run(&files);
+        assert!(issues.iter().any(|i| i.rule_id == "hardcoded-secret"));
+        let issue = issues.iter().find(|i| i.rule_id == "hardcoded-secret").unwrap();
+        assert_eq!(issue.start_line, 1);
+        assert_eq!(issue.severity, Severity::Critical);
+        assert!(issue.title.contains("api_key") || issue.title.to_lowercase().contains("key"));
+    }
+
+    #[test]
+    fn test_disabled_rule_is_skipped() {
+        let mut config = rule_config();
+        config.disabled.push("hardcoded-secret".to_string());
+        let rule_set = RuleSet::load(&config).unwrap();
+
+        let mut files = HashMap::new();
+        files.insert(
+            "config.rs".to_string(),
+            "let api_key = \"sk_live_abcdef1234567890\";\n".to_string(),
+        );
+
+        let issues = rule_set.run(&files);
+        assert!(!issues.iter().any(|i| i.rule_id == "hardcoded-secret"));
+    }
+
+    #[test]
+    fn test_file_glob_restricts_matches() {
+        let rule_set = RuleSet::load(&rule_config()).unwrap();
+        let mut files = HashMap::new();
+        files.insert("main.py".to_string(), "result.unwrap()\n".to_string());
+
+        let issues = rule_set.run(&files);
+        assert!(!issues.iter().any(|i| i.rule_id == "rust-unwrap-outside-tests"));
+    }
+
+    #[test]
+    fn test_must_not_match_suppresses_rule() {
+        let rule_set = RuleSet::load(&rule_config()).unwrap();
+        let mut files = HashMap::new();
+        files.insert(
+            "lib.rs".to_string(),
+            "#[cfg(test)]\nmod tests {\n    fn f() { x.unwrap(); }\n}\n".to_string(),
+        );
+
+        let issues = rule_set.run(&files);
+        assert!(!issues.iter().any(|i| i.rule_id == "rust-unwrap-outside-tests"));
+    }
+
+    #[test]
+    fn test_must_also_match_gates_rule() {
+        let rule_set = RuleSet::load(&rule_config()).unwrap();
+
+        let mut without_format = HashMap::new();
+        without_format.insert("db.rs".to_string(), "conn.execute(query)?;\n".to_string());
+        assert!(!rule_set
+            .run(&without_format)
+            .iter()
+            .any(|i| i.rule_id == "format-built-sql"));
+
+        let mut with_format = HashMap::new();
+        with_format.insert(
+            "db.rs".to_string(),
+            "let query = format!(\"SELECT * FROM t WHERE id = {}\", id);\nconn.execute(&query)?;\n"
+                .to_string(),
+        );
+        assert!(rule_set
+            .run(&with_format)
+            .iter()
+            .any(|i| i.rule_id == "format-built-sql"));
+    }
+
+    #[test]
+    fn test_capture_group_interpolation() {
+        let rule = Rule {
+            id: "test-rule".to_string(),
+            severity: Severity::Medium,
+            category: "Style".to_string(),
+            file_glob: "**/*".to_string(),
+            pattern: r"TODO\((\w+)\)".to_string(),
+            must_also_match: None,
+            must_not_match: None,
+            message: "TODO assigned to ${1}".to_string(),
+            suggestion: "Follow up with ${1}".to_string(),
+        };
+
+        let compiled = compile(rule).unwrap();
+        let issues = compiled.run_against("a.txt", "// TODO(alice) fix this\n");
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title, "TODO assigned to alice");
+        assert_eq!(issues[0].suggestion, "Follow up with alice");
+    }
+}
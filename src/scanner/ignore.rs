@@ -0,0 +1,289 @@
+//! Gitignore-style glob matching for `FileScanner`.
+//!
+//! Supports the subset of `.gitignore` syntax that matters for scanning:
+//! `*` within a path segment, `**` to cross directory boundaries, a
+//! trailing `/` for directory-only rules, and a leading `!` for negation.
+//! Patterns containing a `/` (other than a trailing one) are anchored to
+//! the directory that defines them; patterns without one may match at any
+//! depth, same as real `.gitignore` semantics.
+
+/// A single compiled pattern, either from `ScanConfig::excludes` or a line
+/// in a `.gitignore` file.
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    segments: Vec<String>,
+    negated: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    /// Parse a single exclude pattern or `.gitignore` line. Returns `None`
+    /// for blank lines and comments (`#`), matching `.gitignore` syntax.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+
+        let anchored = pattern.contains('/');
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+        Some(Self {
+            segments: pattern.split('/').map(String::from).collect(),
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    /// Whether this rule negates a previous match (a `!pattern` line).
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Test this rule against a path relative to the directory it was
+    /// defined in (already split on `/`).
+    pub fn matches(&self, path_segments: &[&str], is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+
+        let pattern_segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+
+        if self.anchored {
+            segments_match(&pattern_segments, path_segments)
+        } else {
+            (0..=path_segments.len())
+                .any(|start| segments_match(&pattern_segments, &path_segments[start..]))
+        }
+    }
+}
+
+/// A compiled set of rules, in file order, from one `.gitignore` (or the
+/// flat `ScanConfig::excludes` list).
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRuleSet {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRuleSet {
+    /// Parse every line of a `.gitignore`-style file.
+    pub fn parse(content: &str) -> Self {
+        Self {
+            rules: content.lines().filter_map(IgnoreRule::parse).collect(),
+        }
+    }
+
+    /// Build a rule set directly from a list of patterns (e.g.
+    /// `ScanConfig::excludes`), skipping blanks/comments same as a file.
+    pub fn from_patterns(patterns: &[String]) -> Self {
+        Self {
+            rules: patterns.iter().filter_map(|p| IgnoreRule::parse(p)).collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Test a single rule set in isolation (file order, last match wins),
+    /// for a flat pattern list with no directory hierarchy (e.g.
+    /// `ScanConfig::excludes`).
+    pub fn matches(&self, path_segments: &[&str], is_dir: bool) -> bool {
+        let mut matched = false;
+        for rule in &self.rules {
+            if rule.matches(path_segments, is_dir) {
+                matched = !rule.is_negated();
+            }
+        }
+        matched
+    }
+}
+
+/// A hierarchical stack of `.gitignore` rule sets, one per directory level
+/// between the repo root and the directory currently being walked, plus a
+/// base frame for `ScanConfig::excludes`.
+///
+/// Matching walks the stack outermost-to-innermost; within each frame,
+/// rules are tried in file order. The last matching rule overall wins,
+/// which naturally gives rules defined deeper in the tree (or later in a
+/// file) precedence over shallower/earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreStack {
+    frames: Vec<(String, IgnoreRuleSet)>,
+}
+
+impl IgnoreStack {
+    pub fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Push the rule set defined at `dir_rel` (path relative to the repo
+    /// root, `""` for the root itself). No-op if the set is empty, so
+    /// empty `.gitignore` files don't bloat the stack.
+    pub fn push(&mut self, dir_rel: String, rule_set: IgnoreRuleSet) {
+        if !rule_set.is_empty() {
+            self.frames.push((dir_rel, rule_set));
+        }
+    }
+
+    /// Pop the most recently pushed frame, if one was actually pushed for
+    /// this directory (mirrors `push`'s empty-set no-op).
+    pub fn pop_if(&mut self, dir_rel: &str) {
+        if self.frames.last().is_some_and(|(d, _)| d == dir_rel) {
+            self.frames.pop();
+        }
+    }
+
+    /// Whether `path_rel` (relative to the repo root) is ignored.
+    pub fn is_ignored(&self, path_rel: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+
+        for (dir_prefix, rule_set) in &self.frames {
+            let local_path = if dir_prefix.is_empty() {
+                path_rel
+            } else {
+                match path_rel.strip_prefix(dir_prefix.as_str()) {
+                    Some(rest) => rest.strip_prefix('/').unwrap_or(rest),
+                    None => continue,
+                }
+            };
+            if local_path.is_empty() {
+                continue;
+            }
+
+            let segments: Vec<&str> = local_path.split('/').collect();
+            for rule in &rule_set.rules {
+                if rule.matches(&segments, is_dir) {
+                    ignored = !rule.is_negated();
+                }
+            }
+        }
+
+        ignored
+    }
+}
+
+/// Match a single path segment against a pattern segment containing `*`
+/// wildcards (each `*` matches any run of characters, including none).
+fn segment_match(pattern: &str, text: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let mut dp = vec![vec![false; t.len() + 1]; p.len() + 1];
+    dp[0][0] = true;
+
+    for i in 1..=p.len() {
+        if p[i - 1] == '*' {
+            dp[i][0] = dp[i - 1][0];
+        }
+    }
+    for i in 1..=p.len() {
+        for j in 1..=t.len() {
+            dp[i][j] = if p[i - 1] == '*' {
+                dp[i - 1][j] || dp[i][j - 1]
+            } else {
+                dp[i - 1][j - 1] && p[i - 1] == t[j - 1]
+            };
+        }
+    }
+
+    dp[p.len()][t.len()]
+}
+
+/// Match pattern segments (split on `/`, `**` allowed as a whole segment)
+/// against path segments, anchored at index 0 of both.
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(&"**") => {
+            if pattern.len() == 1 {
+                return true;
+            }
+            (0..=path.len()).any(|skip| segments_match(&pattern[1..], &path[skip..]))
+        }
+        Some(seg) => {
+            !path.is_empty()
+                && segment_match(seg, path[0])
+                && segments_match(&pattern[1..], &path[1..])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_name_matches_anywhere() {
+        let rule = IgnoreRule::parse("target").unwrap();
+        assert!(rule.matches(&["target"], true));
+        assert!(rule.matches(&["nested", "target"], true));
+        assert!(!rule.matches(&["targets"], true));
+    }
+
+    #[test]
+    fn test_star_glob() {
+        let rule = IgnoreRule::parse("*.min.js").unwrap();
+        assert!(rule.matches(&["app.min.js"], false));
+        assert!(!rule.matches(&["app.js"], false));
+    }
+
+    #[test]
+    fn test_double_star_crosses_directories() {
+        let rule = IgnoreRule::parse("**/generated/**").unwrap();
+        assert!(rule.matches(&["src", "generated", "foo.rs"], false));
+        assert!(rule.matches(&["generated", "foo.rs"], false));
+        assert!(!rule.matches(&["src", "foo.rs"], false));
+    }
+
+    #[test]
+    fn test_dir_only_requires_is_dir() {
+        let rule = IgnoreRule::parse("build/").unwrap();
+        assert!(rule.matches(&["build"], true));
+        assert!(!rule.matches(&["build"], false));
+    }
+
+    #[test]
+    fn test_anchored_pattern_only_matches_at_root() {
+        let rule = IgnoreRule::parse("/build").unwrap();
+        assert!(rule.matches(&["build"], true));
+        assert!(!rule.matches(&["nested", "build"], true));
+    }
+
+    #[test]
+    fn test_negation_overrides_earlier_match() {
+        let rule_set = IgnoreRuleSet::parse("*.log\n!important.log\n");
+        let mut stack = IgnoreStack::new();
+        stack.push(String::new(), rule_set);
+
+        assert!(stack.is_ignored("debug.log", false));
+        assert!(!stack.is_ignored("important.log", false));
+    }
+
+    #[test]
+    fn test_nested_gitignore_overrides_root() {
+        let mut stack = IgnoreStack::new();
+        stack.push(String::new(), IgnoreRuleSet::parse("*.log\n"));
+        stack.push("sub".to_string(), IgnoreRuleSet::parse("!keep.log\n"));
+
+        assert!(stack.is_ignored("sub/other.log", false));
+        assert!(!stack.is_ignored("sub/keep.log", false));
+        assert!(stack.is_ignored("other.log", false));
+    }
+}
@@ -3,8 +3,11 @@
 //! This module provides a unified file scanner that respects
 //! configuration for extensions, excludes, and file size limits.
 
+mod ignore;
+
 use anyhow::Result;
-use std::collections::HashMap;
+use ignore::{IgnoreRuleSet, IgnoreStack};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
@@ -20,6 +23,9 @@ pub struct ScanConfig {
     pub max_file_size: usize,
     /// Maximum number of files to scan
     pub max_files: Option<usize>,
+    /// Parse `.gitignore` files encountered while walking and apply their
+    /// rules hierarchically, in addition to `excludes`.
+    pub respect_gitignore: bool,
 }
 
 impl Default for ScanConfig {
@@ -48,6 +54,7 @@ impl Default for ScanConfig {
             .collect(),
             max_file_size: 100 * 1024, // 100KB
             max_files: None,
+            respect_gitignore: false,
         }
     }
 }
@@ -59,10 +66,61 @@ impl From<&crate::config::ScannerConfig> for ScanConfig {
             excludes: config.excludes.clone(),
             max_file_size: config.max_file_size,
             max_files: Some(config.max_files),
+            respect_gitignore: config.respect_gitignore,
         }
     }
 }
 
+/// Whether `path` (relative to the repo root, `/`-separated) matches a single
+/// glob pattern, using the same `*`/`**` semantics as `ScanConfig::excludes`.
+/// Used by `crate::rules` to scope a `Rule` to the files it applies to,
+/// without reimplementing glob matching.
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let rule_set = IgnoreRuleSet::from_patterns(std::slice::from_ref(&pattern.to_string()));
+    let segments: Vec<&str> = path.split('/').collect();
+    rule_set.matches(&segments, false)
+}
+
+/// Expand a set of changed relative paths with other scanned files that look
+/// like they depend on them, for `--since`/`--changed-only` audits where a
+/// diff alone would miss transitive context (e.g. a caller of a changed
+/// function). This is a cheap text heuristic, not a real dependency graph: a
+/// same-extension file is pulled in if it contains the changed file's module
+/// name (its file stem). Stems shorter than 3 characters are skipped since
+/// they're too common to be a meaningful signal.
+pub fn expand_with_dependents(
+    changed: &HashSet<String>,
+    all_files: &HashMap<String, String>,
+) -> HashSet<String> {
+    let mut expanded = changed.clone();
+
+    for changed_path in changed {
+        let Some(stem) = Path::new(changed_path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+        else {
+            continue;
+        };
+        if stem.len() < 3 {
+            continue;
+        }
+
+        let changed_ext = Path::new(changed_path).extension();
+
+        for (path, content) in all_files {
+            if expanded.contains(path) {
+                continue;
+            }
+            let same_extension = Path::new(path).extension() == changed_ext;
+            if same_extension && content.contains(stem) {
+                expanded.insert(path.clone());
+            }
+        }
+    }
+
+    expanded
+}
+
 /// Scanned file information.
 #[derive(Debug, Clone)]
 pub struct ScannedFile {
@@ -80,18 +138,26 @@ pub struct ScannedFile {
 pub struct FileScanner {
     config: ScanConfig,
     repo_root: PathBuf,
+    /// `excludes` compiled into glob rules once, instead of per-file.
+    compiled_excludes: IgnoreRuleSet,
 }
 
 impl FileScanner {
     /// Create a new file scanner.
     pub fn new(repo_root: PathBuf, config: ScanConfig) -> Self {
-        Self { config, repo_root }
+        let compiled_excludes = IgnoreRuleSet::from_patterns(&config.excludes);
+        Self {
+            config,
+            repo_root,
+            compiled_excludes,
+        }
     }
 
     /// Scan for all matching files.
     pub fn scan(&self) -> Result<Vec<ScannedFile>> {
         let mut files = Vec::new();
-        self.walk_dir(&self.repo_root, &mut files)?;
+        let mut ignore_stack = IgnoreStack::new();
+        self.walk_dir(&self.repo_root, &mut files, &mut ignore_stack)?;
 
         // Apply max_files limit if set
         if let Some(max) = self.config.max_files {
@@ -144,13 +210,15 @@ impl FileScanner {
         for entry in dir_entries.flatten() {
             let path = entry.path();
             let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = path.is_dir();
 
             // Skip excluded patterns
-            if self.is_excluded(&name) {
+            let rel_path = self.relative_path(&path);
+            if self.is_excluded(&rel_path, is_dir) {
                 continue;
             }
 
-            let suffix = if path.is_dir() { "/" } else { "" };
+            let suffix = if is_dir { "/" } else { "" };
             entries.push(format!("{}{}", name, suffix));
         }
 
@@ -161,10 +229,9 @@ impl FileScanner {
     /// Check if a file matches scan criteria.
     pub fn matches(&self, path: &Path) -> bool {
         // Check if excluded
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if self.is_excluded(name) {
-                return false;
-            }
+        let rel_path = self.relative_path(path);
+        if self.is_excluded(&rel_path, path.is_dir()) {
+            return false;
         }
 
         // Check extension
@@ -185,15 +252,40 @@ impl FileScanner {
         true
     }
 
-    /// Check if a name matches exclusion patterns.
-    fn is_excluded(&self, name: &str) -> bool {
-        // Hidden files
-        if name.starts_with('.') {
+    /// Path of `path` relative to the repo root, with `/` separators, for
+    /// matching against glob/gitignore patterns.
+    pub fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.repo_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// Whether `path` matches the scanner's configured `excludes` (or is a
+    /// hidden file/directory), independent of extension/size. Unlike
+    /// `matches`, this is meaningful for directories too, so callers that
+    /// walk the tree themselves (see `agent::search`) can prune an excluded
+    /// subtree before descending into it instead of filtering a fully
+    /// expanded file list afterward.
+    pub fn is_excluded_path(&self, path: &Path) -> bool {
+        let rel_path = self.relative_path(path);
+        self.is_excluded(&rel_path, path.is_dir())
+    }
+
+    /// Check if a path (relative to the repo root) matches the compiled
+    /// `excludes` glob patterns, or is a hidden file/directory.
+    fn is_excluded(&self, rel_path: &str, is_dir: bool) -> bool {
+        // Hidden files/directories
+        if rel_path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| name.starts_with('.'))
+        {
             return true;
         }
 
-        // Explicit excludes
-        self.config.excludes.iter().any(|pattern| name == pattern)
+        let segments: Vec<&str> = rel_path.split('/').collect();
+        self.compiled_excludes.matches(&segments, is_dir)
     }
 
     /// Check if a path is within the repository root.
@@ -201,15 +293,25 @@ impl FileScanner {
         // Canonicalize paths to handle symlinks and ..
         let canonical_repo = fs::canonicalize(&self.repo_root)
             .unwrap_or_else(|_| self.repo_root.clone());
-        
-        let canonical_path = fs::canonicalize(path)
-            .unwrap_or_else(|_| path.to_path_buf());
 
-        Ok(canonical_path.starts_with(canonical_repo))
+        match fs::canonicalize(path) {
+            Ok(canonical_path) => Ok(canonical_path.starts_with(canonical_repo)),
+            // Path doesn't exist yet (or can't be canonicalized);
+            // `starts_with` is component-wise and won't collapse `..`, so
+            // normalize lexically first.
+            Err(_) => Ok(crate::pathutil::lexically_normalize(path).starts_with(canonical_repo)),
+        }
     }
 
-    /// Walk directory recursively.
-    fn walk_dir(&self, dir: &Path, files: &mut Vec<ScannedFile>) -> Result<()> {
+    /// Walk directory recursively. `ignore_stack` carries the `.gitignore`
+    /// rule sets of every ancestor directory seen so far, when
+    /// `respect_gitignore` is enabled.
+    fn walk_dir(
+        &self,
+        dir: &Path,
+        files: &mut Vec<ScannedFile>,
+        ignore_stack: &mut IgnoreStack,
+    ) -> Result<()> {
         // Check max_files limit
         if let Some(max) = self.config.max_files {
             if files.len() >= max {
@@ -221,10 +323,18 @@ impl FileScanner {
             return Ok(());
         }
 
+        let dir_rel = self.relative_path(dir);
+        if self.config.respect_gitignore {
+            if let Ok(content) = fs::read_to_string(dir.join(".gitignore")) {
+                ignore_stack.push(dir_rel.clone(), IgnoreRuleSet::parse(&content));
+            }
+        }
+
         let entries = match fs::read_dir(dir) {
             Ok(e) => e,
             Err(e) => {
                 debug!("Cannot read directory {}: {}", dir.display(), e);
+                ignore_stack.pop_if(&dir_rel);
                 return Ok(());
             }
         };
@@ -238,18 +348,21 @@ impl FileScanner {
             }
 
             let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = path.is_dir();
+            let rel_path = self.relative_path(&path);
 
             // Skip excluded
-            if self.is_excluded(&name) {
+            if self.is_excluded(&rel_path, is_dir) {
+                continue;
+            }
+            if self.config.respect_gitignore && ignore_stack.is_ignored(&rel_path, is_dir) {
                 continue;
             }
 
-            if path.is_dir() {
-                self.walk_dir(&path, files)?;
+            if is_dir {
+                self.walk_dir(&path, files, ignore_stack)?;
             } else if path.is_file() && self.matches(&path) {
                 if let Ok(metadata) = fs::metadata(&path) {
-                    let rel_path = path.strip_prefix(&self.repo_root).unwrap_or(&path);
                     let ext = path
                         .extension()
                         .and_then(|e| e.to_str())
@@ -257,7 +370,7 @@ impl FileScanner {
                         .to_string();
 
                     files.push(ScannedFile {
-                        path: rel_path.to_string_lossy().to_string(),
+                        path: rel_path,
                         size: metadata.len(),
                         extension: ext,
                     });
@@ -265,6 +378,130 @@ impl FileScanner {
             }
         }
 
+        ignore_stack.pop_if(&dir_rel);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_with_dependents_pulls_in_callers() {
+        let changed: HashSet<String> = ["src/parser.rs".to_string()].into_iter().collect();
+        let all_files: HashMap<String, String> = [
+            (
+                "src/parser.rs".to_string(),
+                "pub fn parse() {}".to_string(),
+            ),
+            (
+                "src/caller.rs".to_string(),
+                "use crate::parser::parse;".to_string(),
+            ),
+            (
+                "src/unrelated.rs".to_string(),
+                "fn other() {}".to_string(),
+            ),
+            (
+                "src/parser.py".to_string(),
+                "import parser".to_string(), // different language, should not be pulled in
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let expanded = expand_with_dependents(&changed, &all_files);
+
+        assert!(expanded.contains("src/parser.rs"));
+        assert!(expanded.contains("src/caller.rs"));
+        assert!(!expanded.contains("src/unrelated.rs"));
+        assert!(!expanded.contains("src/parser.py"));
+    }
+
+    #[test]
+    fn test_expand_with_dependents_skips_short_stems() {
+        let changed: HashSet<String> = ["src/io.rs".to_string()].into_iter().collect();
+        let all_files: HashMap<String, String> = [
+            ("src/io.rs".to_string(), "pub fn read() {}".to_string()),
+            (
+                "src/everything.rs".to_string(),
+                "// mentions io in passing".to_string(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let expanded = expand_with_dependents(&changed, &all_files);
+
+        assert_eq!(expanded, changed);
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("**/*.rs", "src/main.rs"));
+        assert!(glob_matches("**/*.rs", "main.rs"));
+        assert!(!glob_matches("**/*.rs", "src/main.py"));
+        assert!(glob_matches("**/*", "any/path/at/all.txt"));
+    }
+
+    #[test]
+    fn test_scan_glob_excludes() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("app.rs"), "fn main() {}").unwrap();
+        fs::write(temp.path().join("app.min.js"), "/* minified */").unwrap();
+        fs::create_dir(temp.path().join("generated")).unwrap();
+        fs::write(temp.path().join("generated/codegen.rs"), "// generated").unwrap();
+
+        let mut config = ScanConfig::default();
+        config.extensions = vec!["rs".to_string(), "js".to_string()];
+        config.excludes = vec!["*.min.js".to_string(), "**/generated/**".to_string()];
+
+        let scanner = FileScanner::new(temp.path().to_path_buf(), config);
+        let files: Vec<String> = scanner.scan().unwrap().into_iter().map(|f| f.path).collect();
+
+        assert!(files.contains(&"app.rs".to_string()));
+        assert!(!files.contains(&"app.min.js".to_string()));
+        assert!(!files.contains(&"generated/codegen.rs".to_string()));
+    }
+
+    #[test]
+    fn test_scan_respects_gitignore_hierarchically() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join(".gitignore"), "*.log\n").unwrap();
+        fs::write(temp.path().join("debug.log"), "oops").unwrap();
+        fs::write(temp.path().join("main.rs"), "fn main() {}").unwrap();
+
+        fs::create_dir(temp.path().join("sub")).unwrap();
+        fs::write(temp.path().join("sub/.gitignore"), "!keep.log\n").unwrap();
+        fs::write(temp.path().join("sub/keep.log"), "kept").unwrap();
+        fs::write(temp.path().join("sub/other.log"), "dropped").unwrap();
+
+        let mut config = ScanConfig::default();
+        config.extensions = vec!["rs".to_string(), "log".to_string()];
+        config.respect_gitignore = true;
+
+        let scanner = FileScanner::new(temp.path().to_path_buf(), config);
+        let files: Vec<String> = scanner.scan().unwrap().into_iter().map(|f| f.path).collect();
+
+        assert!(files.contains(&"main.rs".to_string()));
+        assert!(!files.contains(&"debug.log".to_string()));
+        assert!(files.contains(&"sub/keep.log".to_string()));
+        assert!(!files.contains(&"sub/other.log".to_string()));
+    }
+
+    #[test]
+    fn test_scan_ignores_gitignore_without_respect_flag() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join(".gitignore"), "*.log\n").unwrap();
+        fs::write(temp.path().join("debug.log"), "oops").unwrap();
+
+        let mut config = ScanConfig::default();
+        config.extensions = vec!["log".to_string()];
+
+        let scanner = FileScanner::new(temp.path().to_path_buf(), config);
+        let files: Vec<String> = scanner.scan().unwrap().into_iter().map(|f| f.path).collect();
+
+        assert!(files.contains(&"debug.log".to_string()));
+    }
+}
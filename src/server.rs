@@ -0,0 +1,203 @@
+//! `--serve` HTTP mode: a long-running service exposing `POST /v1/audit`
+//! (submit a job) and `GET /v1/audit/{id}` (poll for the result), so CI
+//! webhooks or dashboards can request audits without spawning a process per
+//! repo. Jobs run in the background via `crate::audit_once`, the same
+//! pipeline the one-shot CLI uses, so behavior stays identical between the
+//! two entry points.
+
+use crate::cli::{Args, FailOnLevel, OutputFormat};
+use crate::config::Config;
+use crate::models::Report;
+use anyhow::{Context, Result};
+use axum::extract::{Path as AxumPath, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// `POST /v1/audit` request body. Anything left unset falls back to the
+/// value this server process was started with, mirroring how
+/// `batch::BatchJob` overrides a `--workload` job's base `Args`.
+///
+/// Deliberately has no `local`/path field: a remote HTTP caller choosing an
+/// arbitrary filesystem path for the server process to read and report on
+/// would let anyone who can reach `--serve` read any file the server can.
+/// Analyzing a local directory stays a `--local`/`--workload`-only, operator
+/// controlled, CLI capability.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditJobRequest {
+    /// Repository URL to clone.
+    #[serde(default)]
+    pub repo_url: Option<String>,
+    /// Branch to check out, if not the default.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Model override for this job.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Report format override for this job.
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    /// `--min-severity` threshold for this job.
+    #[serde(default)]
+    pub min_severity: Option<FailOnLevel>,
+    /// `--fail-on` threshold for this job.
+    #[serde(default)]
+    pub fail_on: Option<FailOnLevel>,
+}
+
+/// Response to `POST /v1/audit`: the id to poll via `GET /v1/audit/{id}`.
+#[derive(Debug, Clone, Serialize)]
+struct EnqueueResponse {
+    job_id: String,
+}
+
+/// Current state of one enqueued job, returned by `GET /v1/audit/{id}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum JobStatus {
+    Pending,
+    Running,
+    Done { report: Report },
+    Failed { error: String },
+}
+
+type JobStore = Arc<Mutex<HashMap<String, JobStatus>>>;
+
+#[derive(Clone)]
+struct ServerState {
+    base_args: Arc<Args>,
+    jobs: JobStore,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Runs the `--serve` HTTP server on `port` until the process is killed.
+pub async fn run_server(base_args: Args, port: u16) -> Result<()> {
+    let state = ServerState {
+        base_args: Arc::new(base_args),
+        jobs: Arc::new(Mutex::new(HashMap::new())),
+        next_id: Arc::new(AtomicU64::new(1)),
+    };
+
+    let app = Router::new()
+        .route("/v1/audit", post(submit_audit))
+        .route("/v1/audit/:id", get(get_audit))
+        .with_state(state);
+
+    let addr = format!("0.0.0.0:{}", port);
+    println!("🌐 yoauditor serving on http://{}", addr);
+    info!("Listening on http://{}", addr);
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind to {}", addr))?;
+    axum::serve(listener, app)
+        .await
+        .context("HTTP server exited unexpectedly")
+}
+
+/// `POST /v1/audit`: enqueues the job and returns its id immediately. The
+/// audit itself runs on a spawned task so a slow LLM call doesn't hold the
+/// HTTP connection open.
+async fn submit_audit(
+    State(state): State<ServerState>,
+    Json(job): Json<AuditJobRequest>,
+) -> Json<EnqueueResponse> {
+    let job_id = format!("job-{}", state.next_id.fetch_add(1, Ordering::SeqCst));
+    state.jobs.lock().unwrap().insert(job_id.clone(), JobStatus::Pending);
+
+    let id_for_task = job_id.clone();
+    let state_for_task = state.clone();
+    tokio::spawn(async move {
+        state_for_task
+            .jobs
+            .lock()
+            .unwrap()
+            .insert(id_for_task.clone(), JobStatus::Running);
+
+        let status = match run_job(&state_for_task.base_args, job).await {
+            Ok(report) => JobStatus::Done { report },
+            Err(e) => {
+                warn!("Job '{}' failed: {}", id_for_task, e);
+                JobStatus::Failed { error: e.to_string() }
+            }
+        };
+        state_for_task.jobs.lock().unwrap().insert(id_for_task, status);
+    });
+
+    Json(EnqueueResponse { job_id })
+}
+
+/// `GET /v1/audit/{id}`: returns the job's current status, or 404 if the id
+/// is unknown.
+async fn get_audit(
+    State(state): State<ServerState>,
+    AxumPath(id): AxumPath<String>,
+) -> Result<Json<JobStatus>, StatusCode> {
+    state
+        .jobs
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Builds per-job `Args` from `base_args` (mirroring
+/// `batch::build_job_args`), clones the repo, and runs `audit_once` to
+/// produce the `Report`.
+async fn run_job(base_args: &Args, job: AuditJobRequest) -> Result<Report> {
+    let start_time = Instant::now();
+    let mut args = base_args.clone();
+    // `base_args.serve` is the flag that started this server; a single job's
+    // `Args` is a normal one-shot audit, so it must run the usual
+    // repo/local validation rather than `--serve`'s early return.
+    args.serve = false;
+
+    if job.repo_url.is_some() {
+        args.repo = job.repo_url.clone();
+    }
+    if job.branch.is_some() {
+        args.branch = job.branch.clone();
+    }
+    if let Some(ref model) = job.model {
+        args.model = model.clone();
+    }
+    if let Some(format) = job.format {
+        args.format = format;
+    }
+    if job.min_severity.is_some() {
+        args.min_severity = job.min_severity;
+    }
+    if job.fail_on.is_some() {
+        args.fail_on = job.fail_on;
+    }
+
+    args.validate().map_err(anyhow::Error::msg)?;
+
+    let repo_url = args.repo_url().to_string();
+    let repo_path = crate::get_repository(&args).await?;
+
+    let mut config = crate::load_config(&args)?;
+    config.apply_env()?;
+    config.merge_with_args(&args);
+    config.validate(args.allow_large_scan)?;
+    if args.config.is_none() {
+        if let Ok(repo_config) = Config::discover(&repo_path) {
+            config = repo_config;
+            config.apply_env()?;
+            config.merge_with_args(&args);
+            config.validate(args.allow_large_scan)?;
+        }
+    }
+
+    let scan_config = crate::scanner::ScanConfig::from(&config.scanner);
+
+    crate::audit_once(&args, &config, &repo_path, &scan_config, None, repo_url, start_time).await
+}
@@ -0,0 +1,519 @@
+//! Supply-chain audit over dependency manifests and lockfiles.
+//!
+//! Detects per-ecosystem manifest files at the repo root (`Cargo.toml`/
+//! `Cargo.lock`, `package.json`/`package-lock.json`, `requirements.txt`,
+//! `go.mod`/`go.sum`) and runs version-pinning hygiene checks: loose/
+//! wildcard version ranges, git dependencies pinned to a mutable branch
+//! instead of a commit/tag, manifest entries missing from their lockfile,
+//! and (optionally) packages matching a local advisory list. Everything
+//! here is static text/manifest parsing, so it runs fully offline and needs
+//! no LLM call.
+//!
+//! This complements `dependency_audit` (which checks `Cargo.lock` against
+//! the RustSec vulnerability database): that module asks "is a pinned
+//! version known-vulnerable?", this one asks "is the pinning itself
+//! trustworthy?". Gated behind `--supply-chain`/`[supply_chain].enabled`
+//! since walking every manifest format adds scan time the LLM-based audit
+//! doesn't need by default.
+
+use crate::models::{Issue, Severity};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tracing::warn;
+
+/// Category assigned to every `Issue` this module produces.
+const CATEGORY: &str = "Supply Chain";
+
+/// Runs every ecosystem-specific check that finds a manifest at the repo
+/// root, returning one `Issue` per finding. A missing manifest for a given
+/// ecosystem is simply skipped, not an error -- most repos only use one or
+/// two ecosystems.
+pub fn audit_supply_chain(
+    repo_path: &Path,
+    config: &crate::config::SupplyChainConfig,
+) -> Result<Vec<Issue>> {
+    let advisories = load_advisories(config)?;
+
+    let mut issues = Vec::new();
+    issues.extend(audit_cargo(repo_path, &advisories));
+    issues.extend(audit_npm(repo_path, &advisories));
+    issues.extend(audit_pip(repo_path, &advisories));
+    issues.extend(audit_go(repo_path));
+
+    Ok(issues)
+}
+
+/// One entry in an advisory file: a package name to flag if it turns up in
+/// any detected manifest/lockfile, and why.
+#[derive(Debug, Clone, Deserialize)]
+struct Advisory {
+    name: String,
+    reason: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AdvisoryFile {
+    #[serde(default)]
+    advisories: Vec<Advisory>,
+}
+
+/// Loads `config.advisory_file` (`.json` extension selects the JSON parser,
+/// anything else TOML) into a `name -> reason` lookup. Returns an empty map
+/// if no advisory file is configured.
+fn load_advisories(config: &crate::config::SupplyChainConfig) -> Result<HashMap<String, String>> {
+    let Some(path) = &config.advisory_file else {
+        return Ok(HashMap::new());
+    };
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read supply-chain advisory file: {}", path))?;
+    let parsed: AdvisoryFile = if path.ends_with(".json") {
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as JSON advisories", path))?
+    } else {
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as TOML advisories", path))?
+    };
+
+    Ok(parsed
+        .advisories
+        .into_iter()
+        .map(|advisory| (advisory.name, advisory.reason))
+        .collect())
+}
+
+/// Builds an `Issue` in the `Supply Chain` category, with a rule id derived
+/// the same way `dependency_audit`/agent-reported issues get one.
+fn make_issue(
+    file_path: &str,
+    severity: Severity,
+    title: String,
+    description: String,
+    suggestion: String,
+) -> Issue {
+    let rule_id = Issue::derive_rule_id(CATEGORY, &title);
+    Issue {
+        file_path: file_path.to_string(),
+        start_line: 1,
+        end_line: None,
+        severity,
+        category: CATEGORY.to_string(),
+        title,
+        description,
+        suggestion,
+        code_snippet: None,
+        fix: None,
+        start_column: None,
+        end_column: None,
+        rule_id,
+        known: false,
+    }
+}
+
+fn advisory_issue(file_path: &str, name: &str, reason: &str) -> Issue {
+    make_issue(
+        file_path,
+        Severity::High,
+        format!("{}: flagged by local advisory list", name),
+        reason.to_string(),
+        format!("Review and replace the {} dependency.", name),
+    )
+}
+
+/// Checks `Cargo.toml`/`Cargo.lock`: wildcard (`"*"`) version requirements,
+/// git dependencies with no `rev`/`tag` (so they float on a branch), and
+/// manifest dependencies absent from the lockfile.
+fn audit_cargo(repo_path: &Path, advisories: &HashMap<String, String>) -> Vec<Issue> {
+    let manifest_path = repo_path.join("Cargo.toml");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = toml::from_str::<toml::Value>(&content) else {
+        warn!("Failed to parse Cargo.toml, skipping Cargo supply-chain checks");
+        return Vec::new();
+    };
+
+    let lockfile_path = repo_path.join("Cargo.lock");
+    let has_lockfile = lockfile_path.exists();
+    let lockfile_names: HashSet<String> = rustsec::Lockfile::load(&lockfile_path)
+        .map(|lockfile| {
+            lockfile
+                .packages
+                .iter()
+                .map(|package| package.name.as_str().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut issues = Vec::new();
+
+    for table_name in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(deps) = manifest.get(table_name).and_then(toml::Value::as_table) else {
+            continue;
+        };
+
+        for (name, spec) in deps {
+            if let Some(reason) = advisories.get(name) {
+                issues.push(advisory_issue("Cargo.toml", name, reason));
+            }
+
+            let wildcard_version = match spec {
+                toml::Value::String(version) => version.trim() == "*",
+                toml::Value::Table(fields) => fields
+                    .get("version")
+                    .and_then(toml::Value::as_str)
+                    .is_some_and(|version| version.trim() == "*"),
+                _ => false,
+            };
+            if wildcard_version {
+                issues.push(make_issue(
+                    "Cargo.toml",
+                    Severity::Medium,
+                    format!("{}: wildcard version requirement", name),
+                    format!(
+                        "{} = \"*\" accepts any published version, including breaking or \
+                         compromised releases.",
+                        name
+                    ),
+                    "Pin to a specific version range, e.g. \"1.2\" or \"=1.2.3\".".to_string(),
+                ));
+            }
+
+            if let toml::Value::Table(fields) = spec {
+                let has_git = fields.contains_key("git");
+                let is_pinned = fields.contains_key("rev") || fields.contains_key("tag");
+                if has_git && !is_pinned {
+                    let branch = fields
+                        .get("branch")
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or("its default branch");
+                    issues.push(make_issue(
+                        "Cargo.toml",
+                        Severity::High,
+                        format!("{}: git dependency pinned to a mutable branch", name),
+                        format!(
+                            "{} is pulled from git on {}, with no `rev`/`tag`, so its code can \
+                             change without a version bump.",
+                            name, branch
+                        ),
+                        "Pin to a specific commit with `rev = \"<sha>\"`, or a `tag`."
+                            .to_string(),
+                    ));
+                }
+            }
+
+            if has_lockfile && !lockfile_names.contains(name.as_str()) {
+                issues.push(make_issue(
+                    "Cargo.lock",
+                    Severity::Medium,
+                    format!("{}: declared in Cargo.toml but missing from Cargo.lock", name),
+                    "The manifest and lockfile have drifted; this dependency isn't pinned to a \
+                     resolved version."
+                        .to_string(),
+                    "Run `cargo update` (or `cargo generate-lockfile`) to resync Cargo.lock."
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Checks `package.json`/`package-lock.json`: loose semver ranges (`^`,
+/// `~`, `*`, `latest`) and manifest dependencies that don't turn up in the
+/// lockfile as a quoted name or a `node_modules/<name>` key (a cheap text
+/// check, not a real lockfile parse, same tradeoff
+/// `scanner::expand_with_dependents` makes).
+fn audit_npm(repo_path: &Path, advisories: &HashMap<String, String>) -> Vec<Issue> {
+    let manifest_path = repo_path.join("package.json");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content) else {
+        warn!("Failed to parse package.json, skipping npm supply-chain checks");
+        return Vec::new();
+    };
+
+    let lock_content = std::fs::read_to_string(repo_path.join("package-lock.json")).ok();
+
+    let mut issues = Vec::new();
+
+    for field in ["dependencies", "devDependencies"] {
+        let Some(deps) = manifest.get(field).and_then(serde_json::Value::as_object) else {
+            continue;
+        };
+
+        for (name, version_value) in deps {
+            let Some(version) = version_value.as_str() else {
+                continue;
+            };
+
+            if let Some(reason) = advisories.get(name) {
+                issues.push(advisory_issue("package.json", name, reason));
+            }
+
+            if is_loose_npm_range(version) {
+                issues.push(make_issue(
+                    "package.json",
+                    Severity::Low,
+                    format!("{}: loose version range \"{}\"", name, version),
+                    format!(
+                        "{} resolves to whatever matches \"{}\" at install time, so installs \
+                         aren't reproducible.",
+                        name, version
+                    ),
+                    "Pin to an exact version, and commit package-lock.json so `npm ci` installs \
+                     reproducibly."
+                        .to_string(),
+                ));
+            }
+
+            if let Some(ref lock_content) = lock_content {
+                if !lock_content.contains(&format!("\"{}\"", name))
+                    && !lock_content.contains(&format!("node_modules/{}\"", name))
+                {
+                    issues.push(make_issue(
+                        "package-lock.json",
+                        Severity::Medium,
+                        format!(
+                            "{}: declared in package.json but missing from package-lock.json",
+                            name
+                        ),
+                        "The manifest and lockfile have drifted.".to_string(),
+                        "Run `npm install` to resync package-lock.json.".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Whether an npm version range is "loose": a caret/tilde/range operator,
+/// a bare wildcard, or `"latest"`, any of which let installs pick up a
+/// version the author never tested against.
+fn is_loose_npm_range(version: &str) -> bool {
+    let version = version.trim();
+    version == "*"
+        || version == "latest"
+        || version.starts_with('^')
+        || version.starts_with('~')
+        || version.starts_with('>')
+        || version.starts_with('<')
+        || version.starts_with('x')
+}
+
+/// Checks `requirements.txt`: a dependency with no `==` exact pin.
+fn audit_pip(repo_path: &Path, advisories: &HashMap<String, String>) -> Vec<Issue> {
+    let Ok(content) = std::fs::read_to_string(repo_path.join("requirements.txt")) else {
+        return Vec::new();
+    };
+
+    let mut issues = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+            continue;
+        }
+
+        let name = line
+            .split(|c: char| "=<>!~; ".contains(c))
+            .next()
+            .unwrap_or(line)
+            .trim();
+        if name.is_empty() {
+            continue;
+        }
+
+        if let Some(reason) = advisories.get(name) {
+            issues.push(advisory_issue("requirements.txt", name, reason));
+        }
+
+        if !line.contains("==") {
+            issues.push(make_issue(
+                "requirements.txt",
+                Severity::Low,
+                format!("{}: unpinned requirement \"{}\"", name, line),
+                format!(
+                    "\"{}\" doesn't pin an exact version, so installs can silently pick up a \
+                     newer (possibly breaking or compromised) release.",
+                    line
+                ),
+                format!("Pin {} to an exact version, e.g. `{}==1.2.3`.", name, name),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// Checks `go.mod`: a missing `go.sum` (no checksum verification), and any
+/// `replace` directive pointing at a local filesystem path rather than a
+/// tagged module version.
+fn audit_go(repo_path: &Path) -> Vec<Issue> {
+    let Ok(content) = std::fs::read_to_string(repo_path.join("go.mod")) else {
+        return Vec::new();
+    };
+
+    let mut issues = Vec::new();
+
+    if !repo_path.join("go.sum").exists() {
+        issues.push(make_issue(
+            "go.mod",
+            Severity::Medium,
+            "go.sum is missing".to_string(),
+            "Without go.sum, module downloads aren't checksum-verified against a known-good \
+             hash."
+                .to_string(),
+            "Run `go mod tidy` to generate go.sum.".to_string(),
+        ));
+    }
+
+    for line in content.lines() {
+        let line = line.trim();
+        let Some(rest) = line.strip_prefix("replace ") else {
+            continue;
+        };
+        let Some((_, target)) = rest.split_once("=>") else {
+            continue;
+        };
+        let target = target.trim();
+        if target.starts_with("./") || target.starts_with("../") || target.starts_with('/') {
+            issues.push(make_issue(
+                "go.mod",
+                Severity::Low,
+                format!("replace directive points at a local path: \"{}\"", rest.trim()),
+                "A local `replace` directive won't resolve the same way in every build \
+                 environment (e.g. CI) and can mask drift from the real dependency."
+                    .to_string(),
+                "Remove the replace directive before release, or pin it to a tagged module \
+                 version instead."
+                    .to_string(),
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_advisories() -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    #[test]
+    fn test_cargo_flags_wildcard_version() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[dependencies]\nserde = \"*\"\n",
+        )
+        .unwrap();
+
+        let issues = audit_cargo(temp.path(), &no_advisories());
+        assert!(issues.iter().any(|i| i.title.contains("wildcard version")));
+    }
+
+    #[test]
+    fn test_cargo_flags_git_dependency_on_branch() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[dependencies]\nfoo = { git = \"https://example.com/foo\", branch = \"main\" }\n",
+        )
+        .unwrap();
+
+        let issues = audit_cargo(temp.path(), &no_advisories());
+        assert!(issues.iter().any(|i| i.title.contains("mutable branch")));
+    }
+
+    #[test]
+    fn test_cargo_does_not_flag_pinned_git_dependency() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[dependencies]\nfoo = { git = \"https://example.com/foo\", rev = \"abc123\" }\n",
+        )
+        .unwrap();
+
+        let issues = audit_cargo(temp.path(), &no_advisories());
+        assert!(!issues.iter().any(|i| i.title.contains("mutable branch")));
+    }
+
+    #[test]
+    fn test_cargo_flags_advisory_match() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[dependencies]\nbad-crate = \"1.0\"\n",
+        )
+        .unwrap();
+
+        let mut advisories = HashMap::new();
+        advisories.insert("bad-crate".to_string(), "known to exfiltrate env vars".to_string());
+
+        let issues = audit_cargo(temp.path(), &advisories);
+        assert!(issues.iter().any(|i| i.title.contains("flagged by local advisory list")));
+    }
+
+    #[test]
+    fn test_npm_flags_loose_ranges_and_lock_drift() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"dependencies": {"left-pad": "^1.0.0", "drifted": "1.0.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("package-lock.json"),
+            r#"{"packages": {"node_modules/left-pad": {"version": "1.0.0"}}}"#,
+        )
+        .unwrap();
+
+        let issues = audit_npm(temp.path(), &no_advisories());
+        assert!(issues.iter().any(|i| i.title.contains("left-pad") && i.title.contains("loose")));
+        assert!(issues.iter().any(|i| i.title.contains("drifted") && i.title.contains("missing from package-lock.json")));
+    }
+
+    #[test]
+    fn test_pip_flags_unpinned_requirement() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("requirements.txt"), "requests>=2.0\nflask==2.0.1\n")
+            .unwrap();
+
+        let issues = audit_pip(temp.path(), &no_advisories());
+        assert!(issues.iter().any(|i| i.title.contains("requests")));
+        assert!(!issues.iter().any(|i| i.title.contains("flask")));
+    }
+
+    #[test]
+    fn test_go_flags_missing_sum_and_local_replace() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("go.mod"),
+            "module example.com/foo\n\nrequire bar.com/baz v1.0.0\n\nreplace bar.com/baz => ../baz\n",
+        )
+        .unwrap();
+
+        let issues = audit_go(temp.path());
+        assert!(issues.iter().any(|i| i.title.contains("go.sum is missing")));
+        assert!(issues.iter().any(|i| i.title.contains("replace directive points at a local path")));
+    }
+
+    #[test]
+    fn test_missing_manifests_produce_no_issues() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(audit_cargo(temp.path(), &no_advisories()).is_empty());
+        assert!(audit_npm(temp.path(), &no_advisories()).is_empty());
+        assert!(audit_pip(temp.path(), &no_advisories()).is_empty());
+        assert!(audit_go(temp.path()).is_empty());
+    }
+}
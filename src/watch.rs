@@ -0,0 +1,463 @@
+//! `--watch`/`--watch-remote` modes: keep analyzing a repository as it
+//! changes instead of producing one report and exiting.
+//!
+//! `--watch` sets up a filesystem notifier on a local directory, debounces
+//! bursts of edits into a single batch, filters events through the
+//! scanner's extension/exclude rules, and re-analyzes only the changed
+//! files. `--watch-remote` does the analogous thing for a cloned remote
+//! repo: it polls `origin` via `repo::sync_repository` and re-analyzes the
+//! files that changed between the old and new HEAD. Both splice fresh
+//! per-file results into the previously computed set so the aggregated
+//! summary always reflects the whole project, not just the files touched
+//! since the last cycle.
+
+use crate::agent::CodeAnalysisAgent;
+use crate::analysis;
+use crate::models::{AnalyzedFile, Issue, IssueSummary};
+use crate::repo::{self, CloneOptions, CloneResult};
+use crate::scanner::FileScanner;
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// How long to wait after the first filesystem event before re-analyzing,
+/// so a burst of saves (e.g. a formatter rewriting several files) collapses
+/// into a single run instead of one per event.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Run the watch loop: analyze the whole repo once up front, then keep
+/// re-analyzing changed files as they're edited until the channel closes
+/// (e.g. Ctrl+C terminates the process).
+pub async fn run_watch(
+    repo_path: PathBuf,
+    scanner: FileScanner,
+    mut agent: CodeAnalysisAgent,
+) -> Result<()> {
+    info!("Starting watch mode on {}", repo_path.display());
+    println!(
+        "👀 Watching {} for changes (Ctrl+C to stop)...",
+        repo_path.display()
+    );
+
+    let mut analyzed: HashMap<String, AnalyzedFile> = HashMap::new();
+
+    // Initial full pass so the baseline report covers everything.
+    let mut initial_files: Vec<(String, String)> = scanner.collect_files()?.into_iter().collect();
+    initial_files.sort_by(|a, b| a.0.cmp(&b.0));
+    apply_analysis_cycle(&mut agent, initial_files, &mut analyzed, true).await?;
+    print_summary(&analyzed);
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            for path in event.paths {
+                let _ = tx.send(path);
+            }
+        }
+    })
+    .context("Failed to create filesystem watcher")?;
+
+    watcher
+        .watch(&repo_path, RecursiveMode::Recursive)
+        .context("Failed to watch directory")?;
+
+    loop {
+        let Some(first) = rx.recv().await else {
+            break;
+        };
+
+        let changed = debounce_batch(&mut rx, first).await;
+
+        let relative_paths = relevant_relative_paths(&changed, &repo_path, &scanner);
+        if relative_paths.is_empty() {
+            continue;
+        }
+
+        let files = read_changed_files(&repo_path, &relative_paths, &mut analyzed);
+        if files.is_empty() {
+            continue;
+        }
+
+        info!("Re-analyzing {} changed file(s)", files.len());
+        apply_analysis_cycle(&mut agent, files, &mut analyzed, false).await?;
+        print_summary(&analyzed);
+    }
+
+    Ok(())
+}
+
+/// Run the remote-watch loop: analyze `clone`'s working tree once up front,
+/// then poll `origin` every `poll_interval` via `repo::sync_repository` and
+/// re-analyze whatever changed between the old and new HEAD. Runs until the
+/// process is killed (e.g. Ctrl+C).
+pub async fn run_watch_remote(
+    clone: CloneResult,
+    clone_options: CloneOptions,
+    poll_interval: Duration,
+    scanner: FileScanner,
+    mut agent: CodeAnalysisAgent,
+) -> Result<()> {
+    let repo_path = clone.path.clone();
+    info!(
+        "Starting remote watch mode on {} (polling every {:?})",
+        repo_path.display(),
+        poll_interval
+    );
+    println!(
+        "👀 Watching {} for upstream commits, every {:?} (Ctrl+C to stop)...",
+        repo_path.display(),
+        poll_interval
+    );
+
+    let mut analyzed: HashMap<String, AnalyzedFile> = HashMap::new();
+
+    // Initial full pass so the baseline report covers everything.
+    let mut initial_files: Vec<(String, String)> = scanner.collect_files()?.into_iter().collect();
+    initial_files.sort_by(|a, b| a.0.cmp(&b.0));
+    apply_analysis_cycle(&mut agent, initial_files, &mut analyzed, true).await?;
+    print_summary(&analyzed);
+
+    loop {
+        tokio::time::sleep(poll_interval).await;
+
+        let event = repo::sync_repository(&clone, &clone_options)
+            .context("Failed to sync with origin")?;
+        if !event.changed {
+            continue;
+        }
+
+        info!(
+            "New commits detected: {} -> {}",
+            event.old_commit, event.new_commit
+        );
+
+        let changed_paths = repo::changed_files(&clone.repo, &event.old_commit, &event.new_commit)
+            .context("Failed to diff old and new HEAD")?;
+        let relative_paths: Vec<String> = changed_paths
+            .into_iter()
+            .filter(|p| scanner.matches(&repo_path.join(p)))
+            .collect();
+        if relative_paths.is_empty() {
+            continue;
+        }
+
+        let files = read_changed_files(&repo_path, &relative_paths, &mut analyzed);
+        if files.is_empty() {
+            continue;
+        }
+
+        info!("Re-analyzing {} changed file(s) after upstream fetch", files.len());
+        apply_analysis_cycle(&mut agent, files, &mut analyzed, false).await?;
+        print_summary(&analyzed);
+    }
+}
+
+/// Collects `first` plus any further events that arrive on `rx` within
+/// `DEBOUNCE` of the last one seen, so a burst of saves (e.g. a formatter
+/// rewriting several files) collapses into a single batch instead of one
+/// re-analysis per event.
+async fn debounce_batch(rx: &mut mpsc::UnboundedReceiver<PathBuf>, first: PathBuf) -> HashSet<PathBuf> {
+    let mut changed: HashSet<PathBuf> = HashSet::new();
+    changed.insert(first);
+
+    let deadline = tokio::time::sleep(DEBOUNCE);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            maybe_path = rx.recv() => match maybe_path {
+                Some(path) => { changed.insert(path); }
+                None => break,
+            },
+        }
+    }
+    changed
+}
+
+/// Filter raw filesystem-event paths down to files the scanner actually
+/// cares about, returning paths relative to the repo root.
+fn relevant_relative_paths(
+    changed: &HashSet<PathBuf>,
+    repo_path: &Path,
+    scanner: &FileScanner,
+) -> Vec<String> {
+    changed
+        .iter()
+        .filter(|p| p.is_file() && scanner.matches(p))
+        .filter_map(|p| p.strip_prefix(repo_path).ok())
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+/// Read the current contents of each changed file. A file that can't be
+/// read (most often because it was deleted since the event fired) is
+/// dropped from the tracked analysis set instead of being retried.
+fn read_changed_files(
+    repo_path: &Path,
+    relative_paths: &[String],
+    analyzed: &mut HashMap<String, AnalyzedFile>,
+) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+
+    for path in relative_paths {
+        match std::fs::read_to_string(repo_path.join(path)) {
+            Ok(content) => files.push((path.clone(), content)),
+            Err(e) => {
+                warn!("Dropping {} from watch set: {}", path, e);
+                analyzed.remove(path);
+            }
+        }
+    }
+
+    files
+}
+
+/// Re-analyze `files`, splice the results into `analyzed`, and print the
+/// per-file delta (new/resolved issues) against what was there before.
+///
+/// `initial` selects the full-repo baseline pass (`run_analysis`, so
+/// tool-calling mode gets its usual open-ended "explore the repo" prompt)
+/// versus an incremental re-check of just `files` (`rerun_changed_files`,
+/// which also invalidates any stale cached results for them).
+async fn apply_analysis_cycle(
+    agent: &mut CodeAnalysisAgent,
+    files: Vec<(String, String)>,
+    analyzed: &mut HashMap<String, AnalyzedFile>,
+    initial: bool,
+) -> Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let touched: Vec<(String, usize)> = files
+        .iter()
+        .map(|(path, content)| (path.clone(), content.lines().count()))
+        .collect();
+
+    let raw_issues = if initial {
+        agent.run_analysis().await?
+    } else {
+        let changed_paths: Vec<String> = files.into_iter().map(|(path, _)| path).collect();
+        agent.rerun_changed_files(&changed_paths).await?
+    };
+    let issues: Vec<Issue> = raw_issues.into_iter().map(Issue::from).collect();
+
+    let grouped = analysis::group_by_file(&issues);
+
+    for (path, line_count) in touched {
+        let file_issues = grouped.get(&path).cloned().unwrap_or_default();
+        splice_file_result(path, line_count, file_issues, analyzed);
+    }
+
+    Ok(())
+}
+
+/// Diffs `file_issues` against whatever was previously tracked for `path` in
+/// `analyzed`, prints the delta (new/resolved issue titles), and splices the
+/// fresh result in, replacing the old entry.
+fn splice_file_result(
+    path: String,
+    line_count: usize,
+    file_issues: Vec<Issue>,
+    analyzed: &mut HashMap<String, AnalyzedFile>,
+) {
+    let before_titles: HashSet<String> = analyzed
+        .get(&path)
+        .map(|f| f.issues.iter().map(|i| i.title.clone()).collect())
+        .unwrap_or_default();
+    let after_titles: HashSet<String> = file_issues.iter().map(|i| i.title.clone()).collect();
+
+    print_delta(&path, &before_titles, &after_titles);
+
+    analyzed.insert(
+        path.clone(),
+        AnalyzedFile {
+            path,
+            language: "Unknown".to_string(),
+            line_count,
+            issues: file_issues,
+            analysis_successful: true,
+            error: None,
+        },
+    );
+}
+
+/// Print new and resolved issue titles for one file, if anything changed.
+fn print_delta(path: &str, before: &HashSet<String>, after: &HashSet<String>) {
+    let new_titles: Vec<&String> = after.difference(before).collect();
+    let resolved_titles: Vec<&String> = before.difference(after).collect();
+
+    if new_titles.is_empty() && resolved_titles.is_empty() {
+        return;
+    }
+
+    println!("   📄 {}", path);
+    for title in new_titles {
+        println!("      + {}", title);
+    }
+    for title in resolved_titles {
+        println!("      - {} (resolved)", title);
+    }
+}
+
+/// Print the aggregated severity summary across all tracked files.
+fn print_summary(analyzed: &HashMap<String, AnalyzedFile>) {
+    let all_issues: Vec<Issue> = analyzed.values().flat_map(|f| f.issues.clone()).collect();
+    let summary = IssueSummary::from_issues(&all_issues);
+
+    println!(
+        "   Total: {} issues (🔴 {} 🟠 {} 🟡 {} 🟢 {}) across {} file(s)\n",
+        summary.total,
+        summary.critical,
+        summary.high,
+        summary.medium,
+        summary.low,
+        analyzed.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+    use crate::scanner::ScanConfig;
+    use tempfile::TempDir;
+
+    fn test_issue(title: &str) -> Issue {
+        Issue {
+            file_path: "test.rs".to_string(),
+            start_line: 1,
+            end_line: None,
+            severity: Severity::Medium,
+            category: "bug".to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            suggestion: String::new(),
+            code_snippet: None,
+            fix: None,
+            start_column: None,
+            end_column: None,
+            rule_id: String::new(),
+            known: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debounce_batch_collapses_a_burst_into_one_set() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+        tx.send(PathBuf::from("a.rs")).unwrap();
+        tx.send(PathBuf::from("b.rs")).unwrap();
+        drop(tx);
+
+        let batch = debounce_batch(&mut rx, PathBuf::from("first.rs")).await;
+
+        assert_eq!(batch.len(), 3);
+        assert!(batch.contains(&PathBuf::from("first.rs")));
+        assert!(batch.contains(&PathBuf::from("a.rs")));
+        assert!(batch.contains(&PathBuf::from("b.rs")));
+    }
+
+    #[tokio::test]
+    async fn test_debounce_batch_waits_out_the_debounce_window_when_idle() {
+        let (_tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+
+        let batch = debounce_batch(&mut rx, PathBuf::from("only.rs")).await;
+
+        assert_eq!(batch, HashSet::from([PathBuf::from("only.rs")]));
+    }
+
+    #[test]
+    fn test_relevant_relative_paths_filters_and_relativizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        std::fs::write(repo_path.join("kept.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir(repo_path.join("src")).unwrap();
+        std::fs::write(repo_path.join("src").join("lib.rs"), "").unwrap();
+
+        let scanner = FileScanner::new(repo_path.clone(), ScanConfig::default());
+        let changed = HashSet::from([
+            repo_path.join("kept.rs"),
+            repo_path.join("src").join("lib.rs"),
+            repo_path.join("does-not-exist.rs"),
+        ]);
+
+        let mut relative = relevant_relative_paths(&changed, &repo_path, &scanner);
+        relative.sort();
+
+        assert_eq!(relative, vec!["kept.rs".to_string(), "src/lib.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_read_changed_files_drops_unreadable_files_from_analyzed() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_path_buf();
+        std::fs::write(repo_path.join("a.rs"), "contents").unwrap();
+
+        let mut analyzed: HashMap<String, AnalyzedFile> = HashMap::new();
+        analyzed.insert(
+            "missing.rs".to_string(),
+            AnalyzedFile {
+                path: "missing.rs".to_string(),
+                language: "Unknown".to_string(),
+                line_count: 1,
+                issues: vec![test_issue("stale")],
+                analysis_successful: true,
+                error: None,
+            },
+        );
+
+        let relative_paths = vec!["a.rs".to_string(), "missing.rs".to_string()];
+        let files = read_changed_files(&repo_path, &relative_paths, &mut analyzed);
+
+        assert_eq!(files, vec![("a.rs".to_string(), "contents".to_string())]);
+        assert!(!analyzed.contains_key("missing.rs"));
+    }
+
+    #[test]
+    fn test_splice_file_result_replaces_previous_entry_for_the_path() {
+        let mut analyzed: HashMap<String, AnalyzedFile> = HashMap::new();
+        analyzed.insert(
+            "a.rs".to_string(),
+            AnalyzedFile {
+                path: "a.rs".to_string(),
+                language: "Unknown".to_string(),
+                line_count: 5,
+                issues: vec![test_issue("old issue")],
+                analysis_successful: true,
+                error: None,
+            },
+        );
+
+        splice_file_result(
+            "a.rs".to_string(),
+            10,
+            vec![test_issue("new issue")],
+            &mut analyzed,
+        );
+
+        let updated = analyzed.get("a.rs").unwrap();
+        assert_eq!(updated.line_count, 10);
+        assert_eq!(updated.issues.len(), 1);
+        assert_eq!(updated.issues[0].title, "new issue");
+    }
+
+    #[test]
+    fn test_print_delta_computes_new_and_resolved_titles() {
+        let before = HashSet::from(["fixed me".to_string(), "still here".to_string()]);
+        let after = HashSet::from(["still here".to_string(), "brand new".to_string()]);
+
+        // print_delta only prints; this just exercises it for panics and
+        // documents the diff it's computing, mirroring the set-difference
+        // logic the real watch loop relies on.
+        print_delta("a.rs", &before, &after);
+
+        let new_titles: HashSet<&String> = after.difference(&before).collect();
+        let resolved_titles: HashSet<&String> = before.difference(&after).collect();
+        assert_eq!(new_titles, HashSet::from([&"brand new".to_string()]));
+        assert_eq!(resolved_titles, HashSet::from([&"fixed me".to_string()]));
+    }
+}